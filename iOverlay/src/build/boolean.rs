@@ -202,6 +202,21 @@ impl InclusionFilterStrategy for XorFilter {
     }
 }
 
+impl OverlayRule {
+    #[inline(always)]
+    pub(crate) fn is_included(&self, fill: SegmentFill) -> bool {
+        match self {
+            OverlayRule::Subject => fill.is_subject(),
+            OverlayRule::Clip => fill.is_clip(),
+            OverlayRule::Intersect => fill.is_intersect(),
+            OverlayRule::Union => fill.is_union(),
+            OverlayRule::Difference => fill.is_difference(),
+            OverlayRule::InverseDifference => fill.is_inverse_difference(),
+            OverlayRule::Xor => fill.is_xor(),
+        }
+    }
+}
+
 trait BooleanFillFilter {
     fn is_subject(&self) -> bool;
     fn is_clip(&self) -> bool;
@@ -52,22 +52,27 @@ impl<C: WindingCount, N: GraphNode> GraphBuilder<C, N> {
 
     #[inline]
     pub(super) fn build_fills_with_strategy<F: FillStrategy<C>>(&mut self, solver: &Solver, segments: &[Segment<C>]) {
+        self.build_fills_with(solver, segments, F::add_and_fill);
+    }
+
+    #[inline]
+    pub(super) fn build_fills_with<F: FnMut(C, C) -> (C, SegmentFill)>(&mut self, solver: &Solver, segments: &[Segment<C>], add_and_fill: F) {
         let count = segments.len();
         if solver.is_list_fill(segments) {
             let capacity = count.log2_sqrt().max(4) * 2;
             let mut list = self.take_scan_list(capacity);
-            self.build_fills::<F, KeyExpList<VSegment, i32, C>>(&mut list, segments);
+            self.build_fills::<F, KeyExpList<VSegment, i32, C>>(&mut list, segments, add_and_fill);
             self.list = Some(list);
         } else {
             let capacity = count.log2_sqrt().max(8);
             let mut tree = self.take_scan_tree(capacity);
-            self.build_fills::<F, KeyExpTree<VSegment, i32, C>>(&mut tree, segments);
+            self.build_fills::<F, KeyExpTree<VSegment, i32, C>>(&mut tree, segments, add_and_fill);
             self.tree = Some(tree);
         }
     }
 
     #[inline]
-    fn build_fills<F: FillStrategy<C>, S: KeyExpCollection<VSegment, i32, C>>(&mut self, scan_list: &mut S, segments: &[Segment<C>]) {
+    fn build_fills<F: FnMut(C, C) -> (C, SegmentFill), S: KeyExpCollection<VSegment, i32, C>>(&mut self, scan_list: &mut S, segments: &[Segment<C>], mut add_and_fill: F) {
         let mut node = Vec::with_capacity(4);
 
         let n = segments.len();
@@ -103,7 +108,7 @@ impl<C: WindingCount, N: GraphNode> GraphBuilder<C, N> {
 
             for se in node.iter() {
                 let sid = unsafe { segments.get_unchecked(se.index) };
-                (sum_count, fill) = F::add_and_fill(sid.count, sum_count);
+                (sum_count, fill) = add_and_fill(sid.count, sum_count);
                 unsafe { *self.fills.get_unchecked_mut(se.index) = fill }
                 if sid.x_segment.is_not_vertical() {
                     scan_list.insert(sid.x_segment.into(), sum_count, p.x);
@@ -0,0 +1,44 @@
+use crate::build::builder::{GraphBuilder, InclusionFilterStrategy};
+use crate::core::graph::{OverlayGraph, OverlayNode};
+use crate::core::overlay::IntOverlayOptions;
+use crate::core::solver::Solver;
+use crate::segm::layer::{LayerCounts, ShapeCountLayer};
+use crate::segm::segment::{Segment, SegmentFill, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP};
+
+impl GraphBuilder<ShapeCountLayer, OverlayNode> {
+    /// The predicate result for the region above a segment is stored as `SUBJ_TOP`
+    /// and for the region below as `SUBJ_BOTTOM`, so the graph can be extracted as `Subject`.
+    #[inline]
+    pub(crate) fn build_layer_overlay<P: Fn(&[i32]) -> bool>(&mut self,
+                                                            predicate: P,
+                                                            counts: &mut LayerCounts,
+                                                            options: IntOverlayOptions,
+                                                            solver: &Solver,
+                                                            segments: &[Segment<ShapeCountLayer>],
+    ) -> OverlayGraph<'_> {
+        self.build_fills_with(solver, segments, |this: ShapeCountLayer, bot: ShapeCountLayer| {
+            let top = counts.add(bot, this);
+            let subj_top = counts.matches(top, &predicate) as SegmentFill;
+            let subj_bot = counts.matches(bot, &predicate) as SegmentFill;
+
+            (top, subj_top | (subj_bot << 1))
+        });
+        self.build_links_by_filter::<LayerFilter>(segments);
+        self.build_nodes_and_connect_links(solver);
+        OverlayGraph {
+            nodes: &self.nodes,
+            links: &self.links,
+            options,
+        }
+    }
+}
+
+struct LayerFilter;
+
+impl InclusionFilterStrategy for LayerFilter {
+    #[inline(always)]
+    fn is_included(fill: SegmentFill) -> bool {
+        let subj = fill & SUBJ_BOTH;
+        subj == SUBJ_TOP || subj == SUBJ_BOTTOM
+    }
+}
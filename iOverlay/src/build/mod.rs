@@ -1,6 +1,7 @@
 pub(crate) mod builder;
 pub(crate) mod boolean;
 pub(crate) mod string;
+pub(crate) mod layer;
 mod graph;
 mod offset;
 mod util;
\ No newline at end of file
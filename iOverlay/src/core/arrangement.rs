@@ -0,0 +1,221 @@
+//! This module extracts the full planar subdivision (arrangement) from an `OverlayGraph`.
+//! Every face of the subdivision is reported with its subject/clip fill state, its boundary
+//! contours and the faces it shares an edge with, so a single overlay run can feed
+//! map-coloring, coverage reports or several boolean results at once.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::bind::segment::{ContourIndex, IdSegment, IdSegments};
+use crate::bind::solver::{LeftBottomSegment, ShapeBinder, SortByAngle};
+use crate::core::graph::OverlayGraph;
+use crate::core::link::OverlayLink;
+use crate::core::overlay::ContourDirection;
+use crate::geom::face::{frame, FaceRings, PlanarGraph};
+use crate::segm::segment::{SegmentFill, BOTH_BOTTOM, BOTH_TOP, CLIP_TOP, SUBJ_TOP};
+use i_float::int::point::IntPoint;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::shape::IntContour;
+use i_shape::int::simple::Simplify;
+
+/// A single face of the planar subdivision.
+#[derive(Debug, Clone)]
+pub struct Face {
+    /// Fill state of the face expressed with the top bits: `NONE`, `SUBJ_TOP`, `CLIP_TOP` or `BOTH_TOP`.
+    pub fill: SegmentFill,
+    /// Outer boundary of the face. Empty for the unbounded face.
+    pub outer: IntContour,
+    /// Inner boundaries of the face.
+    pub holes: Vec<IntContour>,
+    /// Sorted indices of the faces that share at least one edge with this face.
+    pub neighbors: Vec<usize>,
+}
+
+impl Face {
+    #[inline]
+    pub fn is_bounded(&self) -> bool {
+        !self.outer.is_empty()
+    }
+
+    #[inline]
+    pub fn is_subject(&self) -> bool {
+        self.fill & SUBJ_TOP != 0
+    }
+
+    #[inline]
+    pub fn is_clip(&self) -> bool {
+        self.fill & CLIP_TOP != 0
+    }
+}
+
+/// The planar subdivision produced by [`OverlayGraph::extract_arrangement`].
+///
+/// The face at index [`Arrangement::UNBOUNDED`] is the unbounded (outer) face, its `outer` contour is empty.
+#[derive(Debug, Clone)]
+pub struct Arrangement {
+    pub faces: Vec<Face>,
+}
+
+impl Arrangement {
+    pub const UNBOUNDED: usize = 0;
+}
+
+impl OverlayGraph<'_> {
+    /// Extracts the full planar subdivision from the overlay graph.
+    ///
+    /// The graph must contain all links, i.e. be created by `Overlay::build_graph_view`.
+    /// Edges that have the same fill state on both sides do not split faces and are ignored.
+    /// - Returns: An `Arrangement`, where every face has its fill state, its outer and hole contours and the list of adjacent faces.
+    ///
+    /// Note: Outer boundaries follow `options.output_direction` and holes have the opposite order.
+    pub fn extract_arrangement(&self) -> Arrangement {
+        let is_active: Vec<bool> = self.links.iter().map(|link| link.top_state() != link.bottom_state()).collect();
+        let face_rings = FaceRings::trace(self, &is_active);
+        let cycle_for_half_edge = face_rings.ring_for_half;
+        let cycles: Vec<Cycle> = face_rings
+            .rings
+            .iter()
+            .map(|ring| {
+                let link = &self.links[ring[0] >> 1];
+                let fill = if ring[0] & 1 == 0 { link.top_state() } else { link.bottom_state() };
+                let contour = ring.iter().map(|&half_edge| self.half_start(half_edge).1).collect();
+                Cycle { fill, contour }
+            })
+            .collect();
+
+        // counterclockwise cycles are outer boundaries, clockwise cycles are holes
+        let mut faces = vec![Face {
+            fill: 0,
+            outer: Vec::new(),
+            holes: Vec::new(),
+            neighbors: Vec::new(),
+        }];
+        let mut face_for_cycle = vec![0; cycles.len()];
+        let mut is_outer = vec![false; cycles.len()];
+        let mut hole_cycles = Vec::new();
+        let mut outer_contours = vec![frame(self.links.iter().flat_map(|link| [link.a.point, link.b.point]))];
+
+        for (i, cycle) in cycles.iter().enumerate() {
+            if cycle.contour.unsafe_area() < 0 {
+                is_outer[i] = true;
+                face_for_cycle[i] = faces.len();
+                faces.push(Face {
+                    fill: cycle.fill,
+                    outer: Vec::new(),
+                    holes: Vec::new(),
+                    neighbors: Vec::new(),
+                });
+                outer_contours.push(cycle.contour.clone());
+            } else {
+                hole_cycles.push(i);
+            }
+        }
+
+        let parents = Self::bind_holes(&outer_contours, &hole_cycles, &cycles);
+        for (&cycle_index, &parent) in hole_cycles.iter().zip(parents.iter()) {
+            face_for_cycle[cycle_index] = parent;
+        }
+
+        // adjacency across every active link
+        for (i, &active) in is_active.iter().enumerate() {
+            if !active {
+                continue;
+            }
+            let f0 = face_for_cycle[cycle_for_half_edge[2 * i]];
+            let f1 = face_for_cycle[cycle_for_half_edge[2 * i + 1]];
+            if f0 != f1 {
+                faces[f0].neighbors.push(f1);
+                faces[f1].neighbors.push(f0);
+            }
+        }
+
+        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
+        for (i, cycle) in cycles.into_iter().enumerate() {
+            let mut contour = cycle.contour;
+            if !self.options.preserve_output_collinear {
+                contour.simplify_contour();
+            }
+            if clockwise {
+                contour.reverse();
+            }
+            let face = &mut faces[face_for_cycle[i]];
+            if is_outer[i] {
+                face.outer = contour;
+            } else {
+                face.holes.push(contour);
+            }
+        }
+
+        for face in faces.iter_mut() {
+            face.neighbors.sort_unstable();
+            face.neighbors.dedup();
+        }
+
+        Arrangement { faces }
+    }
+
+    fn bind_holes(outers: &[IntContour], hole_cycles: &[usize], cycles: &[Cycle]) -> Vec<usize> {
+        if hole_cycles.is_empty() {
+            return Vec::new();
+        }
+
+        let mut anchors: Vec<_> = hole_cycles
+            .iter()
+            .enumerate()
+            .map(|(id, &cycle_index)| IdSegment::with_segment(
+                ContourIndex::new_hole(id),
+                cycles[cycle_index].contour.left_bottom_segment(),
+            ))
+            .collect();
+        anchors.sort_by_a_then_by_angle();
+
+        let x_min = anchors[0].v_segment.a.x;
+        let x_max = anchors[anchors.len() - 1].v_segment.a.x;
+
+        let mut segments = Vec::new();
+        for (i, outer) in outers.iter().enumerate() {
+            outer.append_id_segments(&mut segments, ContourIndex::new_shape(i), x_min, x_max, false);
+        }
+        for (i, &cycle_index) in hole_cycles.iter().enumerate() {
+            cycles[cycle_index].contour.append_id_segments(&mut segments, ContourIndex::new_hole(i), x_min, x_max, false);
+        }
+        segments.sort_by_a_then_by_angle();
+
+        ShapeBinder::bind(outers.len(), anchors, segments).parent_for_child
+    }
+}
+
+struct Cycle {
+    fill: SegmentFill,
+    contour: IntContour,
+}
+
+impl OverlayLink {
+    #[inline(always)]
+    fn top_state(&self) -> SegmentFill {
+        self.fill & BOTH_TOP
+    }
+
+    #[inline(always)]
+    fn bottom_state(&self) -> SegmentFill {
+        (self.fill & BOTH_BOTTOM) >> 1
+    }
+}
+
+impl PlanarGraph for OverlayGraph<'_> {
+    #[inline]
+    fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edges_count(&self) -> usize {
+        self.links.len()
+    }
+
+    #[inline]
+    fn half_start(&self, half: usize) -> (usize, IntPoint) {
+        let link = &self.links[half >> 1];
+        let end = if half & 1 == 0 { link.a } else { link.b };
+        (end.id, end.point)
+    }
+}
@@ -0,0 +1,126 @@
+//! This module allows defining overlay rules beyond the built-in `OverlayRule` variants.
+//! A custom rule is a pair of 16-entry truth tables over the `SegmentFill` bits of a link
+//! (`SUBJ_TOP`, `SUBJ_BOTTOM`, `CLIP_TOP`, `CLIP_BOTTOM`): one decides whether the link is a part
+//! of the result boundary, the other on which side of the link the result lies.
+
+use crate::core::overlay_rule::OverlayRule;
+use crate::segm::segment::{SegmentFill, CLIP_BOTTOM, CLIP_TOP, SUBJ_BOTTOM, SUBJ_TOP};
+
+/// A user-defined overlay rule.
+///
+/// Every link of the result must separate a region that belongs to the result from one that does not.
+/// Rules created with [`CustomOverlayRule::with_region_predicate`] always satisfy this.
+/// Raw truth tables are the caller's responsibility, an inconsistent table produces broken contours.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::custom_rule::CustomOverlayRule;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay::Overlay;
+///
+/// let subj = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+/// let clip = [IntPoint::new(5, 0), IntPoint::new(5, 10), IntPoint::new(15, 10), IntPoint::new(15, 0)];
+///
+/// // everything covered by exactly one of the shapes (Xor)
+/// let rule = CustomOverlayRule::with_region_predicate(|subj, clip| subj != clip);
+/// let shapes = Overlay::with_contour(&subj, &clip).overlay_custom(rule, FillRule::NonZero);
+///
+/// assert_eq!(shapes.len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomOverlayRule {
+    included: u16,
+    fill_top: u16,
+}
+
+impl CustomOverlayRule {
+    /// Creates a rule from raw truth tables indexed by `SegmentFill`.
+    /// - `included`: The bit `fill` is set if a link with this fill is a part of the result boundary.
+    /// - `fill_top`: The bit `fill` is set if the result is above (on the left side of) a link with this fill.
+    #[inline]
+    pub const fn with_truth_table(included: u16, fill_top: u16) -> Self {
+        Self { included, fill_top }
+    }
+
+    /// Creates a rule from predicates over `SegmentFill`.
+    /// - `is_included`: Returns `true` if a link with this fill is a part of the result boundary.
+    /// - `is_fill_top`: Returns `true` if the result is above (on the left side of) a link with this fill.
+    pub fn with_fill_predicate<I, T>(is_included: I, is_fill_top: T) -> Self
+    where
+        I: Fn(SegmentFill) -> bool,
+        T: Fn(SegmentFill) -> bool,
+    {
+        let mut included = 0;
+        let mut fill_top = 0;
+        for fill in 0..16 {
+            if is_included(fill) {
+                included |= 1 << fill;
+            }
+            if is_fill_top(fill) {
+                fill_top |= 1 << fill;
+            }
+        }
+        Self { included, fill_top }
+    }
+
+    /// Creates a rule from a predicate over regions.
+    /// - `is_filled`: Returns `true` if a region covered (`true`) or not covered (`false`) by the subject and the clip belongs to the result.
+    pub fn with_region_predicate<F: Fn(bool, bool) -> bool>(is_filled: F) -> Self {
+        Self::with_fill_predicate(
+            |fill| {
+                let top = is_filled(fill & SUBJ_TOP != 0, fill & CLIP_TOP != 0);
+                let bottom = is_filled(fill & SUBJ_BOTTOM != 0, fill & CLIP_BOTTOM != 0);
+                top != bottom
+            },
+            |fill| is_filled(fill & SUBJ_TOP != 0, fill & CLIP_TOP != 0),
+        )
+    }
+
+    #[inline(always)]
+    pub fn is_included(&self, fill: SegmentFill) -> bool {
+        self.included & (1 << fill) != 0
+    }
+
+    #[inline(always)]
+    pub fn is_fill_top(&self, fill: SegmentFill) -> bool {
+        self.fill_top & (1 << fill) != 0
+    }
+}
+
+impl From<OverlayRule> for CustomOverlayRule {
+    fn from(rule: OverlayRule) -> Self {
+        Self::with_fill_predicate(|fill| rule.is_included(fill), |fill| rule.is_fill_top(fill))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::custom_rule::CustomOverlayRule;
+    use crate::core::overlay_rule::OverlayRule;
+
+    type RegionPredicate = fn(bool, bool) -> bool;
+
+    #[test]
+    fn test_built_in_rules() {
+        let rules: [(OverlayRule, RegionPredicate); 7] = [
+            (OverlayRule::Subject, |s, _| s),
+            (OverlayRule::Clip, |_, c| c),
+            (OverlayRule::Intersect, |s, c| s && c),
+            (OverlayRule::Union, |s, c| s || c),
+            (OverlayRule::Difference, |s, c| s && !c),
+            (OverlayRule::InverseDifference, |s, c| !s && c),
+            (OverlayRule::Xor, |s, c| s != c),
+        ];
+
+        for (rule, predicate) in rules {
+            let built_in = CustomOverlayRule::from(rule);
+            let custom = CustomOverlayRule::with_region_predicate(predicate);
+            for fill in 0..16 {
+                assert_eq!(built_in.is_included(fill), custom.is_included(fill), "{} {}", rule, fill);
+                if custom.is_included(fill) {
+                    assert_eq!(built_in.is_fill_top(fill), custom.is_fill_top(fill), "{} {}", rule, fill);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! This module provides distance queries between geometries: the minimum distance with the pair of nearest points,
+//! the directed and symmetric discrete Hausdorff distance and a "within distance" predicate.
+//! A `DistanceQuery` is built once from shapes (areas) or paths (open lines) and can be compared with many others.
+
+use alloc::vec::Vec;
+use i_float::float::number::FloatNumber;
+use i_float::float::point::FloatPoint;
+use i_float::float::rect::FloatRect;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::IntShape;
+
+/// A pair of points, one on each geometry, and the distance between them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistancePair<P, T> {
+    pub distance: T,
+    /// The point on the first geometry.
+    pub a: P,
+    /// The point on the second geometry.
+    pub b: P,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DistanceSegment {
+    a: FloatPoint<f64>,
+    b: FloatPoint<f64>,
+    min_x: f64,
+    max_x: f64,
+    min_y: f64,
+    max_y: f64,
+}
+
+/// A geometry prepared for distance queries.
+///
+/// Shapes are areas: a point inside a shape has zero distance to it, so two shapes have zero distance
+/// if they overlap or one contains the other. Paths are open lines, only their edges count.
+/// Distances are computed in `f64`, nearest points generally do not lie on the integer grid.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::distance::DistanceQuery;
+///
+/// let square = vec![vec![vec![
+///     IntPoint::new(0, 0),
+///     IntPoint::new(10, 0),
+///     IntPoint::new(10, 10),
+///     IntPoint::new(0, 10),
+/// ]]];
+/// let line = vec![vec![IntPoint::new(13, 5), IntPoint::new(20, 5)]];
+///
+/// let a = DistanceQuery::with_shapes(&square);
+/// let b = DistanceQuery::with_paths(&line);
+///
+/// let nearest = a.distance(&b).unwrap();
+/// assert_eq!(nearest.distance, 3.0);
+/// assert_eq!((nearest.a.x, nearest.a.y), (10.0, 5.0));
+/// assert!(a.is_within_distance(&b, 3.0));
+/// assert!(!a.is_within_distance(&b, 2.9));
+/// ```
+#[derive(Clone)]
+pub struct DistanceQuery {
+    is_area: bool,
+    // sorted by `min_x`
+    segments: Vec<DistanceSegment>,
+    max_width: f64,
+    vertices: Vec<FloatPoint<f64>>,
+    // the first vertex of every path
+    starts: Vec<usize>,
+    rect: Option<FloatRect<f64>>,
+}
+
+impl DistanceQuery {
+    /// Creates a query for areas.
+    /// - `shapes`: Shapes with an outer contour and holes, the filled area is found by the even-odd rule.
+    pub fn with_shapes(shapes: &[IntShape]) -> Self {
+        Self::new(shapes.iter().flatten().map(|contour| contour.iter().map(to_float)), true)
+    }
+
+    /// Creates a query for open lines.
+    /// - `paths`: Paths, the last point is not connected to the first one.
+    pub fn with_paths(paths: &[IntPath]) -> Self {
+        Self::new(paths.iter().map(|path| path.iter().map(to_float)), false)
+    }
+
+    /// Creates a query from paths of points.
+    /// - `paths`: Closed contours if `is_area` is `true`, otherwise open lines.
+    pub(crate) fn new<I, J>(paths: I, is_area: bool) -> Self
+    where
+        I: Iterator<Item = J>,
+        J: Iterator<Item = FloatPoint<f64>>,
+    {
+        let mut segments = Vec::new();
+        let mut vertices = Vec::new();
+        let mut starts = Vec::new();
+        for path in paths {
+            let start = vertices.len();
+            vertices.extend(path);
+            let points = &vertices[start..];
+            if !points.is_empty() {
+                starts.push(start);
+            }
+            match points.len() {
+                0 => {}
+                1 => segments.push(DistanceSegment::new(points[0], points[0])),
+                n => {
+                    for w in points.windows(2) {
+                        segments.push(DistanceSegment::new(w[0], w[1]));
+                    }
+                    if is_area {
+                        segments.push(DistanceSegment::new(points[n - 1], points[0]));
+                    }
+                }
+            }
+        }
+
+        segments.sort_unstable_by(|s0, s1| s0.min_x.total_cmp(&s1.min_x));
+        let max_width = segments.iter().fold(0.0, |w, s| f64::max(w, s.max_x - s.min_x));
+        let rect = FloatRect::with_points(&vertices);
+
+        Self { is_area, segments, max_width, vertices, starts, rect }
+    }
+
+    /// Returns `true` if the query has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Returns the minimum distance and the nearest points, `a` is on this geometry and `b` is on the other one.
+    /// Returns `None` if any geometry is empty.
+    pub fn distance(&self, other: &Self) -> Option<DistancePair<FloatPoint<f64>, f64>> {
+        self.nearest_pair(other, f64::INFINITY, 0.0)
+    }
+
+    /// Returns `true` if the distance between the geometries does not exceed `max_distance`.
+    /// Stops at the first pair of close enough points.
+    pub fn is_within_distance(&self, other: &Self, max_distance: f64) -> bool {
+        let (Some(r0), Some(r1)) = (&self.rect, &other.rect) else {
+            return false;
+        };
+        if rect_sqr_distance(r0, r1) > max_distance * max_distance {
+            return false;
+        }
+        self.nearest_pair(other, max_distance, max_distance).is_some()
+    }
+
+    /// Returns the nearest point of this geometry to `p`. It is `p` itself if `p` lies inside the area.
+    /// Returns `None` if the geometry is empty.
+    pub fn nearest_point(&self, p: FloatPoint<f64>) -> Option<DistancePair<FloatPoint<f64>, f64>> {
+        if self.is_empty() {
+            return None;
+        }
+        if self.contains(p) {
+            return Some(DistancePair { distance: 0.0, a: p, b: p });
+        }
+
+        // a first guess from the segment next to the point
+        let index = self.segments.partition_point(|s| s.min_x < p.x).min(self.segments.len() - 1);
+        let (mut best, mut nearest) = point_segment(p, &self.segments[index]);
+        let mut limit = FloatNumber::sqrt(best);
+
+        let start = self.segments.partition_point(|s| s.min_x < p.x - limit - self.max_width);
+        for s in self.segments[start..].iter() {
+            if s.min_x > p.x + limit {
+                break;
+            }
+            if s.max_x < p.x - limit || s.min_y > p.y + limit || s.max_y < p.y - limit {
+                continue;
+            }
+            let (sqr, q) = point_segment(p, s);
+            if sqr < best {
+                best = sqr;
+                nearest = q;
+                limit = FloatNumber::sqrt(best);
+            }
+        }
+
+        Some(DistancePair { distance: limit, a: p, b: nearest })
+    }
+
+    /// Returns the directed discrete Hausdorff distance from this geometry to the other one:
+    /// the largest distance from a sample point of this geometry to the other geometry,
+    /// `a` is the farthest sample point and `b` is its nearest point on the other geometry.
+    /// The samples are the vertices and the points which split every edge into equal parts not longer than `max_step`.
+    /// The result is less than the exact distance by at most `max_step / 2`.
+    /// - `max_step`: The max distance between two samples of an edge, `f64::INFINITY` measures the vertices only.
+    ///
+    /// Returns `None` if any geometry is empty.
+    pub fn directed_discrete_hausdorff(&self, other: &Self, max_step: f64) -> Option<DistancePair<FloatPoint<f64>, f64>> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        let mut result: Option<DistancePair<FloatPoint<f64>, f64>> = None;
+        for s in self.segments.iter() {
+            let v = s.b - s.a;
+            let length = FloatNumber::sqrt(v.sqr_length());
+            let n = if max_step > 0.0 { step_count(length / max_step) } else { 1 };
+            for i in 0..=n {
+                let pair = other.nearest_point(s.a + v * (i as f64 / n as f64))?;
+                if result.is_none_or(|r| pair.distance > r.distance) {
+                    result = Some(pair);
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the symmetric discrete Hausdorff distance, the larger of both directed distances.
+    /// `a` is on this geometry and `b` is on the other one.
+    /// See `directed_discrete_hausdorff` for `max_step`.
+    ///
+    /// Returns `None` if any geometry is empty.
+    pub fn discrete_hausdorff(&self, other: &Self, max_step: f64) -> Option<DistancePair<FloatPoint<f64>, f64>> {
+        let forward = self.directed_discrete_hausdorff(other, max_step)?;
+        let backward = other.directed_discrete_hausdorff(self, max_step)?;
+        if backward.distance > forward.distance {
+            Some(DistancePair { distance: backward.distance, a: backward.b, b: backward.a })
+        } else {
+            Some(forward)
+        }
+    }
+
+    /// The nearest pair with the distance not greater than `max_distance`,
+    /// the search stops as soon as the distance is not greater than `stop_distance`.
+    fn nearest_pair(
+        &self,
+        other: &Self,
+        max_distance: f64,
+        stop_distance: f64,
+    ) -> Option<DistancePair<FloatPoint<f64>, f64>> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+
+        // a contour of one geometry inside the other one, if no edges cross it is inside as a whole
+        for (query, area) in [(self, other), (other, self)] {
+            if !area.is_area {
+                continue;
+            }
+            for &start in query.starts.iter() {
+                let p = query.vertices[start];
+                if area.contains(p) {
+                    return Some(DistancePair { distance: 0.0, a: p, b: p });
+                }
+            }
+        }
+
+        let mut best = max_distance * max_distance;
+        let mut limit = max_distance;
+        let mut result = None;
+        let stop = stop_distance * stop_distance;
+
+        for s in self.segments.iter() {
+            let start = other.segments.partition_point(|o| o.min_x < s.min_x - limit - other.max_width);
+            for o in other.segments[start..].iter() {
+                if o.min_x > s.max_x + limit {
+                    break;
+                }
+                if o.max_x < s.min_x - limit || o.min_y > s.max_y + limit || o.max_y < s.min_y - limit {
+                    continue;
+                }
+                let (sqr, a, b) = segment_segment(s, o);
+                if sqr <= best && (result.is_none() || sqr < best) {
+                    best = sqr;
+                    limit = FloatNumber::sqrt(sqr);
+                    result = Some(DistancePair { distance: limit, a, b });
+                    if best <= stop {
+                        return result;
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The even-odd test, always `false` for lines.
+    fn contains(&self, p: FloatPoint<f64>) -> bool {
+        if !self.is_area {
+            return false;
+        }
+        let Some(rect) = &self.rect else {
+            return false;
+        };
+        if p.x < rect.min_x || p.x > rect.max_x || p.y < rect.min_y || p.y > rect.max_y {
+            return false;
+        }
+
+        // count edges crossed by the ray to the left
+        let end = self.segments.partition_point(|s| s.min_x <= p.x);
+        let mut is_inside = false;
+        for s in self.segments[..end].iter() {
+            if (s.a.y > p.y) != (s.b.y > p.y) {
+                let x = s.a.x + (p.y - s.a.y) * (s.b.x - s.a.x) / (s.b.y - s.a.y);
+                if x < p.x {
+                    is_inside = !is_inside;
+                }
+            }
+        }
+        is_inside
+    }
+}
+
+impl DistanceSegment {
+    #[inline]
+    fn new(a: FloatPoint<f64>, b: FloatPoint<f64>) -> Self {
+        Self {
+            a,
+            b,
+            min_x: a.x.min(b.x),
+            max_x: a.x.max(b.x),
+            min_y: a.y.min(b.y),
+            max_y: a.y.max(b.y),
+        }
+    }
+}
+
+#[inline]
+fn to_float(p: &IntPoint) -> FloatPoint<f64> {
+    FloatPoint::new(p.x as f64, p.y as f64)
+}
+
+/// The number of equal parts, rounded up, so that no part is longer than one step.
+#[inline]
+fn step_count(steps: f64) -> usize {
+    // `ceil` is not available in `no_std`
+    let n = steps as usize;
+    if (n as f64) < steps { n + 1 } else { n.max(1) }
+}
+
+#[inline]
+fn rect_sqr_distance(r0: &FloatRect<f64>, r1: &FloatRect<f64>) -> f64 {
+    let dx = (r0.min_x - r1.max_x).max(r1.min_x - r0.max_x).max(0.0);
+    let dy = (r0.min_y - r1.max_y).max(r1.min_y - r0.max_y).max(0.0);
+    dx * dx + dy * dy
+}
+
+/// The squared distance from `p` to the segment and the nearest point of the segment.
+#[inline]
+fn point_segment(p: FloatPoint<f64>, s: &DistanceSegment) -> (f64, FloatPoint<f64>) {
+    let v = s.b - s.a;
+    let len = v.sqr_length();
+    let q = if len > 0.0 {
+        let t = ((p - s.a).dot_product(v) / len).clamp(0.0, 1.0);
+        s.a + v * t
+    } else {
+        s.a
+    };
+    ((p - q).sqr_length(), q)
+}
+
+/// The squared distance between two segments and the nearest points, the first one is on `s`.
+fn segment_segment(s: &DistanceSegment, o: &DistanceSegment) -> (f64, FloatPoint<f64>, FloatPoint<f64>) {
+    let vs = s.b - s.a;
+    let vo = o.b - o.a;
+    let d0 = vs.cross_product(o.a - s.a);
+    let d1 = vs.cross_product(o.b - s.a);
+    let d2 = vo.cross_product(s.a - o.a);
+    let d3 = vo.cross_product(s.b - o.a);
+
+    if d0 * d1 < 0.0 && d2 * d3 < 0.0 {
+        // the segments cross
+        let t = d2 / (d2 - d3);
+        let p = s.a + vs * t;
+        return (0.0, p, p);
+    }
+
+    let mut result = {
+        let (sqr, q) = point_segment(s.a, o);
+        (sqr, s.a, q)
+    };
+    let (sqr, q) = point_segment(s.b, o);
+    if sqr < result.0 {
+        result = (sqr, s.b, q);
+    }
+    let (sqr, q) = point_segment(o.a, s);
+    if sqr < result.0 {
+        result = (sqr, q, o.a);
+    }
+    let (sqr, q) = point_segment(o.b, s);
+    if sqr < result.0 {
+        result = (sqr, q, o.b);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::float::point::FloatPoint;
+    use crate::core::distance::{segment_segment, DistanceSegment};
+
+    fn segment(x0: f64, y0: f64, x1: f64, y1: f64) -> DistanceSegment {
+        DistanceSegment::new(FloatPoint::new(x0, y0), FloatPoint::new(x1, y1))
+    }
+
+    #[test]
+    fn test_segment_segment() {
+        let (sqr, a, b) = segment_segment(&segment(0.0, 0.0, 10.0, 10.0), &segment(0.0, 10.0, 10.0, 0.0));
+        assert_eq!(sqr, 0.0);
+        assert_eq!((a.x, a.y, b.x, b.y), (5.0, 5.0, 5.0, 5.0));
+
+        let (sqr, a, b) = segment_segment(&segment(0.0, 0.0, 10.0, 0.0), &segment(5.0, 2.0, 5.0, 7.0));
+        assert_eq!(sqr, 4.0);
+        assert_eq!((a.x, a.y, b.x, b.y), (5.0, 0.0, 5.0, 2.0));
+
+        let (sqr, _, _) = segment_segment(&segment(0.0, 0.0, 0.0, 0.0), &segment(3.0, 4.0, 3.0, 4.0));
+        assert_eq!(sqr, 25.0);
+    }
+}
@@ -3,6 +3,7 @@ use alloc::vec::Vec;
 use crate::i_shape::flat::buffer::FlatContoursBuffer;
 use crate::core::link::OverlayLinkFilter;
 use super::overlay_rule::OverlayRule;
+use crate::core::custom_rule::CustomOverlayRule;
 use crate::bind::segment::{ContourIndex, IdSegment};
 use crate::bind::solver::{JoinHoles, LeftBottomSegment};
 use crate::core::graph::{OverlayGraph, OverlayNode};
@@ -10,6 +11,7 @@ use crate::core::link::OverlayLink;
 use crate::core::nearest_vector::NearestVector;
 use crate::core::overlay::ContourDirection;
 use crate::geom::v_segment::VSegment;
+use crate::segm::segment::SegmentFill;
 use i_float::int::point::IntPoint;
 use i_float::triangle::Triangle;
 use i_shape::int::path::ContourExtension;
@@ -38,7 +40,19 @@ impl OverlayGraph<'_> {
     #[inline]
     pub fn extract_shapes(&self, overlay_rule: OverlayRule, buffer: &mut BooleanExtractionBuffer) -> IntShapes {
         self.links.filter_by_overlay_into(overlay_rule, &mut buffer.visited);
-        self.extract(overlay_rule, buffer)
+        self.extract(|fill| overlay_rule.is_fill_top(fill), buffer)
+    }
+
+    /// Extracts shapes from the overlay graph based on a user-defined overlay rule.
+    /// - `rule`: The custom rule that selects the result boundary links and the filled side.
+    /// - `buffer`: Reusable buffer, optimisation purpose only.
+    /// - Returns: A vector of `IntShape`, representing the geometric result of the applied rule.
+    ///
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    #[inline]
+    pub fn extract_shapes_custom(&self, rule: CustomOverlayRule, buffer: &mut BooleanExtractionBuffer) -> IntShapes {
+        self.filter_by_custom_into(rule, &mut buffer.visited);
+        self.extract(|fill| rule.is_fill_top(fill), buffer)
     }
 
     /// Extracts the flat contours from the overlay graph based on the specified overlay rule.
@@ -55,12 +69,31 @@ impl OverlayGraph<'_> {
     #[inline]
     pub fn extract_contours_into(&self, overlay_rule: OverlayRule, buffer: &mut BooleanExtractionBuffer, output: &mut FlatContoursBuffer) {
         self.links.filter_by_overlay_into(overlay_rule, &mut buffer.visited);
-        self.extract_contours(overlay_rule, buffer, output);
+        self.extract_contours(|fill| overlay_rule.is_fill_top(fill), buffer, output);
+    }
+
+    /// Extracts the flat contours from the overlay graph based on a user-defined overlay rule.
+    /// - `rule`: The custom rule that selects the result boundary links and the filled side.
+    /// - `buffer`: Reusable working buffer to avoid reallocations.
+    /// - `output`: A flat buffer to which the resulting valid contours will be written.
+    #[inline]
+    pub fn extract_contours_into_custom(&self, rule: CustomOverlayRule, buffer: &mut BooleanExtractionBuffer, output: &mut FlatContoursBuffer) {
+        self.filter_by_custom_into(rule, &mut buffer.visited);
+        self.extract_contours(|fill| rule.is_fill_top(fill), buffer, output);
+    }
+
+    #[inline]
+    fn filter_by_custom_into(&self, rule: CustomOverlayRule, visited: &mut Vec<bool>) {
+        visited.clear();
+        visited.reserve_capacity(self.links.len());
+        for link in self.links.iter() {
+            visited.push(!rule.is_included(link.fill));
+        }
     }
 
-    fn extract(
+    fn extract<F: Fn(SegmentFill) -> bool>(
         &self,
-        overlay_rule: OverlayRule,
+        is_fill_top: F,
         buffer: &mut BooleanExtractionBuffer,
     ) -> IntShapes {
         let clockwise = self.options.output_direction == ContourDirection::Clockwise;
@@ -81,7 +114,7 @@ impl OverlayGraph<'_> {
 
             let left_top_link = GraphUtil::find_left_top_link(self.links, self.nodes, link_index, &buffer.visited);
             let link = unsafe { self.links.get_unchecked(left_top_link) };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = is_fill_top(link.fill);
 
             let direction = is_hole == clockwise;
             let start_data = StartPathData::new(direction, link, left_top_link);
@@ -167,9 +200,9 @@ impl OverlayGraph<'_> {
         }
     }
 
-    fn extract_contours(
+    fn extract_contours<F: Fn(SegmentFill) -> bool>(
         &self,
-        overlay_rule: OverlayRule,
+        is_fill_top: F,
         buffer: &mut BooleanExtractionBuffer,
         output: &mut FlatContoursBuffer
     ) {
@@ -187,7 +220,7 @@ impl OverlayGraph<'_> {
 
             let left_top_link = GraphUtil::find_left_top_link(self.links, self.nodes, link_index, &buffer.visited);
             let link = unsafe { self.links.get_unchecked(left_top_link) };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+            let is_hole = is_fill_top(link.fill);
 
             let direction = is_hole == clockwise;
             let start_data = StartPathData::new(direction, link, left_top_link);
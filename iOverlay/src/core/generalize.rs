@@ -0,0 +1,628 @@
+//! This module provides tolerance-driven simplification of shapes for map generalization.
+//! Contours are split into chains between junction points, and every chain is simplified once,
+//! so borders shared by adjacent polygons stay identical. A point is removed only if the new edge
+//! does not touch any other edge and the removed area does not contain any other point,
+//! so contours never become self-intersecting and holes never cross their outer boundary.
+
+use alloc::collections::BTreeMap;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+use crate::geom::predicate::{is_inside_or_on, is_within, orient};
+
+/// Specifies the tolerance-driven simplification algorithm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneralizeMethod<T> {
+    /// Douglas–Peucker: removes points closer than the tolerance distance to the simplified edge.
+    DouglasPeucker(T),
+    /// Visvalingam–Whyatt: removes points whose triangle with its neighbors has an area less than the threshold.
+    Visvalingam(T),
+}
+
+/// Trait `Generalize` provides topology-preserving simplification of shapes with a tolerance.
+///
+/// Unlike `Simplify`, which only removes collinear and degenerate points, it removes every point
+/// below the tolerance unless the removal changes the topology. The result keeps the structure
+/// of the input: the same shapes, the same contours and the same contour directions.
+pub trait Generalize {
+    /// Simplifies shapes with a tolerance.
+    ///
+    /// - `method`: The algorithm and its tolerance in integer units.
+    /// - `preserve_shared_borders`: If `true`, all shapes are simplified together: borders shared by
+    ///   adjacent shapes are simplified identically and shapes never overlap each other.
+    ///   If `false`, every shape is simplified independently.
+    ///
+    /// # Returns
+    /// A collection of `IntShapes` with the same layout as the input.
+    fn generalize(&self, method: GeneralizeMethod<i64>, preserve_shared_borders: bool) -> IntShapes;
+}
+
+impl Generalize for [IntShape] {
+    fn generalize(&self, method: GeneralizeMethod<i64>, preserve_shared_borders: bool) -> IntShapes {
+        if preserve_shared_borders {
+            Generalizer::new(self).generalize(method)
+        } else {
+            self.iter()
+                .flat_map(|shape| Generalizer::new(core::slice::from_ref(shape)).generalize(method))
+                .collect()
+        }
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+struct Chain {
+    first: usize,
+    is_ring: bool,
+    count: usize,
+    min_count: usize,
+}
+
+enum ContourLayout {
+    Chains(Vec<(usize, bool)>),
+    Raw(IntContour),
+}
+
+struct Generalizer {
+    points: Vec<IntPoint>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    chain: Vec<usize>,
+    chains: Vec<Chain>,
+    layout: Vec<Vec<ContourLayout>>,
+    grid: SegmentGrid,
+    marks: Vec<u32>,
+    mark: u32,
+}
+
+impl Generalizer {
+    fn new(shapes: &[IntShape]) -> Self {
+        let contours: Vec<Vec<IntContour>> = shapes
+            .iter()
+            .map(|shape| shape.iter().map(|contour| clean(contour)).collect())
+            .collect();
+
+        // junctions are points with other than two distinct neighbors
+        let mut pairs = Vec::new();
+        for contour in contours.iter().flatten().filter(|c| c.len() >= 3) {
+            let mut a = contour[contour.len() - 1];
+            for &b in contour.iter() {
+                pairs.push((a, b));
+                pairs.push((b, a));
+                a = b;
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        let mut nodes = Vec::new();
+        let mut i = 0;
+        while i < pairs.len() {
+            let mut j = i + 1;
+            while j < pairs.len() && pairs[j].0 == pairs[i].0 {
+                j += 1;
+            }
+            if j - i != 2 {
+                nodes.push(pairs[i].0);
+            }
+            i = j;
+        }
+
+        let mut generalizer = Self {
+            points: Vec::new(),
+            next: Vec::new(),
+            prev: Vec::new(),
+            chain: Vec::new(),
+            chains: Vec::new(),
+            layout: Vec::with_capacity(contours.len()),
+            grid: SegmentGrid::new(&pairs),
+            marks: Vec::new(),
+            mark: 0,
+        };
+
+        let mut map = BTreeMap::new();
+        for shape in contours.into_iter() {
+            let layout = shape
+                .into_iter()
+                .map(|contour| {
+                    if contour.len() < 3 {
+                        ContourLayout::Raw(contour)
+                    } else {
+                        ContourLayout::Chains(generalizer.add_contour(contour, &nodes, &mut map))
+                    }
+                })
+                .collect();
+            generalizer.layout.push(layout);
+        }
+
+        generalizer.marks = vec![0; generalizer.points.len()];
+        for v in 0..generalizer.points.len() {
+            let w = generalizer.next[v];
+            if w != NONE {
+                generalizer.grid.insert(v, generalizer.points[v], generalizer.points[w]);
+            }
+        }
+
+        generalizer
+    }
+
+    fn add_contour(
+        &mut self,
+        mut contour: IntContour,
+        nodes: &[IntPoint],
+        map: &mut BTreeMap<Vec<IntPoint>, usize>,
+    ) -> Vec<(usize, bool)> {
+        let start = contour.iter().position(|p| nodes.binary_search(p).is_ok());
+
+        let start = if let Some(start) = start {
+            start
+        } else {
+            // a ring without junctions, it starts at the min point in the min direction
+            let m = contour.iter().enumerate().min_by_key(|&(_, p)| *p).map(|(i, _)| i).unwrap_or(0);
+            contour.rotate_left(m);
+            let is_reversed = contour[contour.len() - 1] < contour[1];
+            if is_reversed {
+                contour[1..].reverse();
+            }
+            let id = self.chain_id(contour, true, map);
+            return vec![(id, is_reversed)];
+        };
+
+        contour.rotate_left(start);
+        contour.push(contour[0]);
+
+        let mut result = Vec::new();
+        let mut i = 0;
+        while i + 1 < contour.len() {
+            let mut j = i + 1;
+            while nodes.binary_search(&contour[j]).is_err() {
+                j += 1;
+            }
+            let mut part = contour[i..=j].to_vec();
+            let mut reversed = part.clone();
+            reversed.reverse();
+            let is_reversed = reversed < part;
+            if is_reversed {
+                part = reversed;
+            }
+            result.push((self.chain_id(part, false, map), is_reversed));
+            i = j;
+        }
+
+        result
+    }
+
+    fn chain_id(&mut self, path: Vec<IntPoint>, is_ring: bool, map: &mut BTreeMap<Vec<IntPoint>, usize>) -> usize {
+        if let Some(&id) = map.get(&path) {
+            return id;
+        }
+
+        let id = self.chains.len();
+        let first = self.points.len();
+        let n = path.len();
+        for (i, &p) in path.iter().enumerate() {
+            self.points.push(p);
+            self.chain.push(id);
+            self.prev.push(if i > 0 { first + i - 1 } else if is_ring { first + n - 1 } else { NONE });
+            self.next.push(if i + 1 < n { first + i + 1 } else if is_ring { first } else { NONE });
+        }
+
+        let min_count = if is_ring {
+            3
+        } else if path[0] == path[n - 1] {
+            // a loop must keep two points besides its junction
+            4
+        } else {
+            2
+        };
+
+        self.chains.push(Chain { first, is_ring, count: n, min_count });
+        map.insert(path, id);
+
+        id
+    }
+
+    fn generalize(mut self, method: GeneralizeMethod<i64>) -> IntShapes {
+        match method {
+            GeneralizeMethod::DouglasPeucker(tolerance) => self.douglas_peucker(tolerance),
+            GeneralizeMethod::Visvalingam(area) => self.visvalingam(area),
+        }
+
+        let layout = core::mem::take(&mut self.layout);
+        let mut buffer = Vec::new();
+        layout
+            .into_iter()
+            .map(|shape| {
+                shape
+                    .into_iter()
+                    .map(|contour| match contour {
+                        ContourLayout::Raw(contour) => contour,
+                        ContourLayout::Chains(chains) => {
+                            let mut result = Vec::new();
+                            for (id, is_reversed) in chains {
+                                self.collect_chain(id, &mut buffer);
+                                if self.chains[id].is_ring {
+                                    if is_reversed {
+                                        buffer[1..].reverse();
+                                    }
+                                } else {
+                                    if is_reversed {
+                                        buffer.reverse();
+                                    }
+                                    buffer.pop();
+                                }
+                                result.extend_from_slice(&buffer);
+                            }
+                            result
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn collect_chain(&self, id: usize, buffer: &mut Vec<IntPoint>) {
+        buffer.clear();
+        let first = self.chains[id].first;
+        let mut v = first;
+        loop {
+            buffer.push(self.points[v]);
+            v = self.next[v];
+            if v == NONE || v == first {
+                break;
+            }
+        }
+    }
+
+    fn douglas_peucker(&mut self, tolerance: i64) {
+        let sqr_tolerance = (tolerance as f64) * (tolerance as f64);
+        let mut ids = Vec::new();
+        let mut stack = Vec::new();
+        for id in 0..self.chains.len() {
+            ids.clear();
+            let first = self.chains[id].first;
+            let mut v = first;
+            loop {
+                ids.push(v);
+                v = self.next[v];
+                if v == NONE || v == first {
+                    break;
+                }
+            }
+
+            stack.clear();
+            let last = ids.len() - 1;
+            if self.chains[id].is_ring || self.points[ids[0]] == self.points[ids[last]] {
+                // closed chains are split at the farthest point from the start
+                let a = self.points[ids[0]];
+                let k = (1..ids.len())
+                    .max_by_key(|&i| (self.points[ids[i]] - a).sqr_length())
+                    .unwrap_or(1);
+                if self.chains[id].is_ring {
+                    ids.push(first);
+                }
+                stack.push((k, ids.len() - 1));
+                stack.push((0, k));
+            } else {
+                stack.push((0, last));
+            }
+
+            while let Some((i, j)) = stack.pop() {
+                if j - i < 2 {
+                    continue;
+                }
+                let a = self.points[ids[i]];
+                let b = self.points[ids[j]];
+                let ab = b - a;
+                let mut k = i + 1;
+                let mut max_cross = 0;
+                for (m, &v) in ids.iter().enumerate().take(j).skip(i + 1) {
+                    let cross = (ab.cross_product(self.points[v] - a) as i128).abs();
+                    if cross > max_cross {
+                        max_cross = cross;
+                        k = m;
+                    }
+                }
+
+                let max_cross = max_cross as f64;
+                let is_near = max_cross * max_cross <= sqr_tolerance * ab.sqr_length() as f64;
+                if is_near && self.chains[id].count - (j - i - 1) >= self.chains[id].min_count && self.is_safe(ids[i], ids[j]) {
+                    self.remove_range(ids[i], ids[j]);
+                } else {
+                    stack.push((k, j));
+                    stack.push((i, k));
+                }
+            }
+        }
+    }
+
+    fn visvalingam(&mut self, area: i64) {
+        let threshold = 2 * area as i128;
+        let mut versions = vec![0u32; self.points.len()];
+        let mut heap = BinaryHeap::new();
+        for v in 0..self.points.len() {
+            if let Some(a) = self.effective_area(v) {
+                heap.push(Reverse((a, v, 0)));
+            }
+        }
+
+        while let Some(Reverse((a, v, version))) = heap.pop() {
+            if a >= threshold {
+                break;
+            }
+            if version != versions[v] {
+                continue;
+            }
+            let chain = &self.chains[self.chain[v]];
+            if chain.count <= chain.min_count {
+                continue;
+            }
+
+            let (p, n) = (self.prev[v], self.next[v]);
+            if !self.is_safe(p, n) {
+                continue;
+            }
+
+            self.remove_range(p, n);
+            versions[v] += 1;
+            for w in [p, n] {
+                versions[w] += 1;
+                if let Some(a) = self.effective_area(w) {
+                    heap.push(Reverse((a, w, versions[w])));
+                }
+            }
+        }
+    }
+
+    fn effective_area(&self, v: usize) -> Option<i128> {
+        let (p, n) = (self.prev[v], self.next[v]);
+        if p == NONE || n == NONE {
+            return None;
+        }
+        let a = self.points[p];
+        let cross = (self.points[n] - a).cross_product(self.points[v] - a) as i128;
+        Some(cross.abs())
+    }
+
+    fn remove_range(&mut self, a: usize, c: usize) {
+        let id = self.chain[a];
+        let mut v = a;
+        while v != c {
+            let w = self.next[v];
+            self.grid.remove(v, self.points[v], self.points[w]);
+            if v != a {
+                self.next[v] = NONE;
+                self.prev[v] = NONE;
+                self.chains[id].count -= 1;
+                if self.chains[id].first == v {
+                    self.chains[id].first = c;
+                }
+            }
+            v = w;
+        }
+        self.next[a] = c;
+        self.prev[c] = a;
+        self.grid.insert(a, self.points[a], self.points[c]);
+    }
+
+    // checks that the chain from `a` to `c` can be replaced by the edge `a`-`c` without changing the topology
+    fn is_safe(&mut self, a: usize, c: usize) -> bool {
+        self.mark += 1;
+        let mark = self.mark;
+
+        let mut ring = Vec::new();
+        let mut v = a;
+        while v != c {
+            self.marks[v] = mark;
+            ring.push(self.points[v]);
+            v = self.next[v];
+        }
+        ring.push(self.points[c]);
+
+        let pa = self.points[a];
+        let pc = self.points[c];
+        let (min, max) = bounds(&ring);
+
+        let mut is_safe = true;
+        let points = &self.points;
+        let next = &self.next;
+        let marks = &self.marks;
+        self.grid.query(min, max, mark, |u| {
+            if marks[u] == mark {
+                return true;
+            }
+            let p = points[u];
+            let q = points[next[u]];
+            if is_conflict(pa, pc, p, q)
+                || p != pa && p != pc && is_inside_or_on(p, &ring)
+                || q != pa && q != pc && is_inside_or_on(q, &ring)
+            {
+                is_safe = false;
+                return false;
+            }
+            true
+        });
+
+        is_safe
+    }
+}
+
+// removes repeated points and the closing point
+fn clean(contour: &[IntPoint]) -> IntContour {
+    let mut result: IntContour = Vec::with_capacity(contour.len());
+    for &p in contour.iter() {
+        if result.last() != Some(&p) {
+            result.push(p);
+        }
+    }
+    while result.len() > 1 && result.first() == result.last() {
+        result.pop();
+    }
+    result
+}
+
+fn bounds(points: &[IntPoint]) -> (IntPoint, IntPoint) {
+    let mut min = points[0];
+    let mut max = points[0];
+    for p in points.iter() {
+        min.x = min.x.min(p.x);
+        min.y = min.y.min(p.y);
+        max.x = max.x.max(p.x);
+        max.y = max.y.max(p.y);
+    }
+    (min, max)
+}
+
+// edges conflict if they have any common point except a shared end
+fn is_conflict(a: IntPoint, c: IntPoint, p: IntPoint, q: IntPoint) -> bool {
+    let d1 = orient(a, c, p);
+    let d2 = orient(a, c, q);
+    let d3 = orient(p, q, a);
+    let d4 = orient(p, q, c);
+
+    if d1 == 0 && d2 == 0 {
+        let (s0, s1, t0, t1) = if a.x != c.x {
+            (a.x.min(c.x), a.x.max(c.x), p.x.min(q.x), p.x.max(q.x))
+        } else {
+            (a.y.min(c.y), a.y.max(c.y), p.y.min(q.y), p.y.max(q.y))
+        };
+        let overlap = s1.min(t1) as i64 - s0.max(t0) as i64;
+        return overlap > 0 || overlap == 0 && !(p == a || p == c || q == a || q == c);
+    }
+
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+
+    d1 == 0 && p != a && p != c && is_within(a, c, p)
+        || d2 == 0 && q != a && q != c && is_within(a, c, q)
+        || d3 == 0 && a != p && a != q && is_within(p, q, a)
+        || d4 == 0 && c != p && c != q && is_within(p, q, c)
+}
+
+struct SegmentGrid {
+    min_x: i64,
+    min_y: i64,
+    size: i64,
+    side: usize,
+    cells: Vec<Vec<usize>>,
+    marks: Vec<u32>,
+}
+
+impl SegmentGrid {
+    fn new(pairs: &[(IntPoint, IntPoint)]) -> Self {
+        let points: Vec<IntPoint> = pairs.iter().map(|pair| pair.0).collect();
+        if points.is_empty() {
+            return Self { min_x: 0, min_y: 0, size: 1, side: 1, cells: vec![Vec::new()], marks: Vec::new() };
+        }
+
+        let (min, max) = bounds(&points);
+        let n = pairs.len() / 2;
+        let mut side = 1;
+        while side * side < n && side < 1024 {
+            side += 1;
+        }
+        let width = (max.x as i64 - min.x as i64).max(max.y as i64 - min.y as i64);
+        let size = width / side as i64 + 1;
+
+        Self {
+            min_x: min.x as i64,
+            min_y: min.y as i64,
+            size,
+            side,
+            cells: vec![Vec::new(); side * side],
+            marks: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn index(&self, value: i32, min: i64) -> usize {
+        (((value as i64 - min) / self.size).max(0) as usize).min(self.side - 1)
+    }
+
+    fn range(&self, a: IntPoint, b: IntPoint) -> (usize, usize, usize, usize) {
+        (
+            self.index(a.x.min(b.x), self.min_x),
+            self.index(a.x.max(b.x), self.min_x),
+            self.index(a.y.min(b.y), self.min_y),
+            self.index(a.y.max(b.y), self.min_y),
+        )
+    }
+
+    fn insert(&mut self, id: usize, a: IntPoint, b: IntPoint) {
+        if self.marks.len() <= id {
+            self.marks.resize(id + 1, 0);
+        }
+        let (x0, x1, y0, y1) = self.range(a, b);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                self.cells[y * self.side + x].push(id);
+            }
+        }
+    }
+
+    fn remove(&mut self, id: usize, a: IntPoint, b: IntPoint) {
+        let (x0, x1, y0, y1) = self.range(a, b);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let cell = &mut self.cells[y * self.side + x];
+                if let Some(i) = cell.iter().position(|&s| s == id) {
+                    cell.swap_remove(i);
+                }
+            }
+        }
+    }
+
+    // visits every segment once until `visit` returns false
+    fn query<F: FnMut(usize) -> bool>(&mut self, min: IntPoint, max: IntPoint, mark: u32, mut visit: F) {
+        let (x0, x1, y0, y1) = self.range(min, max);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                for &id in self.cells[y * self.side + x].iter() {
+                    if self.marks[id] == mark {
+                        continue;
+                    }
+                    self.marks[id] = mark;
+                    if !visit(id) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict() {
+        let p = IntPoint::new;
+        // crossing
+        assert!(is_conflict(p(0, 0), p(10, 0), p(5, -5), p(5, 5)));
+        // touching at the middle
+        assert!(is_conflict(p(0, 0), p(10, 0), p(5, 0), p(5, 5)));
+        // a shared end
+        assert!(!is_conflict(p(0, 0), p(10, 0), p(10, 0), p(10, 5)));
+        // collinear overlap
+        assert!(is_conflict(p(0, 0), p(10, 0), p(5, 0), p(15, 0)));
+        // collinear with a shared end
+        assert!(!is_conflict(p(0, 0), p(10, 0), p(10, 0), p(15, 0)));
+        // apart
+        assert!(!is_conflict(p(0, 0), p(10, 0), p(0, 1), p(10, 1)));
+    }
+
+    #[test]
+    fn test_inside() {
+        let p = IntPoint::new;
+        let ring = [p(0, 0), p(10, 0), p(10, 10), p(0, 10)];
+        assert!(is_inside_or_on(p(5, 5), &ring));
+        assert!(is_inside_or_on(p(10, 5), &ring));
+        assert!(!is_inside_or_on(p(15, 5), &ring));
+        assert!(!is_inside_or_on(p(5, -1), &ring));
+    }
+}
@@ -0,0 +1,224 @@
+//! This module contains an overlay builder for any number of labeled input layers.
+//! Instead of the fixed `Subject`/`Clip` pair, every contour is assigned a layer id and
+//! the result is selected by an arbitrary predicate over per-layer winding counts,
+//! so many layers can be combined with a single graph build.
+
+use crate::build::builder::GraphBuilder;
+use crate::core::extract::BooleanExtractionBuffer;
+use crate::core::graph::{OverlayGraph, OverlayNode};
+use crate::core::overlay::IntOverlayOptions;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::i_shape::flat::buffer::FlatContoursBuffer;
+use crate::segm::build::BuildSegments;
+use crate::segm::layer::{LayerCounts, ShapeCountLayer};
+use crate::segm::segment::Segment;
+use crate::split::solver::SplitSolver;
+use alloc::vec::Vec;
+use core::fmt;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+
+/// The error of adding a path with a wrong layer id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerError {
+    /// The layer id is not less than the layer count of the overlay.
+    OutOfRange { layer: usize, layer_count: usize },
+}
+
+impl fmt::Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayerError::OutOfRange { layer, layer_count } => {
+                write!(f, "layer id {} is out of range 0..{}", layer, layer_count)
+            }
+        }
+    }
+}
+
+impl core::error::Error for LayerError {}
+
+/// An `Overlay`-like builder that accepts contours from any number of labeled layers.
+///
+/// The layer count is set at runtime. Every segment keeps only a reference to the sparse counts
+/// of the layers it belongs to, so the memory does not grow with the layer count.
+///
+/// A region belongs to the result when the predicate passed to [`LayerOverlay::overlay`] returns `true`
+/// for its counts. The predicate receives a slice with the raw winding number of every layer, so the fill rule
+/// is up to the caller (e.g. `c != 0` for non-zero, `c & 1 == 1` for even-odd).
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_float::int_pnt;
+/// use i_overlay::core::layer_overlay::LayerOverlay;
+///
+/// let a = [int_pnt!(0, 0), int_pnt!(0, 10), int_pnt!(10, 10), int_pnt!(10, 0)];
+/// let b = [int_pnt!(5, 0), int_pnt!(5, 10), int_pnt!(15, 10), int_pnt!(15, 0)];
+/// let c = [int_pnt!(8, 0), int_pnt!(8, 10), int_pnt!(20, 10), int_pnt!(20, 0)];
+///
+/// let mut overlay = LayerOverlay::new(3, 12);
+/// overlay.add_contour(&a, 0).unwrap();
+/// overlay.add_contour(&b, 1).unwrap();
+/// overlay.add_contour(&c, 2).unwrap();
+/// assert!(overlay.add_contour(&c, 3).is_err());
+///
+/// // in A and B but not C
+/// let result = overlay.overlay(|c| c[0] != 0 && c[1] != 0 && c[2] == 0);
+/// assert_eq!(result.len(), 1);
+/// ```
+pub struct LayerOverlay {
+    pub solver: Solver,
+    pub options: IntOverlayOptions,
+    pub boolean_buffer: Option<BooleanExtractionBuffer>,
+    pub(crate) layer_count: usize,
+    pub(crate) segments: Vec<Segment<ShapeCountLayer>>,
+    pub(crate) counts: LayerCounts,
+    pub(crate) split_solver: SplitSolver,
+    pub(crate) graph_builder: GraphBuilder<ShapeCountLayer, OverlayNode>,
+}
+
+impl LayerOverlay {
+    /// Constructs a new `LayerOverlay` instance.
+    /// - `layer_count`: The number of layers, the layer ids are `0..layer_count`.
+    /// - `capacity`: The initial capacity for storing edge data. Ideally, this should be set to the sum of the edges of all shapes to be added.
+    pub fn new(layer_count: usize, capacity: usize) -> Self {
+        Self::new_custom(layer_count, capacity, Default::default(), Default::default())
+    }
+
+    /// Constructs a new `LayerOverlay` instance.
+    /// - `layer_count`: The number of layers, the layer ids are `0..layer_count`.
+    /// - `capacity`: The initial capacity for storing edge data. Ideally, this should be set to the sum of the edges of all shapes to be added.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    pub fn new_custom(layer_count: usize, capacity: usize, options: IntOverlayOptions, solver: Solver) -> Self {
+        Self {
+            solver,
+            options,
+            boolean_buffer: Some(Default::default()),
+            layer_count,
+            segments: Vec::with_capacity(capacity),
+            counts: LayerCounts::new(layer_count),
+            split_solver: SplitSolver::new(),
+            graph_builder: GraphBuilder::<ShapeCountLayer, OverlayNode>::new(),
+        }
+    }
+
+    /// Returns the number of layers.
+    #[inline]
+    pub fn layer_count(&self) -> usize {
+        self.layer_count
+    }
+
+    /// Adds a path to the overlay using an iterator.
+    /// - `iter`: An iterator over `IntPoint` that defines the path.
+    /// - `layer`: The layer id of the path, must be less than the layer count.
+    #[inline]
+    pub fn add_path_iter<I: Iterator<Item = IntPoint>>(&mut self, iter: I, layer: usize) -> Result<(), LayerError> {
+        if layer >= self.layer_count {
+            return Err(LayerError::OutOfRange { layer, layer_count: self.layer_count });
+        }
+        let (direct, invert) = self.counts.with_layer(layer);
+        self.segments.append_path_iter_with_counts(
+            iter,
+            direct,
+            invert,
+            self.options.preserve_input_collinear,
+        );
+        Ok(())
+    }
+
+    /// Adds a single path to the overlay.
+    /// - `contour`: An array of points that form a closed path.
+    /// - `layer`: The layer id of the path, must be less than the layer count.
+    #[inline]
+    pub fn add_contour(&mut self, contour: &[IntPoint], layer: usize) -> Result<(), LayerError> {
+        self.add_path_iter(contour.iter().copied(), layer)
+    }
+
+    /// Adds multiple paths to the overlay.
+    /// - `contours`: An array of `IntContour` instances to be added to the overlay.
+    /// - `layer`: The layer id of the paths, must be less than the layer count.
+    #[inline]
+    pub fn add_contours(&mut self, contours: &[IntContour], layer: usize) -> Result<(), LayerError> {
+        for contour in contours.iter() {
+            self.add_contour(contour, layer)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a single shape to the overlay.
+    /// - `shape`: A reference to a `IntShape` instance to be added.
+    /// - `layer`: The layer id of the shape, must be less than the layer count.
+    #[inline]
+    pub fn add_shape(&mut self, shape: &IntShape, layer: usize) -> Result<(), LayerError> {
+        self.add_contours(shape, layer)
+    }
+
+    /// Adds multiple shapes to the overlay.
+    /// - `shapes`: An array of `IntShape` instances to be added to the overlay.
+    /// - `layer`: The layer id of the shapes, must be less than the layer count.
+    #[inline]
+    pub fn add_shapes(&mut self, shapes: &[IntShape], layer: usize) -> Result<(), LayerError> {
+        for shape in shapes.iter() {
+            self.add_contours(shape, layer)?;
+        }
+        Ok(())
+    }
+
+    /// Adds multiple flat-shape to the overlay.
+    /// - `buffer`: A buffer of contours to be added to the overlay.
+    /// - `layer`: The layer id of the contours, must be less than the layer count.
+    #[inline]
+    pub fn add_flat_buffer(&mut self, buffer: &FlatContoursBuffer, layer: usize) -> Result<(), LayerError> {
+        for range in buffer.ranges.iter() {
+            let contour = &buffer.points[range.clone()];
+            self.add_contour(contour, layer)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.segments.clear();
+        self.counts.clear();
+    }
+
+    /// Convert into `OverlayGraph` filtered by the predicate. The result can be extracted with `OverlayRule::Subject`.
+    /// - `predicate`: Returns `true` if a region with the given per-layer winding counts belongs to the result.
+    #[inline]
+    pub fn build_graph_view<P: Fn(&[i32]) -> bool>(&mut self, predicate: P) -> Option<OverlayGraph<'_>> {
+        self.split_solver
+            .split_segments_with(&mut self.segments, &self.solver, &mut self.counts);
+        if self.segments.is_empty() {
+            return None;
+        }
+        let graph = self.graph_builder.build_layer_overlay(
+            predicate,
+            &mut self.counts,
+            self.options,
+            &self.solver,
+            &self.segments,
+        );
+
+        Some(graph)
+    }
+
+    /// Executes the overlay for the current geometry.
+    /// - `predicate`: Returns `true` if a region with the given per-layer winding counts belongs to the result.
+    /// - Returns: A vector of `IntShape`, where the first contour of each shape is the outer boundary and the rest are holes.
+    #[inline]
+    pub fn overlay<P: Fn(&[i32]) -> bool>(&mut self, predicate: P) -> IntShapes {
+        self.split_solver
+            .split_segments_with(&mut self.segments, &self.solver, &mut self.counts);
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let shapes = self
+            .graph_builder
+            .build_layer_overlay(predicate, &mut self.counts, self.options, &self.solver, &self.segments)
+            .extract_shapes(OverlayRule::Subject, &mut buffer);
+        self.boolean_buffer = Some(buffer);
+        shapes
+    }
+}
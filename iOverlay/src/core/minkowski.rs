@@ -0,0 +1,123 @@
+//! This module provides the Minkowski sum and difference of polygons with holes.
+//! The result is a union of parallelograms built from every pair of edges plus
+//! both inputs translated into each other, merged by a single overlay.
+
+use alloc::vec::Vec;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use i_float::int::point::IntPoint;
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::shape::{IntShape, IntShapes};
+
+/// Trait `Minkowski` provides the Minkowski sum and difference of shapes.
+///
+/// Both inputs must be valid shapes: the first contour of a shape is the outer boundary and the others are holes,
+/// as produced by overlay operations. The direction of contours does not matter.
+/// The caller must keep sums of coordinates in the `i32` range.
+pub trait Minkowski {
+    /// Computes the Minkowski sum `{a + b | a ∈ self, b ∈ kernel}`.
+    ///
+    /// - `kernel`: The shapes to sweep over `self`, e.g. a robot footprint.
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn minkowski_sum(&self, kernel: &[IntShape], options: IntOverlayOptions) -> IntShapes;
+
+    /// Computes the Minkowski difference `{a - b | a ∈ self, b ∈ kernel}`, the sum with the reflected kernel.
+    /// For an obstacle and a footprint it is the set of footprint positions in collision with the obstacle.
+    ///
+    /// - `kernel`: The shapes to reflect and sweep over `self`.
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn minkowski_diff(&self, kernel: &[IntShape], options: IntOverlayOptions) -> IntShapes;
+}
+
+impl Minkowski for [IntShape] {
+    #[inline]
+    fn minkowski_sum(&self, kernel: &[IntShape], options: IntOverlayOptions) -> IntShapes {
+        minkowski(self, kernel, options)
+    }
+
+    #[inline]
+    fn minkowski_diff(&self, kernel: &[IntShape], options: IntOverlayOptions) -> IntShapes {
+        let reflected: Vec<IntShape> = kernel
+            .iter()
+            .map(|shape| {
+                shape
+                    .iter()
+                    .map(|contour| contour.iter().map(|p| IntPoint::new(-p.x, -p.y)).collect())
+                    .collect()
+            })
+            .collect();
+        minkowski(self, &reflected, options)
+    }
+}
+
+fn minkowski(shapes: &[IntShape], kernel: &[IntShape], options: IntOverlayOptions) -> IntShapes {
+    let capacity = 4 * shapes.points_count() * kernel.points_count()
+        + kernel.len() * shapes.points_count()
+        + shapes.len() * kernel.points_count();
+
+    let mut overlay = Overlay::new_custom(capacity, options, Default::default());
+    let mut buffer = Vec::new();
+
+    for shape in shapes.iter() {
+        let a = if let Some(&a) = shape.first().and_then(|outer| outer.first()) { a } else { continue };
+        for kernel_shape in kernel.iter() {
+            let b = if let Some(&b) = kernel_shape.first().and_then(|outer| outer.first()) { b } else { continue };
+
+            // all parallelograms are counterclockwise, so they are filled with the positive rule
+            for contour in shape.iter() {
+                for kernel_contour in kernel_shape.iter() {
+                    overlay.add_edge_sums(contour, kernel_contour);
+                }
+            }
+
+            // interiors, a point of each shape is translated over the other one
+            overlay.add_translated(shape, b, &mut buffer);
+            overlay.add_translated(kernel_shape, a, &mut buffer);
+        }
+    }
+
+    overlay.overlay(OverlayRule::Subject, FillRule::Positive)
+}
+
+impl Overlay {
+    fn add_edge_sums(&mut self, contour: &[IntPoint], kernel: &[IntPoint]) {
+        let (mut a0, mut b0) = if let (Some(&a), Some(&b)) = (contour.last(), kernel.last()) { (a, b) } else { return };
+        for &a1 in contour.iter() {
+            let ea = a1 - a0;
+            for &b1 in kernel.iter() {
+                let eb = b1 - b0;
+                let cross = ea.cross_product(eb);
+                if cross > 0 {
+                    self.add_contour(&[a0 + b0, a1 + b0, a1 + b1, a0 + b1], ShapeType::Subject);
+                } else if cross < 0 {
+                    self.add_contour(&[a0 + b0, a0 + b1, a1 + b1, a1 + b0], ShapeType::Subject);
+                }
+                b0 = b1;
+            }
+            a0 = a1;
+        }
+    }
+
+    fn add_translated(&mut self, shape: &[Vec<IntPoint>], offset: IntPoint, buffer: &mut Vec<IntPoint>) {
+        for (i, contour) in shape.iter().enumerate() {
+            if contour.len() < 3 {
+                continue;
+            }
+            buffer.clear();
+            buffer.extend(contour.iter().map(|&p| p + offset));
+
+            // the outer contour must be counterclockwise and holes clockwise
+            let is_clockwise = buffer.unsafe_area() > 0;
+            if is_clockwise == (i == 0) {
+                buffer.reverse();
+            }
+            self.add_contour(buffer, ShapeType::Subject);
+        }
+    }
+}
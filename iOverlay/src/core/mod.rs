@@ -1,4 +1,17 @@
 pub mod overlay;
+pub mod layer_overlay;
+pub mod arrangement;
+pub mod source;
+pub mod custom_rule;
+pub mod point_query;
+pub mod minkowski;
+pub mod union_all;
+pub mod session;
+pub mod generalize;
+pub mod validate;
+pub mod relate;
+pub mod distance;
+pub mod rect_clip;
 pub mod solver;
 pub mod graph;
 pub mod overlay_rule;
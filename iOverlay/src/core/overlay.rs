@@ -3,14 +3,17 @@
 //! manage subject and clip polygons and convert them into graphs for further operations.
 
 use crate::build::builder::GraphBuilder;
+use crate::core::custom_rule::CustomOverlayRule;
 use crate::core::extract::BooleanExtractionBuffer;
 use crate::core::fill_rule::FillRule;
 use crate::core::overlay_rule::OverlayRule;
 use crate::core::solver::Solver;
+use crate::core::source::{EdgeSource, ShapesSources, SourceTracks};
 use crate::i_shape::flat::buffer::FlatContoursBuffer;
 use crate::segm::boolean::ShapeCountBoolean;
 use crate::segm::build::BuildSegments;
 use crate::segm::segment::Segment;
+use crate::segm::winding::WindingCount;
 use crate::split::solver::SplitSolver;
 use crate::vector::edge::{VectorEdge, VectorShape};
 use alloc::vec::Vec;
@@ -44,7 +47,7 @@ pub struct IntOverlayOptions {
 /// Note: All operations except for `Difference` are commutative, meaning the order of `Subject` and `Clip` shapes does not impact the outcome.
 /// - `Subject`: The primary shape(s) for operations. Acts as the base layer in the operation.
 /// - `Clip`: The modifying shape(s) that are applied to the `Subject`. Determines how the `Subject` is altered or intersected.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShapeType {
     Subject,
     Clip,
@@ -65,6 +68,7 @@ pub struct Overlay {
     pub(crate) segments: Vec<Segment<ShapeCountBoolean>>,
     pub(crate) split_solver: SplitSolver,
     pub(crate) graph_builder: GraphBuilder<ShapeCountBoolean, OverlayNode>,
+    pub(crate) sources: SourceTracks,
 }
 
 impl Overlay {
@@ -79,6 +83,7 @@ impl Overlay {
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountBoolean, OverlayNode>::new(),
+            sources: SourceTracks::default(),
         }
     }
 
@@ -95,6 +100,7 @@ impl Overlay {
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
             graph_builder: GraphBuilder::<ShapeCountBoolean, OverlayNode>::new(),
+            sources: SourceTracks::default(),
         }
     }
 
@@ -232,9 +238,51 @@ impl Overlay {
         }
     }
 
+    /// Adds a single path to the overlay and remembers its id for provenance tracking.
+    /// Edges of results extracted with `overlay_with_sources` or `build_shape_vectors_with_sources` report this id.
+    /// - `contour`: An array of points that form a closed path.
+    /// - `shape_type`: Specifies the role of the added path in the overlay operation, either as `Subject` or `Clip`.
+    /// - `id`: A user id of the path.
+    #[inline]
+    pub fn add_contour_with_id(&mut self, contour: &[IntPoint], shape_type: ShapeType, id: usize) {
+        let start = self.segments.len();
+        self.add_contour(contour, shape_type);
+        let (direct, _) = ShapeCountBoolean::with_shape_type(shape_type);
+        self.sources.add(&self.segments, start, direct, EdgeSource { id, shape_type });
+    }
+
+    /// Adds a single path to the overlay and tracks every edge with its own id.
+    /// The `edge_id` gets the ends of a segment in the path direction and returns its id, or `None` for an untracked edge.
+    /// If collinear points are dropped, a merged segment starts at the start point of its first edge.
+    #[inline]
+    pub(crate) fn add_contour_with_edge_ids<F: FnMut(IntPoint, IntPoint) -> Option<usize>>(
+        &mut self,
+        contour: &[IntPoint],
+        shape_type: ShapeType,
+        edge_id: F,
+    ) {
+        let start = self.segments.len();
+        self.add_contour(contour, shape_type);
+        let (direct, _) = ShapeCountBoolean::with_shape_type(shape_type);
+        self.sources.add_edges(&self.segments, start, direct, shape_type, edge_id);
+    }
+
+    /// Adds a single shape to the overlay and remembers its id for provenance tracking.
+    /// All contours of the shape share the same id.
+    /// - `shape`: A reference to a `IntShape` instance to be added.
+    /// - `shape_type`: Specifies the role of the added shape in the overlay operation, either as `Subject` or `Clip`.
+    /// - `id`: A user id of the shape.
+    #[inline]
+    pub fn add_shape_with_id(&mut self, shape: &IntShape, shape_type: ShapeType, id: usize) {
+        for contour in shape.iter() {
+            self.add_contour_with_id(contour, shape_type, id);
+        }
+    }
+
     #[inline]
     pub fn clear(&mut self) {
         self.segments.clear();
+        self.sources.clear();
     }
 
     /// Adds multiple flat-shape to the overlay as either subject or clip shapes.
@@ -256,8 +304,7 @@ impl Overlay {
         fill_rule: FillRule,
         overlay_rule: OverlayRule,
     ) -> Vec<VectorShape> {
-        self.split_solver
-            .split_segments(&mut self.segments, &self.solver);
+        self.split();
         if self.segments.is_empty() {
             return Vec::new();
         }
@@ -272,11 +319,25 @@ impl Overlay {
             .extract_shape_vectors(overlay_rule)
     }
 
+    /// The same as `build_shape_vectors`, but also reports which input contours produced every `VectorEdge`.
+    /// Only contours added with `add_contour_with_id` or `add_shape_with_id` are reported.
+    /// - `fill_rule`: The build rule to use for the shapes.
+    /// - `overlay_rule`: The overlay rule to apply.
+    /// - Returns: Vector shapes and the sources of every edge, `sources[i][j][k]` belongs to `shapes[i][j][k]`.
+    pub fn build_shape_vectors_with_sources(
+        &mut self,
+        fill_rule: FillRule,
+        overlay_rule: OverlayRule,
+    ) -> (Vec<VectorShape>, ShapesSources) {
+        let shapes = self.build_shape_vectors(fill_rule, overlay_rule);
+        let sources = self.sources.vectors_sources(&self.segments, &shapes);
+        (shapes, sources)
+    }
+
     /// Convert into vectors from the added paths or shapes, applying the specified build rule. This method is particularly useful for development purposes and for creating visualizations in educational demos, where understanding the impact of different rules on the final geometry is crucial.
     /// - `fill_rule`: The build rule to use for the shapes.
     pub fn build_separate_vectors(&mut self, fill_rule: FillRule) -> Vec<VectorEdge> {
-        self.split_solver
-            .split_segments(&mut self.segments, &self.solver);
+        self.split();
         if self.segments.is_empty() {
             return Vec::new();
         }
@@ -289,8 +350,7 @@ impl Overlay {
     /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes, influencing how the resulting graph represents intersections and unions.
     #[inline]
     pub fn build_graph_view(&mut self, fill_rule: FillRule) -> Option<OverlayGraph> {
-        self.split_solver
-            .split_segments(&mut self.segments, &self.solver);
+        self.split();
         if self.segments.is_empty() {
             return None;
         }
@@ -344,8 +404,7 @@ impl Overlay {
     /// particularly for complex or resource-intensive geometries.
     #[inline]
     pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> IntShapes {
-        self.split_solver
-            .split_segments(&mut self.segments, &self.solver);
+        self.split();
         if self.segments.is_empty() {
             return Vec::new();
         }
@@ -363,6 +422,55 @@ impl Overlay {
         self.boolean_buffer = Some(buffer);
         shapes
     }
+
+    /// Executes a single Boolean operation on the current geometry using a user-defined overlay rule.
+    /// - `rule`: The custom rule, see [`CustomOverlayRule`].
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    /// - Returns: A vector of `IntShape`, representing the geometric result of the applied rule.
+    ///
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    #[inline]
+    pub fn overlay_custom(&mut self, rule: CustomOverlayRule, fill_rule: FillRule) -> IntShapes {
+        self.split();
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+        let mut buffer = self.boolean_buffer.take().unwrap_or_default();
+        let shapes = self
+            .graph_builder
+            .build_boolean_all(fill_rule, self.options, &self.solver, &self.segments)
+            .extract_shapes_custom(rule, &mut buffer);
+        self.boolean_buffer = Some(buffer);
+        shapes
+    }
+
+    /// The same as `overlay`, but also reports which input contours produced every output edge.
+    /// Only contours added with `add_contour_with_id` or `add_shape_with_id` are reported.
+    ///
+    /// ### Parameters:
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    /// - Returns: Shapes and the sources of every edge, where `sources[i][j][k]` belongs to the edge
+    ///   from `shapes[i][j][k]` to the next point of the same contour.
+    ///
+    /// Note: An edge may have several sources if input contours overlap, and none if it was produced
+    /// by a contour added without id. The sources are carried through the split with the segments,
+    /// so points moved by the intersection rounding do not matter.
+    pub fn overlay_with_sources(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> (IntShapes, ShapesSources) {
+        let shapes = self.overlay(overlay_rule, fill_rule);
+        let sources = self.sources.contours_sources(&self.segments, &shapes);
+        (shapes, sources)
+    }
+
+    /// Splits the segments, keeping the sources of the contours added with id.
+    #[inline]
+    pub(crate) fn split(&mut self) -> bool {
+        if self.sources.is_empty() {
+            self.split_solver.split_segments(&mut self.segments, &self.solver)
+        } else {
+            self.sources.split(&mut self.split_solver, &mut self.segments, &self.solver)
+        }
+    }
 }
 
 impl Default for IntOverlayOptions {
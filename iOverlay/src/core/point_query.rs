@@ -0,0 +1,419 @@
+//! This module provides point-in-polygon queries. A `PointQuery` is built once from the split
+//! segments of an `Overlay`, from an `OverlayGraph` or from `IntShapes`, and then answers winding
+//! numbers, fill state, containment and boundary hits for single points or large batches.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use crate::core::fill_rule::FillRule;
+use crate::core::graph::OverlayGraph;
+use crate::core::overlay::Overlay;
+use crate::core::overlay_rule::OverlayRule;
+use crate::segm::segment::{SegmentFill, CLIP_BOTTOM, CLIP_TOP, SUBJ_BOTTOM, SUBJ_TOP};
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntShape;
+
+/// Result of a point query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PointWinding {
+    /// Winding number of the subject at the point.
+    pub subj: i32,
+    /// Winding number of the clip at the point.
+    pub clip: i32,
+    /// `true` if the point lies exactly on an edge.
+    pub is_boundary: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct QuerySegment {
+    a: IntPoint,
+    b: IntPoint,
+    subj: i32,
+    clip: i32,
+    shape: usize,
+}
+
+/// A point location structure.
+///
+/// Winding numbers follow the same convention as the fill sweep: every segment adds its count
+/// to the region above it. A point that lies on an edge is reported as a boundary hit and gets
+/// the winding number of the region below (or left of) that edge.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay::Overlay;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+///
+/// let subj = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+/// let clip = [IntPoint::new(5, 0), IntPoint::new(5, 10), IntPoint::new(15, 10), IntPoint::new(15, 0)];
+///
+/// let query = Overlay::with_contour(&subj, &clip).build_point_query(FillRule::NonZero);
+///
+/// assert!(query.contains(IntPoint::new(7, 5), OverlayRule::Intersect));
+/// assert!(!query.contains(IntPoint::new(12, 5), OverlayRule::Difference));
+/// assert!(query.winding(IntPoint::new(10, 5)).is_boundary);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PointQuery {
+    fill_rule: FillRule,
+    slabs: SlabTree,
+    // sorted end points of all segments
+    ends: Vec<IntPoint>,
+    // disjoint y ranges of the vertical segments sorted by x
+    verticals: Vec<(i32, i32, i32)>,
+}
+
+/// A segment tree over the x slabs between the segment ends. Every segment is kept by the nodes whose
+/// slabs it spans completely, so the segments of a node do not cross there and are sorted from bottom to top.
+#[derive(Debug, Clone)]
+struct SlabTree {
+    // the borders of the slabs, slab `i` is `xs[i] <= x < xs[i + 1]`
+    xs: Vec<i32>,
+    // the heap layout: node `i` has children `2 * i` and `2 * i + 1`, the leaves start at `size`
+    size: usize,
+    // node `i` keeps `items[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<usize>,
+    items: Vec<SlabItem>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SlabItem {
+    segment: QuerySegment,
+    // the sum of the counts of this segment and all segments under it in the node
+    subj: i32,
+    clip: i32,
+}
+
+impl PointQuery {
+    /// Creates a query from shapes. Every shape is treated as a subject, its index is reported by [`PointQuery::shape_index`].
+    /// - `shapes`: Shapes without overlaps, e.g. the result of an overlay operation.
+    /// - `fill_rule`: The fill rule used for containment.
+    pub fn with_shapes(shapes: &[IntShape], fill_rule: FillRule) -> Self {
+        let mut segments = Vec::new();
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            for contour in shape.iter() {
+                let mut a = if let Some(&p) = contour.last() { p } else { continue; };
+                for &b in contour.iter() {
+                    // a counterclockwise contour adds +1 above its left to right segments
+                    if a < b {
+                        segments.push(QuerySegment { a, b, subj: 1, clip: 0, shape: shape_index });
+                    } else if b < a {
+                        segments.push(QuerySegment { a: b, b: a, subj: -1, clip: 0, shape: shape_index });
+                    }
+                    a = b;
+                }
+            }
+        }
+        Self::new(segments, fill_rule)
+    }
+
+    // the segments must not cross each other
+    fn new(segments: Vec<QuerySegment>, fill_rule: FillRule) -> Self {
+        let mut ends: Vec<IntPoint> = segments.iter().flat_map(|s| [s.a, s.b]).collect();
+        ends.sort_unstable();
+        ends.dedup();
+
+        let mut vertical_segments: Vec<(i32, i32, i32)> = segments
+            .iter()
+            .filter(|s| s.a.x == s.b.x)
+            .map(|s| (s.a.x, s.a.y, s.b.y))
+            .collect();
+        vertical_segments.sort_unstable();
+        let mut verticals: Vec<(i32, i32, i32)> = Vec::with_capacity(vertical_segments.len());
+        for (x, y0, y1) in vertical_segments {
+            match verticals.last_mut() {
+                Some(last) if last.0 == x && y0 <= last.2 => last.2 = last.2.max(y1),
+                _ => verticals.push((x, y0, y1)),
+            }
+        }
+
+        let slabs = SlabTree::new(segments.into_iter().filter(|s| s.a.x != s.b.x).collect());
+
+        Self { fill_rule, slabs, ends, verticals }
+    }
+
+    /// Returns winding numbers of the subject and the clip at the point.
+    /// The cost is `O(log² n)` for `n` segments.
+    pub fn winding(&self, p: IntPoint) -> PointWinding {
+        let mut result = PointWinding::default();
+        self.slabs.for_each_node(p, |items| {
+            let count = items.partition_point(|item| item.segment.cross(p) > 0);
+            if count > 0 {
+                result.subj += items[count - 1].subj;
+                result.clip += items[count - 1].clip;
+            }
+            if count < items.len() && items[count].segment.cross(p) == 0 {
+                result.is_boundary = true;
+            }
+        });
+
+        if !result.is_boundary {
+            result.is_boundary = self.ends.binary_search(&p).is_ok() || self.is_on_vertical(p);
+        }
+
+        result
+    }
+
+    /// Returns the fill state of the region at the point under the fill rule: a combination of `SUBJ_TOP` and `CLIP_TOP`.
+    #[inline]
+    pub fn fill(&self, p: IntPoint) -> SegmentFill {
+        self.fill_of(self.winding(p))
+    }
+
+    /// Checks if the point is inside the result of the overlay rule. Boundary points are not included.
+    #[inline]
+    pub fn contains(&self, p: IntPoint, overlay_rule: OverlayRule) -> bool {
+        let winding = self.winding(p);
+        !winding.is_boundary && overlay_rule.is_state_filled(self.fill_of(winding))
+    }
+
+    /// Returns the index of the shape containing the point, for queries created with [`PointQuery::with_shapes`].
+    /// Boundary points are not included.
+    pub fn shape_index(&self, p: IntPoint) -> Option<usize> {
+        let winding = self.winding(p);
+        if winding.is_boundary || self.fill_of(winding) & SUBJ_TOP == 0 {
+            return None;
+        }
+
+        // the shape of the nearest segment under the point, every node gives its top one
+        let mut nearest: Option<&QuerySegment> = None;
+        self.slabs.for_each_node(p, |items| {
+            let count = items.partition_point(|item| item.segment.cross(p) > 0);
+            if count > 0 {
+                let s = &items[count - 1].segment;
+                if nearest.is_none_or(|n| n.is_under(s)) {
+                    nearest = Some(s);
+                }
+            }
+        });
+
+        nearest.map(|s| s.shape)
+    }
+
+    /// Returns winding numbers for every point.
+    pub fn winding_batch(&self, points: &[IntPoint]) -> Vec<PointWinding> {
+        points.iter().map(|&p| self.winding(p)).collect()
+    }
+
+    /// Checks every point against the result of the overlay rule. Boundary points are not included.
+    pub fn contains_batch(&self, points: &[IntPoint], overlay_rule: OverlayRule) -> Vec<bool> {
+        self.winding_batch(points)
+            .into_iter()
+            .map(|w| !w.is_boundary && overlay_rule.is_state_filled(self.fill_of(w)))
+            .collect()
+    }
+
+    fn is_on_vertical(&self, p: IntPoint) -> bool {
+        let index = self.verticals.partition_point(|&(x, y0, _)| x < p.x || x == p.x && y0 <= p.y);
+        index > 0 && {
+            let (x, _, y1) = self.verticals[index - 1];
+            x == p.x && p.y <= y1
+        }
+    }
+
+    #[inline]
+    fn fill_of(&self, winding: PointWinding) -> SegmentFill {
+        let subj = self.fill_rule.is_filled(winding.subj) as SegmentFill;
+        let clip = self.fill_rule.is_filled(winding.clip) as SegmentFill;
+        subj | (clip << 2)
+    }
+}
+
+impl SlabTree {
+    // the segments must not be vertical
+    fn new(segments: Vec<QuerySegment>) -> Self {
+        let mut xs: Vec<i32> = segments.iter().flat_map(|s| [s.a.x, s.b.x]).collect();
+        xs.sort_unstable();
+        xs.dedup();
+
+        let slabs_count = xs.len().saturating_sub(1);
+        let size = slabs_count.next_power_of_two();
+        let mut nodes: Vec<Vec<QuerySegment>> = vec![Vec::new(); 2 * size];
+        for s in segments {
+            let start = xs.partition_point(|&x| x < s.a.x);
+            let end = xs.partition_point(|&x| x < s.b.x);
+            Self::insert(&mut nodes, 1, 0, size, start, end, s);
+        }
+
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut items = Vec::new();
+        offsets.push(0);
+        for (index, mut node) in nodes.into_iter().enumerate() {
+            if !node.is_empty() {
+                // all segments of the node span its slabs, so they are ordered by y in the middle of them
+                let (start, end) = Self::node_slabs(index, size);
+                let x2 = xs[start] as i64 + xs[end.min(slabs_count)] as i64;
+                node.sort_unstable_by(|s0, s1| s0.cmp_at(s1, x2));
+                let (mut subj, mut clip) = (0, 0);
+                for segment in node {
+                    subj += segment.subj;
+                    clip += segment.clip;
+                    items.push(SlabItem { segment, subj, clip });
+                }
+            }
+            offsets.push(items.len());
+        }
+
+        Self { xs, size, offsets, items }
+    }
+
+    fn insert(
+        nodes: &mut [Vec<QuerySegment>],
+        index: usize,
+        node_start: usize,
+        node_end: usize,
+        start: usize,
+        end: usize,
+        segment: QuerySegment,
+    ) {
+        if end <= node_start || node_end <= start {
+            return;
+        }
+        if start <= node_start && node_end <= end {
+            nodes[index].push(segment);
+            return;
+        }
+        let middle = (node_start + node_end) / 2;
+        Self::insert(nodes, 2 * index, node_start, middle, start, end, segment);
+        Self::insert(nodes, 2 * index + 1, middle, node_end, start, end, segment);
+    }
+
+    // the slabs `start..end` of the node
+    #[inline]
+    fn node_slabs(index: usize, size: usize) -> (usize, usize) {
+        let depth = index.ilog2();
+        let width = size >> depth;
+        let start = (index - (1 << depth)) * width;
+        (start, start + width)
+    }
+
+    /// Calls `f` with the segments of every node whose slabs contain `p.x`, from the leaf up to the root.
+    #[inline]
+    fn for_each_node<'a, F: FnMut(&'a [SlabItem])>(&'a self, p: IntPoint, mut f: F) {
+        let slab = self.xs.partition_point(|&x| x <= p.x);
+        if slab == 0 || slab >= self.xs.len() {
+            return;
+        }
+        let mut index = self.size + slab - 1;
+        while index > 0 {
+            f(&self.items[self.offsets[index]..self.offsets[index + 1]]);
+            index >>= 1;
+        }
+    }
+}
+
+impl QuerySegment {
+    /// Compares the y of both segments at `x2 / 2`.
+    #[inline(always)]
+    fn cmp_at(&self, other: &QuerySegment, x2: i64) -> Ordering {
+        // 2 * y * dx = 2 * a.y * dx + dy * (x2 - 2 * a.x)
+        let dx0 = self.b.x as i128 - self.a.x as i128;
+        let dx1 = other.b.x as i128 - other.a.x as i128;
+        let y0 = 2 * self.a.y as i128 * dx0 + (self.b.y as i128 - self.a.y as i128) * (x2 as i128 - 2 * self.a.x as i128);
+        let y1 = 2 * other.a.y as i128 * dx1 + (other.b.y as i128 - other.a.y as i128) * (x2 as i128 - 2 * other.a.x as i128);
+        (y0 * dx1).cmp(&(y1 * dx0))
+    }
+
+    #[inline(always)]
+    fn cross(&self, p: IntPoint) -> i128 {
+        let ex = self.b.x as i64 - self.a.x as i64;
+        let ey = self.b.y as i64 - self.a.y as i64;
+        let px = p.x as i64 - self.a.x as i64;
+        let py = p.y as i64 - self.a.y as i64;
+        ex as i128 * py as i128 - ey as i128 * px as i128
+    }
+
+    /// Checks if `self` is under `other` at the x range they share.
+    #[inline(always)]
+    fn is_under(&self, other: &QuerySegment) -> bool {
+        let x = self.a.x.max(other.a.x);
+        // compare y of both segments at x: y = a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x)
+        let dx0 = self.b.x as i128 - self.a.x as i128;
+        let dx1 = other.b.x as i128 - other.a.x as i128;
+        let y0 = self.a.y as i128 * dx0 + (self.b.y as i128 - self.a.y as i128) * (x as i128 - self.a.x as i128);
+        let y1 = other.a.y as i128 * dx1 + (other.b.y as i128 - other.a.y as i128) * (x as i128 - other.a.x as i128);
+        let cmp = y0 * dx1 - y1 * dx0;
+        if cmp != 0 {
+            return cmp < 0;
+        }
+        // the same point, compare by slope
+        (self.b.y as i128 - self.a.y as i128) * dx1 < (other.b.y as i128 - other.a.y as i128) * dx0
+    }
+}
+
+impl FillRule {
+    #[inline(always)]
+    fn is_filled(&self, count: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => count & 1 == 1,
+            FillRule::NonZero => count != 0,
+            FillRule::Positive => count > 0,
+            FillRule::Negative => count < 0,
+        }
+    }
+}
+
+impl OverlayRule {
+    /// Checks if a region with the given state (`SUBJ_TOP`, `CLIP_TOP` bits) belongs to the rule result.
+    #[inline(always)]
+    fn is_state_filled(&self, state: SegmentFill) -> bool {
+        let subj = state & SUBJ_TOP != 0;
+        let clip = state & CLIP_TOP != 0;
+        match self {
+            OverlayRule::Subject => subj,
+            OverlayRule::Clip => clip,
+            OverlayRule::Intersect => subj && clip,
+            OverlayRule::Union => subj || clip,
+            OverlayRule::Difference => subj && !clip,
+            OverlayRule::InverseDifference => !subj && clip,
+            OverlayRule::Xor => subj != clip,
+        }
+    }
+}
+
+impl Overlay {
+    /// Creates a `PointQuery` from the added paths or shapes. Winding numbers are exact input winding numbers.
+    /// - `fill_rule`: The fill rule used for containment.
+    pub fn build_point_query(&mut self, fill_rule: FillRule) -> PointQuery {
+        self.split();
+        let segments = self
+            .segments
+            .iter()
+            .map(|s| QuerySegment {
+                a: s.x_segment.a,
+                b: s.x_segment.b,
+                subj: s.count.subj,
+                clip: s.count.clip,
+                shape: 0,
+            })
+            .collect();
+        PointQuery::new(segments, fill_rule)
+    }
+}
+
+impl OverlayGraph<'_> {
+    /// Creates a `PointQuery` from the graph links. The graph keeps only the fill state,
+    /// so winding numbers are `0` or `1` and the query uses `FillRule::NonZero`.
+    pub fn build_point_query(&self) -> PointQuery {
+        let segments = self
+            .links
+            .iter()
+            .filter_map(|link| {
+                let subj = (link.fill & SUBJ_TOP != 0) as i32 - (link.fill & SUBJ_BOTTOM != 0) as i32;
+                let clip = (link.fill & CLIP_TOP != 0) as i32 - (link.fill & CLIP_BOTTOM != 0) as i32;
+                if subj == 0 && clip == 0 {
+                    // the same state on both sides, nothing to count
+                    return None;
+                }
+                let (a, b) = if link.a.point < link.b.point {
+                    (link.a.point, link.b.point)
+                } else {
+                    (link.b.point, link.a.point)
+                };
+                Some(QuerySegment { a, b, subj, clip, shape: 0 })
+            })
+            .collect();
+        PointQuery::new(segments, FillRule::NonZero)
+    }
+}
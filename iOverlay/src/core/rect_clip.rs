@@ -0,0 +1,259 @@
+//! This module provides a specialized clip by an axis-aligned rectangle and a batch split of shapes across a grid of tiles.
+//! Contours are grouped by their bounding boxes, only the groups which reach a rectangle go through the `Overlay`
+//! with it, so the result is the same as a general intersection and the contours far from the rectangle cost nothing.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+use i_shape::int::path::{IntPath, IntPaths};
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::geom::overlap::overlap_groups;
+
+/// Trait `RectClip` clips shapes by an axis-aligned rectangle.
+///
+/// The result is the same as `OverlayRule::Intersect` with the rectangle as the clip.
+/// A contour can change the result only through the contours crossing it, so a group of contours with overlapped
+/// bounding boxes is skipped as a whole if none of them touches the rectangle.
+pub trait RectClip {
+    /// Returns the part of the shapes inside `rect`.
+    /// - `rect`: The clip rectangle, it is always filled regardless of `fill_rule`.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    fn clip_by_rect(&self, rect: &IntRect, fill_rule: FillRule) -> IntShapes;
+}
+
+/// Trait `RectClipLines` clips open polylines by an axis-aligned rectangle.
+pub trait RectClipLines {
+    /// Returns the parts of the polylines inside `rect`.
+    /// - `rect`: The clip rectangle.
+    /// - `boundary_included`: If `true`, the parts lying on the rectangle sides are kept.
+    fn clip_lines_by_rect(&self, rect: &IntRect, boundary_included: bool) -> IntPaths;
+}
+
+impl RectClip for [IntShape] {
+    fn clip_by_rect(&self, rect: &IntRect, fill_rule: FillRule) -> IntShapes {
+        let grid = TileGrid::new(IntPoint::new(rect.min_x, rect.min_y), rect.width(), rect.height(), 1, 1);
+        grid.split_shapes(self, fill_rule).pop().unwrap_or_default()
+    }
+}
+
+impl RectClip for [IntContour] {
+    #[inline]
+    fn clip_by_rect(&self, rect: &IntRect, fill_rule: FillRule) -> IntShapes {
+        [self.to_vec()].clip_by_rect(rect, fill_rule)
+    }
+}
+
+impl RectClipLines for [IntPath] {
+    fn clip_lines_by_rect(&self, rect: &IntRect, boundary_included: bool) -> IntPaths {
+        let mut result = Vec::new();
+        for path in self.iter() {
+            let mut piece: Vec<IntPoint> = Vec::new();
+            for w in path.windows(2) {
+                let Some((a, b)) = clip_segment(w[0], w[1], rect) else {
+                    continue;
+                };
+                if a == b || (!boundary_included && is_on_side(a, b, rect)) {
+                    continue;
+                }
+                if piece.last() != Some(&a) {
+                    if piece.len() > 1 {
+                        result.push(core::mem::take(&mut piece));
+                    }
+                    piece.clear();
+                    piece.push(a);
+                }
+                piece.push(b);
+            }
+            if piece.len() > 1 {
+                result.push(piece);
+            }
+        }
+        result
+    }
+}
+
+/// A regular grid of tiles, `columns` by `rows`, starting at `origin`.
+#[derive(Debug, Clone, Copy)]
+pub struct TileGrid {
+    pub origin: IntPoint,
+    pub tile_width: i32,
+    pub tile_height: i32,
+    pub columns: usize,
+    pub rows: usize,
+}
+
+impl TileGrid {
+    /// Creates a grid. The tile size must be positive and the whole grid must fit into `i32`.
+    #[inline]
+    pub fn new(origin: IntPoint, tile_width: i32, tile_height: i32, columns: usize, rows: usize) -> Self {
+        Self { origin, tile_width, tile_height, columns, rows }
+    }
+
+    /// Returns the rectangle of the tile in `column` and `row`.
+    #[inline]
+    pub fn tile_rect(&self, column: usize, row: usize) -> IntRect {
+        let min_x = self.origin.x + column as i32 * self.tile_width;
+        let min_y = self.origin.y + row as i32 * self.tile_height;
+        IntRect::new(min_x, min_x + self.tile_width, min_y, min_y + self.tile_height)
+    }
+
+    /// Splits the shapes across the tiles.
+    ///
+    /// Returns the clipped shapes of every tile in row-major order, the tile in `column` and `row` is at
+    /// `row * columns + column`. Each entry is the same as `clip_by_rect` with the tile rectangle.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn split_shapes(&self, shapes: &[IntShape], fill_rule: FillRule) -> Vec<IntShapes> {
+        let count = self.columns * self.rows;
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let xs = lines(self.origin.x, self.tile_width, self.columns);
+        let ys = lines(self.origin.y, self.tile_height, self.rows);
+
+        let mut contours = Vec::new();
+        let mut rects = Vec::new();
+        for contour in shapes.iter().flatten() {
+            if let Some(rect) = IntRect::with_points(contour) {
+                contours.push(contour);
+                rects.push(rect);
+            }
+        }
+
+        // a group goes to every tile touched by one of its contours
+        let groups = overlap_groups(&rects);
+        let mut tiles: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for (index, group) in groups.iter().enumerate() {
+            for &i in group.iter() {
+                let rect = &rects[i];
+                let (c0, c1) = tile_range(&xs, rect.min_x, rect.max_x);
+                let (r0, r1) = tile_range(&ys, rect.min_y, rect.max_y);
+                for row in r0..r1 {
+                    for tile in tiles[row * self.columns..].iter_mut().take(c1).skip(c0) {
+                        if tile.last() != Some(&index) {
+                            tile.push(index);
+                        }
+                    }
+                }
+            }
+        }
+
+        tiles
+            .into_iter()
+            .enumerate()
+            .map(|(tile, tile_groups)| {
+                if tile_groups.is_empty() {
+                    return Vec::new();
+                }
+                let ids = tile_groups.iter().flat_map(|&index| groups[index].iter());
+                let capacity = ids.clone().map(|&i| contours[i].len()).sum::<usize>() + 4;
+                let mut overlay = Overlay::new(capacity);
+                for &i in ids {
+                    overlay.add_contour(contours[i], ShapeType::Subject);
+                }
+                let rect = self.tile_rect(tile % self.columns, tile / self.columns);
+                overlay.add_contour(&rect_contour(&rect, fill_rule), ShapeType::Clip);
+                overlay.overlay(OverlayRule::Intersect, fill_rule)
+            })
+            .collect()
+    }
+}
+
+#[inline]
+fn lines(origin: i32, step: i32, count: usize) -> Vec<i32> {
+    (0..=count).map(|i| origin + i as i32 * step).collect()
+}
+
+/// The range of slabs between the lines `xs` touched by the segment from `lo` to `hi`.
+#[inline]
+fn tile_range(xs: &[i32], lo: i32, hi: i32) -> (usize, usize) {
+    let first = xs.partition_point(|&x| x < lo).saturating_sub(1);
+    let end = xs.partition_point(|&x| x <= hi).min(xs.len() - 1);
+    (first, end.max(first))
+}
+
+/// The rectangle as a contour with the winding number filled by `fill_rule`.
+fn rect_contour(rect: &IntRect, fill_rule: FillRule) -> IntContour {
+    let mut contour = vec![
+        IntPoint::new(rect.min_x, rect.min_y),
+        IntPoint::new(rect.max_x, rect.min_y),
+        IntPoint::new(rect.max_x, rect.max_y),
+        IntPoint::new(rect.min_x, rect.max_y),
+    ];
+    if fill_rule == FillRule::Negative {
+        contour.reverse();
+    }
+    contour
+}
+
+/// Returns the point of the segment at `x`, the result does not depend on the segment direction.
+#[inline]
+fn point_at_x(p: IntPoint, q: IntPoint, x: i32) -> IntPoint {
+    if x == p.x {
+        return p;
+    }
+    if x == q.x {
+        return q;
+    }
+    let (s, e) = if (p.x, p.y) < (q.x, q.y) { (p, q) } else { (q, p) };
+    // the product of two full range differences does not fit into i64
+    let dy = (x as i128 - s.x as i128) * (e.y as i128 - s.y as i128);
+    let y = s.y as i128 + div_round(dy, e.x as i128 - s.x as i128);
+    IntPoint::new(x, y as i32)
+}
+
+#[inline]
+fn point_at_y(p: IntPoint, q: IntPoint, y: i32) -> IntPoint {
+    let t = point_at_x(IntPoint::new(p.y, p.x), IntPoint::new(q.y, q.x), y);
+    IntPoint::new(t.y, t.x)
+}
+
+/// Divides with rounding half away from zero, `b` is positive.
+#[inline]
+fn div_round(a: i128, b: i128) -> i128 {
+    if a >= 0 { (2 * a + b) / (2 * b) } else { -((-2 * a + b) / (2 * b)) }
+}
+
+fn clip_segment(p: IntPoint, q: IntPoint, rect: &IntRect) -> Option<(IntPoint, IntPoint)> {
+    if p.x.max(q.x) < rect.min_x || p.x.min(q.x) > rect.max_x {
+        return None;
+    }
+    // a point inside the range is kept as is, a horizontal or a vertical segment has no single point at its own coordinate
+    let a = if p.x < rect.min_x || p.x > rect.max_x { point_at_x(p, q, p.x.clamp(rect.min_x, rect.max_x)) } else { p };
+    let b = if q.x < rect.min_x || q.x > rect.max_x { point_at_x(p, q, q.x.clamp(rect.min_x, rect.max_x)) } else { q };
+
+    if a.y.max(b.y) < rect.min_y || a.y.min(b.y) > rect.max_y {
+        return None;
+    }
+    let c = if a.y < rect.min_y || a.y > rect.max_y { point_at_y(a, b, a.y.clamp(rect.min_y, rect.max_y)) } else { a };
+    let d = if b.y < rect.min_y || b.y > rect.max_y { point_at_y(a, b, b.y.clamp(rect.min_y, rect.max_y)) } else { b };
+
+    Some((c, d))
+}
+
+#[inline]
+fn is_on_side(a: IntPoint, b: IntPoint, rect: &IntRect) -> bool {
+    (a.x == b.x && (a.x == rect.min_x || a.x == rect.max_x)) || (a.y == b.y && (a.y == rect.min_y || a.y == rect.max_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::rect_clip::{div_round, point_at_x};
+
+    #[test]
+    fn test_point_at_x() {
+        let a = IntPoint::new(0, 0);
+        let b = IntPoint::new(3, 1);
+        assert_eq!(point_at_x(a, b, 1), IntPoint::new(1, 0));
+        assert_eq!(point_at_x(a, b, 2), IntPoint::new(2, 1));
+        assert_eq!(point_at_x(b, a, 2), point_at_x(a, b, 2));
+        assert_eq!(div_round(-3, 2), -2);
+        assert_eq!(div_round(3, 2), 2);
+        assert_eq!(div_round(-1, 3), 0);
+    }
+}
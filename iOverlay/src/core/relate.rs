@@ -0,0 +1,242 @@
+//! This module provides spatial predicates in terms of the DE-9IM model.
+//! The intersection matrix is read from the fill state of the `OverlayGraph` links and nodes,
+//! so the relation of two geometries is found without extracting any shapes.
+
+use core::fmt;
+use crate::core::fill_rule::FillRule;
+use crate::core::graph::{OverlayGraph, OverlayNode};
+use crate::core::link::OverlayLink;
+use crate::core::overlay::Overlay;
+use crate::segm::segment::{SegmentFill, CLIP_BOTTOM, CLIP_TOP, SUBJ_BOTTOM, SUBJ_TOP};
+
+/// The location of a point relative to a geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Interior = 0,
+    Boundary = 1,
+    Exterior = 2,
+}
+
+/// The dimension of an intersection, `Empty` stands for no intersection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dimension {
+    Empty,
+    Point,
+    Line,
+    Area,
+}
+
+/// The DE-9IM matrix of the subject and the clip.
+/// A row is the location in the subject, a column is the location in the clip.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay::Overlay;
+///
+/// let subj = [IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)];
+/// let clip = [IntPoint::new(10, 0), IntPoint::new(20, 0), IntPoint::new(20, 10), IntPoint::new(10, 10)];
+///
+/// let matrix = Overlay::with_contour(&subj, &clip).relate(FillRule::NonZero);
+///
+/// assert_eq!(matrix.to_string(), "FF2F11212");
+/// assert!(matrix.touches());
+/// assert!(!matrix.overlaps());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntersectionMatrix {
+    cells: [[Dimension; 3]; 3],
+}
+
+impl IntersectionMatrix {
+    /// The matrix of two empty geometries: only the exteriors intersect.
+    #[inline]
+    pub fn empty() -> Self {
+        let mut cells = [[Dimension::Empty; 3]; 3];
+        cells[2][2] = Dimension::Area;
+        Self { cells }
+    }
+
+    /// Returns the dimension of the intersection of the subject location `a` and the clip location `b`.
+    #[inline]
+    pub fn get(&self, a: Location, b: Location) -> Dimension {
+        self.cells[a as usize][b as usize]
+    }
+
+    /// Checks the matrix against a DE-9IM pattern of 9 symbols: `T` any intersection, `F` no intersection,
+    /// `0`, `1`, `2` the exact dimension and `*` anything.
+    /// Returns `false` if the pattern is not valid.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let bytes = pattern.as_bytes();
+        if bytes.len() != 9 {
+            return false;
+        }
+        self.cells.iter().flatten().zip(bytes.iter()).all(|(&dim, &symbol)| match symbol {
+            b'*' => true,
+            b'T' => dim != Dimension::Empty,
+            b'F' => dim == Dimension::Empty,
+            b'0' => dim == Dimension::Point,
+            b'1' => dim == Dimension::Line,
+            b'2' => dim == Dimension::Area,
+            _ => false,
+        })
+    }
+
+    /// The geometries have at least one common point.
+    #[inline]
+    pub fn intersects(&self) -> bool {
+        !self.disjoint()
+    }
+
+    /// The geometries have no common points.
+    #[inline]
+    pub fn disjoint(&self) -> bool {
+        self.matches("FF*FF****")
+    }
+
+    /// The geometries have common points, but their interiors do not intersect.
+    #[inline]
+    pub fn touches(&self) -> bool {
+        self.matches("FT*******") || self.matches("F**T*****") || self.matches("F***T****")
+    }
+
+    /// No point of the clip lies outside the subject and their interiors intersect.
+    #[inline]
+    pub fn contains(&self) -> bool {
+        self.matches("T*****FF*")
+    }
+
+    /// No point of the subject lies outside the clip and their interiors intersect.
+    #[inline]
+    pub fn within(&self) -> bool {
+        self.matches("T*F**F***")
+    }
+
+    /// No point of the clip lies outside the subject.
+    #[inline]
+    pub fn covers(&self) -> bool {
+        self.intersects() && self.matches("******FF*")
+    }
+
+    /// No point of the subject lies outside the clip.
+    #[inline]
+    pub fn covered_by(&self) -> bool {
+        self.intersects() && self.matches("**F**F***")
+    }
+
+    /// The interiors intersect and each geometry has points outside the other one.
+    #[inline]
+    pub fn overlaps(&self) -> bool {
+        self.matches("T*T***T**")
+    }
+
+    /// The geometries cover the same area.
+    #[inline]
+    pub fn equals(&self) -> bool {
+        self.matches("T*F**FFF*")
+    }
+
+    #[inline]
+    fn add(&mut self, a: Location, b: Location, dimension: Dimension) {
+        let cell = &mut self.cells[a as usize][b as usize];
+        *cell = (*cell).max(dimension);
+    }
+}
+
+impl fmt::Display for IntersectionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &dim in self.cells.iter().flatten() {
+            let symbol = match dim {
+                Dimension::Empty => 'F',
+                Dimension::Point => '0',
+                Dimension::Line => '1',
+                Dimension::Area => '2',
+            };
+            write!(f, "{}", symbol)?;
+        }
+        Ok(())
+    }
+}
+
+impl OverlayGraph<'_> {
+    /// Computes the DE-9IM matrix of the subject and the clip from the fill state of the graph.
+    /// Every link gives a line in the boundary or the interior of each geometry, every side of a link
+    /// gives an area and every node gives a point.
+    pub fn relate(&self) -> IntersectionMatrix {
+        let mut matrix = IntersectionMatrix::empty();
+
+        for link in self.links.iter() {
+            let fill = link.fill;
+            matrix.add(subj_location(fill), clip_location(fill), Dimension::Line);
+            matrix.add(
+                area_location(fill & SUBJ_TOP != 0),
+                area_location(fill & CLIP_TOP != 0),
+                Dimension::Area,
+            );
+            matrix.add(
+                area_location(fill & SUBJ_BOTTOM != 0),
+                area_location(fill & CLIP_BOTTOM != 0),
+                Dimension::Area,
+            );
+        }
+
+        for node in self.nodes.iter() {
+            let indices: &[usize] = match node {
+                OverlayNode::Bridge(indices) => indices,
+                OverlayNode::Cross(indices) => indices,
+            };
+            let a = node_location(self.links, indices, subj_location);
+            let b = node_location(self.links, indices, clip_location);
+            matrix.add(a, b, Dimension::Point);
+        }
+
+        matrix
+    }
+}
+
+impl Overlay {
+    /// Computes the DE-9IM matrix of the added subject and clip paths.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    pub fn relate(&mut self, fill_rule: FillRule) -> IntersectionMatrix {
+        self.build_graph_view(fill_rule)
+            .map(|graph| graph.relate())
+            .unwrap_or(IntersectionMatrix::empty())
+    }
+}
+
+#[inline]
+fn area_location(is_filled: bool) -> Location {
+    if is_filled { Location::Interior } else { Location::Exterior }
+}
+
+#[inline]
+fn line_location(top: bool, bottom: bool) -> Location {
+    match (top, bottom) {
+        (true, true) => Location::Interior,
+        (false, false) => Location::Exterior,
+        _ => Location::Boundary,
+    }
+}
+
+#[inline]
+fn subj_location(fill: SegmentFill) -> Location {
+    line_location(fill & SUBJ_TOP != 0, fill & SUBJ_BOTTOM != 0)
+}
+
+#[inline]
+fn clip_location(fill: SegmentFill) -> Location {
+    line_location(fill & CLIP_TOP != 0, fill & CLIP_BOTTOM != 0)
+}
+
+/// A node is on the boundary if any of its links is, otherwise all its links are on the same side.
+#[inline]
+fn node_location(links: &[OverlayLink], indices: &[usize], location: fn(SegmentFill) -> Location) -> Location {
+    let mut result = Location::Exterior;
+    for &index in indices.iter() {
+        match location(links[index].fill) {
+            Location::Boundary => return Location::Boundary,
+            other => result = other,
+        }
+    }
+    result
+}
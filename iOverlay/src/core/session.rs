@@ -0,0 +1,260 @@
+//! This module provides an overlay session which caches the result of every independent group of contours.
+//! Contours are grouped into clusters with overlapping bounding boxes. Contours of different clusters
+//! can not interact, so the result is a concatenation of cluster results, and an edit runs the overlay
+//! again only for the clusters it touches. It is not an incremental overlay: a touched cluster is computed from scratch.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::{IntContour, IntShapes};
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{IntOverlayOptions, Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::geom::overlap::{is_overlap, overlap_groups};
+
+struct SessionContour {
+    contour: IntContour,
+    shape_type: ShapeType,
+    rect: IntRect,
+    cluster: usize,
+}
+
+struct Cluster {
+    ids: Vec<usize>,
+    rect: IntRect,
+    result: Option<IntShapes>,
+}
+
+/// This struct keeps contours by id and the results of their independent groups,
+/// so adding, removing or replacing a contour recomputes only the clusters it touches.
+///
+/// Only the result shapes are cached, one per cluster. The split segments and the graph are not kept,
+/// so an edit runs the whole overlay again for every contour of the touched clusters.
+/// When all contours overlap each other by bounding box they form one cluster, and every edit costs
+/// as much as a full `Overlay` run.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay::ShapeType;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::core::session::OverlaySession;
+///
+/// let square = |x: i32| vec![
+///     IntPoint::new(x, 0),
+///     IntPoint::new(x + 10, 0),
+///     IntPoint::new(x + 10, 10),
+///     IntPoint::new(x, 10),
+/// ];
+///
+/// let mut session = OverlaySession::new(OverlayRule::Union, FillRule::NonZero);
+/// let a = session.add_contour(square(0), ShapeType::Subject);
+/// let b = session.add_contour(square(20), ShapeType::Clip);
+/// assert_eq!(session.overlay().len(), 2);
+///
+/// // drag the second square over the first one
+/// session.replace_contour(b, square(5));
+/// assert_eq!(session.overlay().len(), 1);
+///
+/// session.remove_contour(a);
+/// let result = session.overlay();
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(result[0][0].len(), 4);
+/// ```
+pub struct OverlaySession {
+    overlay_rule: OverlayRule,
+    fill_rule: FillRule,
+    options: IntOverlayOptions,
+    solver: Solver,
+    contours: BTreeMap<usize, SessionContour>,
+    clusters: BTreeMap<usize, Cluster>,
+    next_contour_id: usize,
+    next_cluster_id: usize,
+}
+
+impl OverlaySession {
+    /// Creates an empty session.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    #[inline]
+    pub fn new(overlay_rule: OverlayRule, fill_rule: FillRule) -> Self {
+        Self::new_custom(overlay_rule, fill_rule, Default::default(), Default::default())
+    }
+
+    /// Creates an empty session.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    pub fn new_custom(overlay_rule: OverlayRule, fill_rule: FillRule, options: IntOverlayOptions, solver: Solver) -> Self {
+        Self {
+            overlay_rule,
+            fill_rule,
+            options,
+            solver,
+            contours: BTreeMap::new(),
+            clusters: BTreeMap::new(),
+            next_contour_id: 0,
+            next_cluster_id: 0,
+        }
+    }
+
+    /// Changes the rules, all results are recomputed on the next `overlay` call.
+    pub fn set_rules(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) {
+        self.overlay_rule = overlay_rule;
+        self.fill_rule = fill_rule;
+        for cluster in self.clusters.values_mut() {
+            cluster.result = None;
+        }
+    }
+
+    /// Adds a contour.
+    /// - `contour`: The contour points.
+    /// - `shape_type`: Specifies the role of the contour, either as `Subject` or `Clip`.
+    ///
+    /// # Returns
+    /// The id of the contour for later edits.
+    pub fn add_contour(&mut self, contour: IntContour, shape_type: ShapeType) -> usize {
+        let id = self.next_contour_id;
+        self.next_contour_id += 1;
+        self.insert(id, contour, shape_type);
+        id
+    }
+
+    /// Removes a contour.
+    ///
+    /// # Returns
+    /// `false` if there is no contour with the id.
+    pub fn remove_contour(&mut self, id: usize) -> bool {
+        if let Some(item) = self.contours.remove(&id) {
+            self.detach(id, item.cluster);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replaces the points of a contour and keeps its id and shape type.
+    ///
+    /// # Returns
+    /// `false` if there is no contour with the id.
+    pub fn replace_contour(&mut self, id: usize, contour: IntContour) -> bool {
+        if let Some(item) = self.contours.remove(&id) {
+            self.detach(id, item.cluster);
+            self.insert(id, contour, item.shape_type);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the contour by id.
+    #[inline]
+    pub fn contour(&self, id: usize) -> Option<&IntContour> {
+        self.contours.get(&id).map(|item| &item.contour)
+    }
+
+    /// Returns the number of contours.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.contours.len()
+    }
+
+    /// Returns `true` if the session has no contours.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+
+    /// Computes the result of the boolean operation. Only the clusters changed since the last call are recomputed.
+    ///
+    /// # Returns
+    /// A vector of `IntShape` instances representing the result.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    pub fn overlay(&mut self) -> IntShapes {
+        let mut result = Vec::new();
+        for cluster in self.clusters.values_mut() {
+            let shapes = cluster.result.get_or_insert_with(|| {
+                let capacity = cluster.ids.iter().map(|id| self.contours[id].contour.len()).sum();
+                let mut overlay = Overlay::new_custom(capacity, self.options, self.solver);
+                for id in cluster.ids.iter() {
+                    let item = &self.contours[id];
+                    overlay.add_contour(&item.contour, item.shape_type);
+                }
+                overlay.overlay(self.overlay_rule, self.fill_rule)
+            });
+            result.extend_from_slice(shapes);
+        }
+        result
+    }
+
+    /// Returns the number of points in all contours.
+    #[inline]
+    pub fn points_count(&self) -> usize {
+        self.contours.values().map(|item| item.contour.len()).sum()
+    }
+
+    fn insert(&mut self, id: usize, contour: IntContour, shape_type: ShapeType) {
+        let rect = if let Some(rect) = IntRect::with_points(&contour) {
+            rect
+        } else {
+            // an empty contour does not affect the result
+            IntRect::new(0, 0, 0, 0)
+        };
+
+        // all clusters overlapped by the contour merge into one
+        let overlapped: Vec<usize> = self
+            .clusters
+            .iter()
+            .filter(|(_, cluster)| is_overlap(&cluster.rect, &rect))
+            .map(|(&key, _)| key)
+            .collect();
+
+        let mut ids = Vec::new();
+        let mut cluster_rect = rect.clone();
+        for key in overlapped.iter() {
+            let cluster = self.clusters.remove(key).unwrap();
+            cluster_rect = IntRect::with_rects(&cluster_rect, &cluster.rect);
+            ids.extend(cluster.ids);
+        }
+        ids.push(id);
+
+        let key = self.next_cluster_id;
+        self.next_cluster_id += 1;
+        for other in ids.iter() {
+            if let Some(item) = self.contours.get_mut(other) {
+                item.cluster = key;
+            }
+        }
+        self.contours.insert(id, SessionContour { contour, shape_type, rect, cluster: key });
+        self.clusters.insert(key, Cluster { ids, rect: cluster_rect, result: None });
+    }
+
+    // removes the contour from its cluster, the rest of the cluster may fall apart
+    fn detach(&mut self, id: usize, key: usize) {
+        let mut cluster = if let Some(cluster) = self.clusters.remove(&key) { cluster } else { return };
+        cluster.ids.retain(|&other| other != id);
+
+        for group in self.split(cluster.ids) {
+            let key = self.next_cluster_id;
+            self.next_cluster_id += 1;
+            let mut rect = self.contours[&group[0]].rect.clone();
+            for other in group.iter() {
+                let item = self.contours.get_mut(other).unwrap();
+                item.cluster = key;
+                rect = IntRect::with_rects(&rect, &item.rect);
+            }
+            self.clusters.insert(key, Cluster { ids: group, rect, result: None });
+        }
+    }
+
+    // groups contours into connected components of overlapped bounding boxes
+    fn split(&self, ids: Vec<usize>) -> Vec<Vec<usize>> {
+        let rects: Vec<IntRect> = ids.iter().map(|id| self.contours[id].rect.clone()).collect();
+        overlap_groups(&rects)
+            .into_iter()
+            .map(|group| group.into_iter().map(|i| ids[i]).collect())
+            .collect()
+    }
+}
@@ -203,7 +203,7 @@ impl Overlay {
             }
         } else {
             self.add_flat_buffer(flat_buffer, ShapeType::Subject);
-            self.split_solver.split_segments(&mut self.segments, &self.solver);
+            self.split();
             if self.segments.is_empty() {
                 flat_buffer.clear_and_reserve(0, 0);
                 return;
@@ -233,9 +233,7 @@ impl Overlay {
             self.options.preserve_input_collinear,
         );
 
-        let split_modified = self
-            .split_solver
-            .split_segments(&mut self.segments, &self.solver);
+        let split_modified = self.split();
 
         if split_modified || append_modified || self.segments.is_empty() {
             return false;
@@ -0,0 +1,223 @@
+//! This module provides provenance tracking for overlay results.
+//! Every segment of a contour added with a user id keeps a reference to its source through the split:
+//! split parts copy it and merged segments combine the sources of all their parts.
+//! So every edge of an extracted shape or `VectorEdge` can be traced back to the input contour(s) it came from.
+
+use alloc::vec::Vec;
+use crate::core::overlay::ShapeType;
+use crate::core::solver::Solver;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::Segment;
+use crate::segm::track::{MergeTree, TrackCount, TrackRef};
+use crate::split::solver::SplitSolver;
+use crate::vector::edge::VectorShape;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntShapes;
+
+/// Identifies an input contour that produced an output edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeSource {
+    /// User id passed to `Overlay::add_contour_with_id` or `Overlay::add_shape_with_id`.
+    pub id: usize,
+    /// Role of the input contour in the overlay operation.
+    pub shape_type: ShapeType,
+}
+
+/// Sources of every edge of a contour, where the edge `i` goes from point `i` to point `i + 1` (the last one closes the contour).
+pub type ContourSources = Vec<Vec<EdgeSource>>;
+
+/// Sources of every contour of a shape, in the same order as the contours.
+pub type ShapeSources = Vec<ContourSources>;
+
+/// Sources of every shape, in the same order as the shapes.
+pub type ShapesSources = Vec<ShapeSources>;
+
+/// The sources of the `Overlay` segments, `tracks[i]` belongs to the segment `i`.
+/// The segments added without id are not tracked, so `tracks` is padded lazily.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceTracks {
+    sources: Vec<EdgeSource>,
+    tracks: Vec<TrackRef>,
+    tree: MergeTree,
+}
+
+impl SourceTracks {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.sources.clear();
+        self.tracks.clear();
+        self.tree.clear();
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Tracks the segments of one contour, `segments[start..]` must be just added with the `direct` count.
+    pub(crate) fn add(&mut self, segments: &[Segment<ShapeCountBoolean>], start: usize, direct: ShapeCountBoolean, source: EdgeSource) {
+        self.tracks.resize(start, TrackRef::NONE);
+        let leaf = self.tree.leaf(self.sources.len() as u32);
+        self.sources.push(source);
+        for s in segments[start..].iter() {
+            self.tracks.push(if s.count == direct { leaf } else { leaf.invert() });
+        }
+    }
+
+    /// Tracks the segments of one contour edge by edge, `segments[start..]` must be just added with the `direct` count.
+    /// The `edge_id` gets the ends of a segment in the contour direction and returns its id, `None` leaves the segment untracked.
+    pub(crate) fn add_edges<F: FnMut(IntPoint, IntPoint) -> Option<usize>>(
+        &mut self,
+        segments: &[Segment<ShapeCountBoolean>],
+        start: usize,
+        direct: ShapeCountBoolean,
+        shape_type: ShapeType,
+        mut edge_id: F,
+    ) {
+        self.tracks.resize(start, TrackRef::NONE);
+        let mut last: Option<(usize, TrackRef)> = None;
+        for s in segments[start..].iter() {
+            let is_direct = s.count == direct;
+            let (a, b) = if is_direct { (s.x_segment.a, s.x_segment.b) } else { (s.x_segment.b, s.x_segment.a) };
+            let track = match (edge_id(a, b), last) {
+                (None, _) => TrackRef::NONE,
+                (Some(id), Some((last_id, leaf))) if id == last_id => leaf,
+                (Some(id), _) => {
+                    let leaf = self.tree.leaf(self.sources.len() as u32);
+                    self.sources.push(EdgeSource { id, shape_type });
+                    last = Some((id, leaf));
+                    leaf
+                }
+            };
+            self.tracks.push(if is_direct { track } else { track.invert() });
+        }
+    }
+
+    /// Splits the segments and keeps `tracks` in sync with them.
+    pub(crate) fn split(&mut self, split_solver: &mut SplitSolver, segments: &mut Vec<Segment<ShapeCountBoolean>>, solver: &Solver) -> bool {
+        self.tracks.resize(segments.len(), TrackRef::NONE);
+        let mut tracked: Vec<_> = segments
+            .iter()
+            .zip(self.tracks.iter())
+            .map(|(s, &track)| Segment { x_segment: s.x_segment, count: TrackCount { count: s.count, track } })
+            .collect();
+
+        let result = split_solver.split_segments_with(&mut tracked, solver, &mut self.tree);
+
+        segments.clear();
+        self.tracks.clear();
+        for s in tracked.iter() {
+            segments.push(Segment { x_segment: s.x_segment, count: s.count.count });
+            self.tracks.push(s.count.track);
+        }
+
+        result
+    }
+
+    pub(crate) fn contours_sources(&mut self, segments: &[Segment<ShapeCountBoolean>], shapes: &IntShapes) -> ShapesSources {
+        shapes.iter().map(|shape| {
+            shape.iter().map(|contour| {
+                let n = contour.len();
+                (0..n).map(|i| self.sources(segments, contour[i], contour[(i + 1) % n])).collect()
+            }).collect()
+        }).collect()
+    }
+
+    pub(crate) fn vectors_sources(&mut self, segments: &[Segment<ShapeCountBoolean>], shapes: &[VectorShape]) -> ShapesSources {
+        shapes.iter().map(|shape| {
+            shape.iter().map(|path| {
+                path.iter().map(|edge| self.sources(segments, edge.a, edge.b)).collect()
+            }).collect()
+        }).collect()
+    }
+
+    /// Returns all unique sources of the split segments that form the edge `a -> b`.
+    /// An output edge is a chain of collinear split segments, since the extraction only drops collinear points.
+    fn sources(&mut self, segments: &[Segment<ShapeCountBoolean>], a: IntPoint, b: IntPoint) -> Vec<EdgeSource> {
+        let (start, end) = if a < b { (a, b) } else { (b, a) };
+        let dx = end.x as i64 - start.x as i64;
+        let dy = end.y as i64 - start.y as i64;
+
+        let mut result: Vec<EdgeSource> = Vec::new();
+        let mut p = start;
+        while p != end {
+            // the segments are sorted, and `a < b` for every one of them
+            let first = segments.partition_point(|s| s.x_segment.a < p);
+            let next = segments[first..]
+                .iter()
+                .take_while(|s| s.x_segment.a == p)
+                .position(|s| {
+                    let ex = s.x_segment.b.x as i64 - p.x as i64;
+                    let ey = s.x_segment.b.y as i64 - p.y as i64;
+                    ex * dy == ey * dx && s.x_segment.b <= end
+                });
+
+            let index = if let Some(i) = next { first + i } else { break };
+
+            let sources = &self.sources;
+            let track = self.tracks.get(index).copied().unwrap_or(TrackRef::NONE);
+            self.tree.for_each_leaf(track, |value, _| {
+                let source = sources[value as usize];
+                if !result.contains(&source) {
+                    result.push(source);
+                }
+            });
+
+            p = segments[index].x_segment.b;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use crate::core::overlay::ShapeType;
+    use crate::core::solver::Solver;
+    use crate::core::source::{EdgeSource, SourceTracks};
+    use crate::segm::boolean::ShapeCountBoolean;
+    use crate::segm::build::BuildSegments;
+    use crate::segm::segment::Segment;
+    use crate::segm::winding::WindingCount;
+    use crate::split::solver::SplitSolver;
+
+    fn add(tracks: &mut SourceTracks, segments: &mut Vec<Segment<ShapeCountBoolean>>, contour: &[IntPoint], source: EdgeSource) {
+        let start = segments.len();
+        segments.append_path_iter(contour.iter().copied(), source.shape_type, false);
+        let (direct, _) = ShapeCountBoolean::with_shape_type(source.shape_type);
+        tracks.add(segments, start, direct, source);
+    }
+
+    #[test]
+    fn test_sub_edge() {
+        let mut tracks = SourceTracks::default();
+        let mut segments = Vec::new();
+        let subj = EdgeSource { id: 7, shape_type: ShapeType::Subject };
+        let clip = EdgeSource { id: 8, shape_type: ShapeType::Clip };
+        add(&mut tracks, &mut segments, &[IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10), IntPoint::new(0, 10)], subj);
+        add(&mut tracks, &mut segments, &[IntPoint::new(5, -5), IntPoint::new(15, -5), IntPoint::new(15, 5), IntPoint::new(5, 5)], clip);
+        tracks.split(&mut SplitSolver::new(), &mut segments, &Solver::default());
+
+        assert_eq!(tracks.sources(&segments, IntPoint::new(10, 5), IntPoint::new(10, 10)), vec![subj]);
+        assert_eq!(tracks.sources(&segments, IntPoint::new(10, 0), IntPoint::new(0, 0)), vec![subj]);
+        assert_eq!(tracks.sources(&segments, IntPoint::new(5, 5), IntPoint::new(15, 5)), vec![clip]);
+        assert!(tracks.sources(&segments, IntPoint::new(0, 0), IntPoint::new(10, 10)).is_empty());
+    }
+
+    #[test]
+    fn test_overlapped_edge() {
+        let mut tracks = SourceTracks::default();
+        let mut segments = Vec::new();
+        let subj = EdgeSource { id: 1, shape_type: ShapeType::Subject };
+        let clip = EdgeSource { id: 2, shape_type: ShapeType::Clip };
+        add(&mut tracks, &mut segments, &[IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(10, 10)], subj);
+        add(&mut tracks, &mut segments, &[IntPoint::new(5, 0), IntPoint::new(20, 0), IntPoint::new(20, -10)], clip);
+        tracks.split(&mut SplitSolver::new(), &mut segments, &Solver::default());
+
+        assert_eq!(tracks.sources(&segments, IntPoint::new(0, 0), IntPoint::new(20, 0)), vec![subj, clip]);
+        assert_eq!(tracks.sources(&segments, IntPoint::new(0, 0), IntPoint::new(5, 0)), vec![subj]);
+    }
+}
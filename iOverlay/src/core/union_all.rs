@@ -0,0 +1,176 @@
+//! This module provides a cascaded union of many shapes.
+//! Shapes are ordered along a Z-curve of a power of two grid, so neighbors fall into the same groups.
+//! Every group is united by its own overlay, and the results are united level by level.
+
+use alloc::vec::Vec;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{ContourDirection, IntOverlayOptions, Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use i_float::int::point::IntPoint;
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::shape::{IntShape, IntShapes};
+
+/// The number of shapes united by one overlay at the first level.
+const LEAF_SIZE: usize = 32;
+
+/// The number of results united by one overlay at the next levels.
+const MERGE_SIZE: usize = 4;
+
+/// Trait `UnionAll` provides the union of many shapes.
+pub trait UnionAll {
+    /// Unites all shapes into non-overlapping shapes.
+    ///
+    /// Every shape must be valid: the first contour is the outer boundary and the others are holes.
+    /// The direction of contours does not matter.
+    /// Spatially close shapes are united by small overlays first, and the results are merged in a cascade,
+    /// which is much faster and lighter than one overlay for a large number of shapes.
+    /// Groups of the same level are united in parallel under the `allow_multithreading` feature
+    /// if the solver allows multithreading.
+    ///
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn union_all(&self, options: IntOverlayOptions, solver: Solver) -> IntShapes;
+}
+
+impl UnionAll for [IntShape] {
+    fn union_all(&self, options: IntOverlayOptions, solver: Solver) -> IntShapes {
+        let mut shapes: Vec<&IntShape> = self
+            .iter()
+            .filter(|shape| shape.first().is_some_and(|outer| outer.len() >= 3))
+            .collect();
+
+        if shapes.len() <= LEAF_SIZE {
+            return unite(&shapes, options, solver);
+        }
+
+        sort_by_z_order(&mut shapes);
+
+        // the intermediate results must keep small parts and have the default direction
+        let level_options = IntOverlayOptions {
+            min_output_area: 0,
+            output_direction: ContourDirection::CounterClockwise,
+            ..options
+        };
+
+        let groups: Vec<Vec<&IntShape>> = shapes.chunks(LEAF_SIZE).map(|chunk| chunk.to_vec()).collect();
+        let mut results = unite_chunks(&groups, level_options, solver);
+        while results.len() > MERGE_SIZE {
+            let groups: Vec<Vec<&IntShape>> = results
+                .chunks(MERGE_SIZE)
+                .map(|chunk| chunk.iter().flatten().collect())
+                .collect();
+            results = unite_chunks(&groups, level_options, solver);
+        }
+
+        let last: Vec<&IntShape> = results.iter().flatten().collect();
+        unite(&last, options, solver)
+    }
+}
+
+fn unite_chunks(groups: &[Vec<&IntShape>], options: IntOverlayOptions, solver: Solver) -> Vec<IntShapes> {
+    #[cfg(feature = "allow_multithreading")]
+    {
+        if solver.multithreading.is_some() {
+            use rayon::iter::IntoParallelRefIterator;
+            use rayon::iter::ParallelIterator;
+
+            return groups.par_iter().map(|group| unite(group, options, solver)).collect();
+        }
+    }
+
+    groups.iter().map(|group| unite(group, options, solver)).collect()
+}
+
+fn unite(shapes: &[&IntShape], options: IntOverlayOptions, solver: Solver) -> IntShapes {
+    let capacity = shapes.iter().map(|shape| shape.points_count()).sum();
+    let mut overlay = Overlay::new_custom(capacity, options, solver);
+    let mut buffer = Vec::new();
+    for shape in shapes.iter() {
+        for (i, contour) in shape.iter().enumerate() {
+            if contour.len() < 3 {
+                continue;
+            }
+            // the outer contour must be counterclockwise and holes clockwise
+            let is_clockwise = contour.unsafe_area() > 0;
+            if is_clockwise == (i == 0) {
+                buffer.clear();
+                buffer.extend(contour.iter().rev());
+                overlay.add_contour(&buffer, ShapeType::Subject);
+            } else {
+                overlay.add_contour(contour, ShapeType::Subject);
+            }
+        }
+    }
+
+    overlay.overlay(OverlayRule::Subject, FillRule::Positive)
+}
+
+fn sort_by_z_order(shapes: &mut [&IntShape]) {
+    let centers: Vec<IntPoint> = shapes.iter().map(|shape| center(&shape[0])).collect();
+    let min_x = centers.iter().map(|p| p.x).min().unwrap_or(0);
+    let min_y = centers.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_x = centers.iter().map(|p| p.x).max().unwrap_or(0);
+    let max_y = centers.iter().map(|p| p.y).max().unwrap_or(0);
+
+    // a cell holds a few shapes on average
+    let size = (max_x as i64 - min_x as i64).max(max_y as i64 - min_y as i64).max(1) as u64;
+    let count = (shapes.len() / 4).max(1);
+    let max_power = count.ilog2() >> 1;
+    let log = size.ilog2();
+    let power = log.saturating_sub(max_power);
+
+    let mut keys: Vec<(u64, usize)> = centers
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let x = ((p.x as i64 - min_x as i64) as u64 >> power) as u32;
+            let y = ((p.y as i64 - min_y as i64) as u64 >> power) as u32;
+            (interleave(x) | (interleave(y) << 1), i)
+        })
+        .collect();
+    keys.sort_unstable();
+
+    let sorted: Vec<&IntShape> = keys.iter().map(|&(_, i)| shapes[i]).collect();
+    shapes.copy_from_slice(&sorted);
+}
+
+#[inline]
+fn center(contour: &[IntPoint]) -> IntPoint {
+    let mut min = contour[0];
+    let mut max = contour[0];
+    for p in contour.iter() {
+        min.x = min.x.min(p.x);
+        min.y = min.y.min(p.y);
+        max.x = max.x.max(p.x);
+        max.y = max.y.max(p.y);
+    }
+    IntPoint::new(((min.x as i64 + max.x as i64) >> 1) as i32, ((min.y as i64 + max.y as i64) >> 1) as i32)
+}
+
+// spreads the bits of the value to the even positions
+#[inline]
+fn interleave(value: u32) -> u64 {
+    let mut x = value as u64;
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    x = (x | (x << 1)) & 0x5555_5555_5555_5555;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleave() {
+        assert_eq!(interleave(0b11), 0b101);
+        assert_eq!(interleave(0b101) | (interleave(0b11) << 1), 0b1_1011);
+        assert_eq!(interleave(u32::MAX), 0x5555_5555_5555_5555);
+    }
+}
@@ -0,0 +1,275 @@
+//! This module provides a validation of shapes.
+//! Unlike overlay and simplification, which silently repair the input, the validator only reports problems
+//! with their locations, so invalid data can be rejected or flagged.
+
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::shape::IntShape;
+use crate::geom::predicate::{is_inside_or_on, is_within, orient};
+use crate::core::overlay::ContourDirection;
+
+/// The location of a vertex. An edge is referenced by its start vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexIndex {
+    pub shape: usize,
+    pub contour: usize,
+    pub vertex: usize,
+}
+
+/// A problem found by the validation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue<P> {
+    /// The contour has less than 3 distinct points.
+    Degenerate { shape: usize, contour: usize },
+    /// The vertex is equal to the next vertex of the contour.
+    DuplicatePoint { vertex: VertexIndex, point: P },
+    /// The contour turns back at the vertex, the same pattern as removed by `i_shape::int::despike`.
+    Spike { vertex: VertexIndex, point: P },
+    /// Two edges cross or touch each other. The crossing point is rounded to the grid.
+    SelfIntersection { a: VertexIndex, b: VertexIndex, point: P },
+    /// Two collinear edges have a common part of non-zero length.
+    OverlappingEdges { a: VertexIndex, b: VertexIndex },
+    /// The outer contour does not follow the main direction or the hole does not follow the opposite one.
+    WrongOrientation { shape: usize, contour: usize },
+    /// The hole has a point outside its outer contour.
+    HoleOutside { shape: usize, contour: usize, point: P },
+}
+
+/// Trait `Validate` checks shapes without changing them.
+pub trait Validate {
+    /// Returns all problems of the shapes.
+    ///
+    /// The shapes are valid if the result is empty: every contour has at least 3 distinct points,
+    /// no duplicated points and spikes, edges of all shapes do not cross, touch or overlap each other,
+    /// outer contours follow the main direction, and holes follow the opposite direction and lie inside their outer contours.
+    /// - `main_direction`: The expected direction of outer contours.
+    ///
+    /// ```rust
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::core::overlay::ContourDirection;
+    /// use i_overlay::core::validate::{Validate, ValidationIssue, VertexIndex};
+    ///
+    /// // a bow-tie
+    /// let shapes = vec![vec![vec![
+    ///     IntPoint::new(0, 0),
+    ///     IntPoint::new(10, 10),
+    ///     IntPoint::new(10, 0),
+    ///     IntPoint::new(0, 10),
+    /// ]]];
+    ///
+    /// let issues = shapes.validate(ContourDirection::CounterClockwise);
+    ///
+    /// assert_eq!(issues, vec![ValidationIssue::SelfIntersection {
+    ///     a: VertexIndex { shape: 0, contour: 0, vertex: 0 },
+    ///     b: VertexIndex { shape: 0, contour: 0, vertex: 2 },
+    ///     point: IntPoint::new(5, 5),
+    /// }]);
+    /// ```
+    fn validate(&self, main_direction: ContourDirection) -> Vec<ValidationIssue<IntPoint>>;
+}
+
+struct Edge {
+    a: IntPoint,
+    b: IntPoint,
+    index: VertexIndex,
+    // the contour number and the position in it, to skip neighbor edges
+    contour: usize,
+    position: usize,
+    count: usize,
+}
+
+impl Edge {
+    #[inline]
+    fn is_neighbor(&self, other: &Edge) -> bool {
+        self.contour == other.contour
+            && ((self.position + 1) % self.count == other.position || (other.position + 1) % other.count == self.position)
+    }
+}
+
+impl Validate for [IntShape] {
+    fn validate(&self, main_direction: ContourDirection) -> Vec<ValidationIssue<IntPoint>> {
+        let mut issues = Vec::new();
+        let mut edges = Vec::new();
+        let mut contour_number = 0;
+
+        for (s, shape) in self.iter().enumerate() {
+            for (c, contour) in shape.iter().enumerate() {
+                let n = contour.len();
+
+                // vertices which differ from the next one
+                let mut ids = Vec::with_capacity(n);
+                for (i, &p) in contour.iter().enumerate() {
+                    if p == contour[(i + 1) % n] {
+                        let vertex = VertexIndex { shape: s, contour: c, vertex: i };
+                        issues.push(ValidationIssue::DuplicatePoint { vertex, point: p });
+                    } else {
+                        ids.push(i);
+                    }
+                }
+
+                let count = ids.len();
+                if count < 3 {
+                    issues.push(ValidationIssue::Degenerate { shape: s, contour: c });
+                    continue;
+                }
+
+                for k in 0..count {
+                    let p0 = contour[ids[(k + count - 1) % count]];
+                    let p1 = contour[ids[k]];
+                    let p2 = contour[ids[(k + 1) % count]];
+                    let v10 = p1.subtract(p0);
+                    let v21 = p2.subtract(p1);
+                    if v10.cross_product(v21) == 0 && v10.dot_product(v21) < 0 {
+                        let vertex = VertexIndex { shape: s, contour: c, vertex: ids[k] };
+                        issues.push(ValidationIssue::Spike { vertex, point: p1 });
+                    }
+
+                    edges.push(Edge {
+                        a: p1,
+                        b: p2,
+                        index: VertexIndex { shape: s, contour: c, vertex: ids[k] },
+                        contour: contour_number,
+                        position: k,
+                        count,
+                    });
+                }
+                contour_number += 1;
+
+                let area = contour.unsafe_area();
+                if area != 0 {
+                    let is_clockwise = area > 0;
+                    let expect_clockwise = (main_direction == ContourDirection::Clockwise) == (c == 0);
+                    if is_clockwise != expect_clockwise {
+                        issues.push(ValidationIssue::WrongOrientation { shape: s, contour: c });
+                    }
+                }
+
+                let outer = &shape[0];
+                if c > 0 && outer.len() >= 3 {
+                    let outside = contour.iter().find(|&&p| !is_inside_or_on(p, outer));
+                    if let Some(&point) = outside {
+                        issues.push(ValidationIssue::HoleOutside { shape: s, contour: c, point });
+                    }
+                }
+            }
+        }
+
+        find_intersections(&mut edges, &mut issues);
+
+        issues
+    }
+}
+
+fn find_intersections(edges: &mut [Edge], issues: &mut Vec<ValidationIssue<IntPoint>>) {
+    edges.sort_by_key(|e| e.a.x.min(e.b.x));
+
+    let mut active: Vec<usize> = Vec::new();
+    for i in 0..edges.len() {
+        let e = &edges[i];
+        let min_x = e.a.x.min(e.b.x);
+        active.retain(|&j| edges[j].a.x.max(edges[j].b.x) >= min_x);
+        for &j in active.iter() {
+            let f = &edges[j];
+            let is_y_overlap = e.a.y.min(e.b.y) <= f.a.y.max(f.b.y) && f.a.y.min(f.b.y) <= e.a.y.max(e.b.y);
+            if !is_y_overlap || e.is_neighbor(f) {
+                continue;
+            }
+            if let Some(issue) = intersect(f, e) {
+                issues.push(issue);
+            }
+        }
+        active.push(i);
+    }
+}
+
+fn intersect(e: &Edge, f: &Edge) -> Option<ValidationIssue<IntPoint>> {
+    let d1 = orient(e.a, e.b, f.a);
+    let d2 = orient(e.a, e.b, f.b);
+    let d3 = orient(f.a, f.b, e.a);
+    let d4 = orient(f.a, f.b, e.b);
+
+    let touch = if d1 == 0 && d2 == 0 {
+        let (s0, s1, t0, t1) = if e.a.x != e.b.x {
+            (e.a.x.min(e.b.x), e.a.x.max(e.b.x), f.a.x.min(f.b.x), f.a.x.max(f.b.x))
+        } else {
+            (e.a.y.min(e.b.y), e.a.y.max(e.b.y), f.a.y.min(f.b.y), f.a.y.max(f.b.y))
+        };
+        let overlap = s1.min(t1) as i64 - s0.max(t0) as i64;
+        if overlap > 0 {
+            return Some(ValidationIssue::OverlappingEdges { a: e.index, b: f.index });
+        } else if overlap < 0 {
+            return None;
+        }
+        if is_within(e.a, e.b, f.a) { f.a } else { f.b }
+    } else if d1 * d2 <= 0 && d3 * d4 <= 0 {
+        if d1 == 0 {
+            f.a
+        } else if d2 == 0 {
+            f.b
+        } else if d3 == 0 {
+            e.a
+        } else if d4 == 0 {
+            e.b
+        } else {
+            // a proper crossing is reported even if the rounded point is an edge end
+            let point = cross_point(e.a, e.b, f.a, f.b);
+            return Some(ValidationIssue::SelfIntersection { a: e.index, b: f.index, point });
+        }
+    } else {
+        return None;
+    };
+
+    // a touch at the end of an edge is also a touch at the start of the next edge, report it once
+    if touch == e.b || touch == f.b {
+        return None;
+    }
+
+    Some(ValidationIssue::SelfIntersection { a: e.index, b: f.index, point: touch })
+}
+
+fn cross_point(a0: IntPoint, a1: IntPoint, b0: IntPoint, b1: IntPoint) -> IntPoint {
+    let ax = a1.x as i128 - a0.x as i128;
+    let ay = a1.y as i128 - a0.y as i128;
+    let bx = b1.x as i128 - b0.x as i128;
+    let by = b1.y as i128 - b0.y as i128;
+    let dx = b0.x as i128 - a0.x as i128;
+    let dy = b0.y as i128 - a0.y as i128;
+
+    let den = ax * by - ay * bx;
+    let num = dx * by - dy * bx;
+
+    let x = a0.x as i128 + div_round(ax * num, den);
+    let y = a0.y as i128 + div_round(ay * num, den);
+
+    IntPoint::new(x as i32, y as i32)
+}
+
+#[inline]
+fn div_round(num: i128, den: i128) -> i128 {
+    let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+    if num >= 0 {
+        (num + den / 2) / den
+    } else {
+        -((-num + den / 2) / den)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_round() {
+        assert_eq!(div_round(7, 2), 4);
+        assert_eq!(div_round(-7, 2), -4);
+        assert_eq!(div_round(7, -3), -2);
+        assert_eq!(div_round(5, 3), 2);
+    }
+
+    #[test]
+    fn test_cross_point() {
+        let p = cross_point(IntPoint::new(0, 0), IntPoint::new(10, 5), IntPoint::new(0, 5), IntPoint::new(10, 0));
+        assert_eq!(p, IntPoint::new(5, 3));
+    }
+}
@@ -0,0 +1,604 @@
+//! This module provides an approximate recovery of circular arcs in the result of boolean operations on curve paths.
+//! It is not an arc overlay: arcs are flattened into chords and the overlay itself works on lines.
+//! Every chord keeps the id of its source arc through the split, so result edges are classified by provenance,
+//! and runs of edges from arcs of the same circle are merged back into arcs of that circle.
+//! An edge merged from several sources is recovered only if all of them are arcs of the same circle.
+//! The ends of merged arcs are moved to the exact intersections of the source circles and lines,
+//! while the points in between are the flattened ones, so they deviate from the true result by up to the tolerance.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::core::source::{ContourSources, EdgeSource};
+use crate::float::curve::{CurvePath, CurveSegment, EllipticalArc, angle, flatten_segment, is_same, length};
+use crate::float::overlay::OverlayOptions;
+
+const NONE: usize = usize::MAX;
+
+// the source id of line edges, so an edge of an arc chord and a line is not taken for an arc
+const LINE: usize = usize::MAX - 1;
+
+/// A shape of curve contours: the first contour is the outer boundary and the others are holes.
+pub type CurveShape<P, T> = Vec<CurvePath<P, T>>;
+
+/// A collection of curve shapes.
+pub type CurveShapes<P, T> = Vec<CurveShape<P, T>>;
+
+struct SourceArc<P, T> {
+    // the first arc of the same circle
+    circle: usize,
+    center: P,
+    radius: T,
+    a: P,
+    b: P,
+}
+
+/// This struct performs boolean operations on curve paths with an approximate recovery of circular arcs.
+/// Bézier curves and elliptical arcs are flattened and come out as lines.
+///
+/// The operation runs on the flattened contours. A result edge is recovered as a part of a circular arc
+/// only if it comes from a chord of a source arc, so lines tangent or close to a circle stay lines.
+/// The arc ends are exact, but the arcs themselves follow the flattened result, so the intersections
+/// between two chords of the same arc are resolved up to the tolerance.
+///
+/// ```rust
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::arc_recovery::ArcRecovery;
+/// use i_overlay::float::curve::{CurvePath, CurveSegment};
+///
+/// let circle = CurvePath::new([1.0, 0.0])
+///     .arc_to([1.0, 1.0], 0.0, false, true, [-1.0, 0.0])
+///     .arc_to([1.0, 1.0], 0.0, false, true, [1.0, 0.0]);
+/// let square = CurvePath::new([0.0, 0.0])
+///     .line_to([2.0, 0.0])
+///     .line_to([2.0, 2.0])
+///     .line_to([0.0, 2.0]);
+///
+/// let shapes = ArcRecovery::with_subj_and_clip(&[circle], &[square], 0.001)
+///     .overlay(OverlayRule::Intersect, FillRule::EvenOdd);
+///
+/// // a quarter of the circle: two lines and one arc
+/// let contour = &shapes[0][0];
+/// assert_eq!(contour.segments.len(), 3);
+/// let arcs = contour.segments.iter().filter(|s| matches!(s, CurveSegment::Arc { .. })).count();
+/// assert_eq!(arcs, 1);
+/// ```
+pub struct ArcRecovery<P: FloatPointCompatible<T>, T: FloatNumber> {
+    overlay: Overlay,
+    adapter: FloatPointAdapter<P, T>,
+    arcs: Vec<SourceArc<P, T>>,
+    // the arcs of every circle, indexed by the circle
+    circle_arcs: Vec<Vec<usize>>,
+    preserve_output_collinear: bool,
+    epsilon: T,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> ArcRecovery<P, T> {
+    /// Creates a new `ArcRecovery` instance and initializes it with subject and clip curve paths.
+    ///
+    /// - `subj`: The subject closed curve paths.
+    /// - `clip`: The clip closed curve paths.
+    /// - `tolerance`: The maximum distance between a curve and its chords used by the overlay.
+    #[inline]
+    pub fn with_subj_and_clip(subj: &[CurvePath<P, T>], clip: &[CurvePath<P, T>], tolerance: T) -> Self {
+        Self::with_subj_and_clip_custom(subj, clip, tolerance, Default::default(), Default::default())
+    }
+
+    /// Creates a new `ArcRecovery` instance and initializes it with subject and clip curve paths.
+    ///
+    /// - `subj`: The subject closed curve paths.
+    /// - `clip`: The clip closed curve paths.
+    /// - `tolerance`: The maximum distance between a curve and its chords used by the overlay.
+    /// - `options`: Adjust custom behavior. `preserve_output_collinear` applies to the line runs only,
+    ///   the points inside a recovered arc are always dropped.
+    /// - `solver`: Type of solver to use.
+    pub fn with_subj_and_clip_custom(
+        subj: &[CurvePath<P, T>],
+        clip: &[CurvePath<P, T>],
+        tolerance: T,
+        options: OverlayOptions<T>,
+        solver: Solver,
+    ) -> Self {
+        let mut arcs = Vec::new();
+        let subj_paths: Vec<_> = subj.iter().map(|path| flatten(path, tolerance, &mut arcs)).collect();
+        let clip_paths: Vec<_> = clip.iter().map(|path| flatten(path, tolerance, &mut arcs)).collect();
+
+        let mut circle_arcs = vec![Vec::new(); arcs.len()];
+        for (i, arc) in arcs.iter().enumerate() {
+            circle_arcs[arc.circle].push(i);
+        }
+
+        let adapter = FloatPointAdapter::with_iter(
+            subj_paths.iter().chain(clip_paths.iter()).flat_map(|(points, _)| points.iter()),
+        );
+        let epsilon = tolerance.abs() + T::from_float(2.0) * adapter.inv_scale;
+
+        let capacity = subj_paths.iter().chain(clip_paths.iter()).map(|(points, _)| points.len()).sum();
+        let mut int_options = options.int_with_adapter(&adapter);
+        // every split point is kept, so a result edge never spans a line and an arc,
+        // the collinear points of line runs are dropped later
+        int_options.preserve_output_collinear = true;
+        let mut overlay = Overlay::new_custom(capacity, int_options, solver);
+
+        // the arc chords of a contour by their start points
+        let mut chords: BTreeMap<IntPoint, Vec<(IntPoint, usize)>> = BTreeMap::new();
+        let mut int_path = Vec::new();
+        for (paths, shape_type) in [(subj_paths, ShapeType::Subject), (clip_paths, ShapeType::Clip)] {
+            for (points, edge_arcs) in paths.iter() {
+                int_path.clear();
+                int_path.extend(points.iter().map(|p| adapter.float_to_int(p)));
+
+                chords.clear();
+                let n = int_path.len();
+                for (i, &arc) in edge_arcs.iter().enumerate() {
+                    let (a, b) = (int_path[i], int_path[(i + 1) % n]);
+                    if arc != NONE && a != b {
+                        chords.entry(a).or_default().push((b, arc));
+                    }
+                }
+
+                // the collinear chords are merged into one segment, which starts at the first of them
+                overlay.add_contour_with_edge_ids(&int_path, shape_type, |a, b| {
+                    let chord = chords.get(&a).and_then(|list| list.iter().find(|&&(c, _)| is_same_direction(a, b, c)));
+                    Some(chord.map_or(LINE, |&(_, arc)| arc))
+                });
+            }
+        }
+
+        Self {
+            overlay,
+            adapter,
+            arcs,
+            circle_arcs,
+            preserve_output_collinear: options.preserve_output_collinear,
+            epsilon,
+        }
+    }
+
+    /// Executes a single Boolean operation on the current geometry using the specified overlay and fill rules.
+    ///
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    ///
+    /// # Returns
+    /// A collection of curve shapes, where the result edges which come from source circular arcs are merged into arcs.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> CurveShapes<P, T> {
+        let (shapes, sources) = self.overlay.overlay_with_sources(overlay_rule, fill_rule);
+        shapes
+            .iter()
+            .zip(sources.iter())
+            .map(|(shape, shape_sources)| {
+                shape
+                    .iter()
+                    .zip(shape_sources.iter())
+                    .map(|(contour, contour_sources)| self.recover(contour, contour_sources))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn recover(&self, contour: &[IntPoint], sources: &ContourSources) -> CurvePath<P, T> {
+        let n = contour.len();
+        let points: Vec<P> = contour.iter().map(|p| self.adapter.int_to_float(p)).collect();
+
+        // the circle of an edge from the point i to the point i + 1
+        let edge_arcs: Vec<usize> = sources.iter().map(|edge| self.edge_circle(edge)).collect();
+
+        // a point between two collinear lines
+        let is_skipped = |i: usize| -> bool {
+            let i = i % n;
+            let prev = (i + n - 1) % n;
+            !self.preserve_output_collinear
+                && edge_arcs[prev] == NONE
+                && edge_arcs[i] == NONE
+                && is_same_direction(contour[prev], contour[i], contour[(i + 1) % n])
+        };
+
+        // start at the first change of the edge type
+        let start = (0..n).find(|&i| edge_arcs[(i + n - 1) % n] != edge_arcs[i]);
+        let start = if let Some(start) = start {
+            start
+        } else if edge_arcs[0] == NONE {
+            let mut kept = (0..n).filter(|&i| !is_skipped(i)).map(|i| points[i]);
+            let start = kept.next().unwrap_or(points[0]);
+            let segments = kept.map(CurveSegment::Line).collect();
+            return CurvePath { start, segments };
+        } else {
+            // the whole contour is a circle, it is split in two arcs
+            let arc = &self.arcs[edge_arcs[0]];
+            let m = n / 2;
+            let a = self.sweep(arc, &points, 0, m);
+            let b = self.sweep(arc, &points, m, n);
+            let segments = [(m, a), (0, b)]
+                .into_iter()
+                .map(|(i, delta)| circular_arc(arc.radius, delta, points[i]))
+                .collect();
+            return CurvePath { start: points[0], segments };
+        };
+
+        let vertex = |i: usize| -> P {
+            let i = i % n;
+            let arc_in = edge_arcs[(i + n - 1) % n];
+            let arc_out = edge_arcs[i];
+            self.vertex(points[i], arc_in, arc_out, points[(i + n - 1) % n], points[(i + 1) % n])
+        };
+
+        let first = vertex(start);
+        let mut segments = Vec::new();
+        let mut i = start;
+        while i < start + n {
+            let arc = edge_arcs[i % n];
+            if arc == NONE {
+                i += 1;
+                if i == start + n {
+                    segments.push(CurveSegment::Line(first));
+                } else if !is_skipped(i) {
+                    segments.push(CurveSegment::Line(vertex(i)));
+                }
+            } else {
+                let mut j = i + 1;
+                while j < start + n && edge_arcs[j % n] == arc {
+                    j += 1;
+                }
+                let delta = self.sweep(&self.arcs[arc], &points, i, j);
+                let end = if j == start + n { first } else { vertex(j) };
+                segments.push(circular_arc(self.arcs[arc].radius, delta, end));
+                i = j;
+            }
+        }
+
+        CurvePath { start: first, segments }
+    }
+
+    // the common circle of all sources of an edge, `NONE` if one of them is a line or they come from different circles
+    fn edge_circle(&self, sources: &[EdgeSource]) -> usize {
+        let mut circles = sources.iter().map(|source| if source.id == LINE { NONE } else { self.arcs[source.id].circle });
+        let Some(first) = circles.next() else {
+            return NONE;
+        };
+        if circles.all(|circle| circle == first) { first } else { NONE }
+    }
+
+    // the signed angle of the arc run from the point i to the point j
+    fn sweep(&self, arc: &SourceArc<P, T>, points: &[P], i: usize, j: usize) -> T {
+        let n = points.len();
+        let c = arc.center;
+        let mut delta = T::from_float(0.0);
+        for k in i..j {
+            let a = points[k % n];
+            let b = points[(k + 1) % n];
+            delta = delta + angle(a.x() - c.x(), a.y() - c.y(), b.x() - c.x(), b.y() - c.y());
+        }
+        delta
+    }
+
+    // an exact position of a point where the edge type changes
+    fn vertex(&self, p: P, arc_in: usize, arc_out: usize, prev: P, next: P) -> P {
+        if arc_in == arc_out {
+            return p;
+        }
+
+        // the source ends of arcs are exact
+        for &circle in [arc_in, arc_out].iter().filter(|&&circle| circle != NONE) {
+            for arc in self.circle_arcs[circle].iter().map(|&i| &self.arcs[i]) {
+                for end in [arc.a, arc.b] {
+                    if distance(p, end) <= self.epsilon {
+                        return end;
+                    }
+                }
+            }
+        }
+
+        let exact = match (arc_in, arc_out) {
+            (NONE, arc) => self.arcs[arc].line_cross(prev, p, p),
+            (arc, NONE) => self.arcs[arc].line_cross(p, next, p),
+            (a, b) => self.arcs[a].circle_cross(&self.arcs[b], p),
+        };
+
+        match exact {
+            Some(q) if distance(p, q) <= T::from_float(2.0) * self.epsilon => q,
+            _ => p,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> SourceArc<P, T> {
+    // the intersection of the circle and the line `a`-`b`, which is the nearest to `p`
+    fn line_cross(&self, a: P, b: P, p: P) -> Option<P> {
+        let dx = b.x() - a.x();
+        let dy = b.y() - a.y();
+        let dd = dx * dx + dy * dy;
+        if dd == T::from_float(0.0) {
+            return None;
+        }
+
+        // the projection of the center to the line
+        let t = ((self.center.x() - a.x()) * dx + (self.center.y() - a.y()) * dy) / dd;
+        let hx = a.x() + t * dx;
+        let hy = a.y() + t * dy;
+        let h2 = (self.center.x() - hx) * (self.center.x() - hx) + (self.center.y() - hy) * (self.center.y() - hy);
+        let s2 = self.radius * self.radius - h2;
+        if s2 < T::from_float(0.0) {
+            return None;
+        }
+
+        let s = (s2 / dd).sqrt();
+        let q0 = P::from_xy(hx + s * dx, hy + s * dy);
+        let q1 = P::from_xy(hx - s * dx, hy - s * dy);
+
+        Some(if distance(q0, p) < distance(q1, p) { q0 } else { q1 })
+    }
+
+    // the intersection of two circles, which is the nearest to `p`
+    fn circle_cross(&self, other: &Self, p: P) -> Option<P> {
+        let dx = other.center.x() - self.center.x();
+        let dy = other.center.y() - self.center.y();
+        let d = length(dx, dy);
+        if d == T::from_float(0.0) {
+            return None;
+        }
+
+        let r0 = self.radius;
+        let r1 = other.radius;
+        let a = (r0 * r0 - r1 * r1 + d * d) / (T::from_float(2.0) * d);
+        let h2 = r0 * r0 - a * a;
+        if h2 < T::from_float(0.0) {
+            return None;
+        }
+
+        let h = h2.sqrt();
+        let mx = self.center.x() + a * dx / d;
+        let my = self.center.y() + a * dy / d;
+        let q0 = P::from_xy(mx - h * dy / d, my + h * dx / d);
+        let q1 = P::from_xy(mx + h * dy / d, my - h * dx / d);
+
+        Some(if distance(q0, p) < distance(q1, p) { q0 } else { q1 })
+    }
+}
+
+#[inline]
+fn distance<P: FloatPointCompatible<T>, T: FloatNumber>(a: P, b: P) -> T {
+    length(a.x() - b.x(), a.y() - b.y())
+}
+
+// the point `c` lies on the ray from `a` through `b`
+#[inline]
+fn is_same_direction(a: IntPoint, b: IntPoint, c: IntPoint) -> bool {
+    let (bx, by) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let (cx, cy) = (c.x as i64 - a.x as i64, c.y as i64 - a.y as i64);
+    bx * cy == by * cx && bx * cx + by * cy > 0
+}
+
+#[inline]
+fn circular_arc<P: FloatPointCompatible<T>, T: FloatNumber>(radius: T, delta: T, to: P) -> CurveSegment<P, T> {
+    CurveSegment::Arc {
+        radii: P::from_xy(radius, radius),
+        rotation: T::from_float(0.0),
+        large_arc: delta.abs() > T::from_float(PI),
+        sweep: delta > T::from_float(0.0),
+        to,
+    }
+}
+
+fn circular_arc_of<P: FloatPointCompatible<T>, T: FloatNumber>(a: P, segment: &CurveSegment<P, T>) -> Option<EllipticalArc<T>> {
+    if let CurveSegment::Arc {
+        radii,
+        rotation,
+        large_arc,
+        sweep,
+        to,
+    } = segment
+    {
+        let arc = EllipticalArc::new(a, *radii, *rotation, *large_arc, *sweep, *to)?;
+        if (arc.rx - arc.ry).abs() <= T::from_float(1e-9) * arc.rx {
+            return Some(arc);
+        }
+    }
+    None
+}
+
+// flattens the path and marks the edges of circular arcs
+fn flatten<P: FloatPointCompatible<T>, T: FloatNumber>(
+    path: &CurvePath<P, T>,
+    tolerance: T,
+    arcs: &mut Vec<SourceArc<P, T>>,
+) -> (Vec<P>, Vec<usize>) {
+    let mut points = Vec::with_capacity(path.segments.len() + 1);
+    let mut edge_arcs = Vec::with_capacity(path.segments.len() + 1);
+    points.push(path.start);
+
+    let mut a = path.start;
+    for segment in path.segments.iter() {
+        let b = flatten_segment(a, segment, tolerance, &mut points);
+
+        let arc_index = if let Some(arc) = circular_arc_of(a, segment) {
+            let index = arcs.len();
+            let center = P::from_xy(arc.cx, arc.cy);
+            let epsilon = T::from_float(1e-9) * arc.rx;
+            let circle = arcs
+                .iter()
+                .position(|other| distance(other.center, center) <= epsilon && (other.radius - arc.rx).abs() <= epsilon)
+                .unwrap_or(index);
+            arcs.push(SourceArc {
+                circle,
+                center,
+                radius: arc.rx,
+                a,
+                b,
+            });
+            index
+        } else {
+            NONE
+        };
+
+        edge_arcs.resize(points.len() - 1, arc_index);
+        a = b;
+    }
+
+    if points.len() > 1 && is_same(points[points.len() - 1], path.start) {
+        points.pop();
+    } else {
+        // the closing line
+        edge_arcs.push(NONE);
+    }
+    edge_arcs.truncate(points.len());
+
+    (points, edge_arcs)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::f64::consts::PI;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::arc_recovery::ArcRecovery;
+    use crate::float::curve::{CurvePath, CurveSegment};
+    use crate::float::overlay::OverlayOptions;
+
+    fn circle(cx: f64, cy: f64, r: f64) -> CurvePath<[f64; 2], f64> {
+        CurvePath::new([cx + r, cy])
+            .arc_to([r, r], 0.0, false, true, [cx - r, cy])
+            .arc_to([r, r], 0.0, false, true, [cx + r, cy])
+    }
+
+    fn arcs(path: &CurvePath<[f64; 2], f64>) -> Vec<(f64, bool, [f64; 2])> {
+        path.segments
+            .iter()
+            .filter_map(|s| match s {
+                CurveSegment::Arc { radii, large_arc, to, .. } => Some((radii[0], *large_arc, *to)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_near(a: [f64; 2], b: [f64; 2], epsilon: f64) -> bool {
+        (a[0] - b[0]).abs() <= epsilon && (a[1] - b[1]).abs() <= epsilon
+    }
+
+    #[test]
+    fn test_circle_and_square() {
+        let square = CurvePath::new([0.0, 0.0]).line_to([2.0, 0.0]).line_to([2.0, 2.0]).line_to([0.0, 2.0]);
+
+        let shapes = ArcRecovery::with_subj_and_clip(&[circle(0.0, 0.0, 1.0)], &[square], 0.001)
+            .overlay(OverlayRule::Difference, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        let contour = &shapes[0][0];
+        let arcs = arcs(contour);
+        assert_eq!(arcs.len(), 1);
+
+        let (radius, large_arc, to) = arcs[0];
+        assert!((radius - 1.0).abs() < 1e-9);
+        assert!(large_arc);
+        // the arc ends at one of the exact intersections
+        assert!(is_near(to, [1.0, 0.0], 1e-9) || is_near(to, [0.0, 1.0], 1e-9));
+        assert_eq!(contour.segments.len(), 3);
+    }
+
+    #[test]
+    fn test_two_circles() {
+        let shapes = ArcRecovery::with_subj_and_clip(&[circle(0.0, 0.0, 1.0)], &[circle(1.0, 0.0, 1.0)], 0.001)
+            .overlay(OverlayRule::Intersect, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        let contour = &shapes[0][0];
+        assert_eq!(contour.segments.len(), 2);
+
+        // the lens ends are the exact circle intersections
+        let y = 0.75f64.sqrt();
+        for p in [contour.start, arcs(contour)[0].2, arcs(contour)[1].2] {
+            assert!(is_near(p, [0.5, y], 1e-9) || is_near(p, [0.5, -y], 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_full_circle() {
+        let shapes = ArcRecovery::with_subj_and_clip(&[circle(5.0, 5.0, 2.0)], &[], 0.001)
+            .overlay(OverlayRule::Subject, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        let arcs = arcs(&shapes[0][0]);
+        assert_eq!(arcs.len(), 2);
+        let points = shapes[0][0].flatten(0.0001);
+        let mut sum = 0.0;
+        let mut a = points[points.len() - 1];
+        for &b in points.iter() {
+            sum += a[0] * b[1] - b[0] * a[1];
+            a = b;
+        }
+        assert!((0.5 * sum.abs() - 4.0 * PI).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_lines_only() {
+        let a = CurvePath::new([0.0, 0.0]).line_to([2.0, 0.0]).line_to([2.0, 2.0]).line_to([0.0, 2.0]);
+        let b = CurvePath::new([1.0, 1.0]).line_to([3.0, 1.0]).line_to([3.0, 3.0]).line_to([1.0, 3.0]);
+
+        let shapes = ArcRecovery::with_subj_and_clip(&[a], &[b], 0.001).overlay(OverlayRule::Union, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].segments.len(), 7);
+        assert!(arcs(&shapes[0][0]).is_empty());
+    }
+
+    #[test]
+    fn test_tangent_line() {
+        // the bottom edge of the small square touches the circle at its top and lies closer than the tolerance
+        let square = CurvePath::new([-0.01, 1.0]).line_to([0.01, 1.0]).line_to([0.01, 1.02]).line_to([-0.01, 1.02]);
+
+        let shapes = ArcRecovery::with_subj_and_clip(&[square], &[circle(0.0, 0.0, 1.0)], 0.001)
+            .overlay(OverlayRule::Difference, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        assert!(arcs(&shapes[0][0]).is_empty());
+        assert_eq!(shapes[0][0].segments.len(), 3);
+    }
+
+    #[test]
+    fn test_chord_on_line() {
+        // the tolerance is not less than the radius, so the quarter arc is flattened into two chords,
+        // and the clip has the same edges as lines
+        let h = core::f64::consts::FRAC_1_SQRT_2;
+        let subj = CurvePath::new([0.0, 0.0]).line_to([1.0, 0.0]).arc_to([1.0, 1.0], 0.0, false, true, [0.0, 1.0]);
+        let clip = CurvePath::new([0.0, 0.0]).line_to([1.0, 0.0]).line_to([h, h]).line_to([0.0, 1.0]);
+
+        // the common edges come from the arc and the lines, so they stay lines
+        let shapes = ArcRecovery::with_subj_and_clip(core::slice::from_ref(&subj), &[clip], 1.0)
+            .overlay(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].segments.len(), 3);
+        assert!(arcs(&shapes[0][0]).is_empty());
+
+        // the same edges from the arc alone are an arc
+        let shapes = ArcRecovery::with_subj_and_clip(&[subj], &[], 1.0).overlay(OverlayRule::Subject, FillRule::NonZero);
+        assert_eq!(shapes[0][0].segments.len(), 3);
+        assert_eq!(arcs(&shapes[0][0]).len(), 1);
+    }
+
+    #[test]
+    fn test_collinear_lines() {
+        let a = [CurvePath::new([0.0, 0.0]).line_to([1.0, 0.0]).line_to([2.0, 0.0]).line_to([2.0, 2.0]).line_to([0.0, 2.0])];
+
+        let shapes = ArcRecovery::with_subj_and_clip(&a, &[circle(1.0, 1.0, 0.5)], 0.001)
+            .overlay(OverlayRule::Difference, FillRule::EvenOdd);
+        assert_eq!(shapes[0][0].segments.len(), 3);
+        assert_eq!(arcs(&shapes[0][1]).len(), 2);
+
+        let options = OverlayOptions { preserve_input_collinear: true, preserve_output_collinear: true, ..Default::default() };
+        let shapes = ArcRecovery::with_subj_and_clip_custom(&a, &[circle(1.0, 1.0, 0.5)], 0.001, options, Default::default())
+            .overlay(OverlayRule::Difference, FillRule::EvenOdd);
+        assert_eq!(shapes[0][0].segments.len(), 4);
+    }
+}
@@ -4,8 +4,12 @@ use i_shape::base::data::Paths;
 use i_shape::source::resource::ShapeResource;
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
+use crate::float::error::{no_error_bound, FloatInputError};
+use crate::float::precision::FixedPrecision;
 use crate::float::string_overlay::FloatStringOverlay;
 use crate::string::clip::ClipRule;
+use crate::string::piece::StringPiece;
+use alloc::vec::Vec;
 
 pub trait FloatClip<R, P, T>
 where
@@ -39,6 +43,64 @@ where
     /// # Returns
     /// A `Paths<P>` collection of string lines that meet the clipping conditions.
     fn clip_by_with_solver(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Paths<P>;
+
+    /// The same as `clip_by`, but checks the input first.
+    /// - `resource`: A clipping shape.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of string lines that meet the clipping conditions,
+    /// or `FloatInputError` if a point is not finite or the bounds can not be mapped into the integer grid.
+    fn try_clip_by(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule) -> Result<Paths<P>, FloatInputError>;
+
+    /// The same as `clip_by_with_solver`, but checks the input first.
+    /// - `resource`: A clipping shape.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of string lines that meet the clipping conditions, or `FloatInputError`
+    /// if a point is not finite, the bounds can not be mapped into the integer grid or the grid is too coarse for `max_error`.
+    fn try_clip_by_with_solver(
+        &self,
+        source: &R,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        max_error: T,
+    ) -> Result<Paths<P>, FloatInputError>;
+
+    /// The same as `try_clip_by`, but snaps the input to the fixed grid,
+    /// so results of all operations made with the same `precision` lie on the same grid.
+    /// - `resource`: A clipping shape.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    /// - `precision`: The grid step and origin.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of string lines that meet the clipping conditions, or `FloatInputError`
+    /// if a point is not finite, the precision is not valid or the input is too far from the origin of the grid.
+    fn clip_by_with_precision(
+        &self,
+        source: &R,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Paths<P>, FloatInputError>;
+
+    /// The same as `clip_by`, but tells for every piece which source path it comes from and where on it.
+    /// - `resource`: A clipping shape.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    ///
+    /// # Returns
+    /// A collection of `StringPiece` with the index of the source path and the arc lengths where the piece
+    /// starts and ends on it, ordered by the source index and then along the source path.
+    /// A part shared by several source paths is reported for every one of them.
+    fn clip_by_with_sources(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<P, T>>;
 }
 
 impl<R0, R1, P, T> FloatClip<R0, P, T> for R1
@@ -58,4 +120,40 @@ where
         FloatStringOverlay::with_shape_and_string(resource, self)
             .clip_string_lines_with_solver(fill_rule, clip_rule, solver)
     }
+
+    #[inline]
+    fn try_clip_by(&self, resource: &R0, fill_rule: FillRule, clip_rule: ClipRule) -> Result<Paths<P>, FloatInputError> {
+        self.try_clip_by_with_solver(resource, fill_rule, clip_rule, Default::default(), no_error_bound())
+    }
+
+    #[inline]
+    fn try_clip_by_with_solver(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+        max_error: T,
+    ) -> Result<Paths<P>, FloatInputError> {
+        Ok(FloatStringOverlay::try_with_shape_and_string(resource, self, max_error)?
+            .clip_string_lines_with_solver(fill_rule, clip_rule, solver))
+    }
+
+    #[inline]
+    fn clip_by_with_precision(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Paths<P>, FloatInputError> {
+        Ok(FloatStringOverlay::with_shape_and_string_precision(resource, self, precision)?
+            .clip_string_lines_with_solver(fill_rule, clip_rule, Default::default()))
+    }
+
+    #[inline]
+    fn clip_by_with_sources(&self, resource: &R0, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<P, T>> {
+        FloatStringOverlay::with_shape_and_string(resource, self)
+            .clip_string_lines_with_sources(fill_rule, clip_rule, Default::default())
+    }
 }
@@ -0,0 +1,506 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::Paths;
+use i_shape::source::resource::ShapeResource;
+
+// protects from a huge number of points with a tiny tolerance
+const MAX_SPLIT_COUNT: usize = 1 << 12;
+
+/// A segment of a curve path. Every segment starts at the end of the previous one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveSegment<P, T> {
+    /// A straight line to the point.
+    Line(P),
+    /// A quadratic Bézier curve with a control point and an end point.
+    Quad(P, P),
+    /// A cubic Bézier curve with two control points and an end point.
+    Cubic(P, P, P),
+    /// An elliptical arc, parameterized like the SVG `A` command.
+    Arc {
+        /// The radii of the ellipse along its axes. Too small radii are scaled up to reach the end point.
+        radii: P,
+        /// The rotation of the ellipse axes in radians.
+        rotation: T,
+        /// If `true`, the arc spans more than 180 degrees.
+        large_arc: bool,
+        /// If `true`, the arc goes in the direction of increasing angles
+        /// (counterclockwise for the y-axis pointing up).
+        sweep: bool,
+        /// The end point.
+        to: P,
+    },
+}
+
+/// A closed path built from lines, Bézier curves and arcs, e.g. an SVG path or a glyph outline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurvePath<P, T> {
+    /// The start point of the path.
+    pub start: P,
+    /// The segments of the path. The path is closed from the last point back to the start.
+    pub segments: Vec<CurveSegment<P, T>>,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> CurvePath<P, T> {
+    #[inline]
+    pub fn new(start: P) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Adds a straight line to the point.
+    #[inline]
+    pub fn line_to(mut self, to: P) -> Self {
+        self.segments.push(CurveSegment::Line(to));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve.
+    #[inline]
+    pub fn quad_to(mut self, control: P, to: P) -> Self {
+        self.segments.push(CurveSegment::Quad(control, to));
+        self
+    }
+
+    /// Adds a cubic Bézier curve.
+    #[inline]
+    pub fn cubic_to(mut self, control_0: P, control_1: P, to: P) -> Self {
+        self.segments.push(CurveSegment::Cubic(control_0, control_1, to));
+        self
+    }
+
+    /// Adds an elliptical arc, parameterized like the SVG `A` command.
+    #[inline]
+    pub fn arc_to(mut self, radii: P, rotation: T, large_arc: bool, sweep: bool, to: P) -> Self {
+        self.segments.push(CurveSegment::Arc {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            to,
+        });
+        self
+    }
+
+    /// Approximates the path with a polyline.
+    ///
+    /// - `tolerance`: The maximum distance between a curve and its chords, in the same units as the points.
+    ///   The number of chords is chosen separately for every segment from its curvature.
+    ///
+    /// # Returns
+    /// A contour without the closing point.
+    pub fn flatten(&self, tolerance: T) -> Vec<P> {
+        let mut points = Vec::with_capacity(self.segments.len() + 1);
+        points.push(self.start);
+        let mut a = self.start;
+        for segment in self.segments.iter() {
+            a = flatten_segment(a, segment, tolerance, &mut points);
+        }
+
+        if points.len() > 1 && is_same(points[points.len() - 1], self.start) {
+            points.pop();
+        }
+
+        points
+    }
+}
+
+/// Flattened curve paths, accepted by every float operation as a `ShapeResource`.
+///
+/// ```rust
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::float::curve::{CurvePath, FlatCurves};
+/// use i_overlay::float::single::SingleFloatOverlay;
+///
+/// let circle = CurvePath::new([1.0, 0.0])
+///     .arc_to([1.0, 1.0], 0.0, false, true, [-1.0, 0.0])
+///     .arc_to([1.0, 1.0], 0.0, false, true, [1.0, 0.0]);
+/// let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+///
+/// let curves = FlatCurves::new(&[circle], 0.001);
+/// let shapes = curves.overlay(&square, OverlayRule::Intersect, FillRule::EvenOdd);
+///
+/// assert_eq!(shapes.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlatCurves<P> {
+    pub paths: Paths<P>,
+}
+
+impl<P> FlatCurves<P> {
+    /// Flattens curve paths.
+    ///
+    /// - `curves`: The closed curve paths.
+    /// - `tolerance`: The maximum distance between a curve and its chords, in the float units of the
+    ///   `FloatPointAdapter`. A value about the adapter `inv_scale` keeps chords near the integer grid precision.
+    pub fn new<T>(curves: &[CurvePath<P, T>], tolerance: T) -> Self
+    where
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self {
+            paths: curves.iter().map(|curve| curve.flatten(tolerance)).collect(),
+        }
+    }
+}
+
+impl<P, T> ShapeResource<P, T> for FlatCurves<P>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    type ResourceIter<'a>
+        = <[Vec<P>] as ShapeResource<P, T>>::ResourceIter<'a>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_paths(&self) -> Self::ResourceIter<'_> {
+        self.paths.as_slice().iter_paths()
+    }
+}
+
+#[inline]
+fn split_count<T: FloatNumber>(value: T) -> usize {
+    if value.to_f64() >= MAX_SPLIT_COUNT as f64 {
+        MAX_SPLIT_COUNT
+    } else {
+        // it is ceil for the positive values
+        value.to_usize() + 1
+    }
+}
+
+// the max distance between a chord and the curve is |B''| * h^2 / 8, where `h` is the parameter step
+fn chord_count<T: FloatNumber>(max_second_derivative: T, tolerance: T) -> usize {
+    if max_second_derivative == T::from_float(0.0) {
+        return 1;
+    }
+    if tolerance <= T::from_float(0.0) {
+        return MAX_SPLIT_COUNT;
+    }
+    split_count((max_second_derivative / (T::from_float(8.0) * tolerance)).sqrt())
+}
+
+#[inline]
+pub(crate) fn is_same<P: FloatPointCompatible<T>, T: FloatNumber>(a: P, b: P) -> bool {
+    a.x() == b.x() && a.y() == b.y()
+}
+
+#[inline]
+pub(crate) fn length<T: FloatNumber>(x: T, y: T) -> T {
+    (x * x + y * y).sqrt()
+}
+
+fn flatten_quad<P: FloatPointCompatible<T>, T: FloatNumber>(a: P, c: P, b: P, tolerance: T, points: &mut Vec<P>) {
+    let two = T::from_float(2.0);
+    let d = two * length(a.x() - two * c.x() + b.x(), a.y() - two * c.y() + b.y());
+    let n = chord_count(d, tolerance);
+    let step = T::from_float(1.0) / T::from_usize(n);
+    for i in 1..n {
+        let t = T::from_usize(i) * step;
+        let s = T::from_float(1.0) - t;
+        let (k0, k1, k2) = (s * s, two * s * t, t * t);
+        points.push(P::from_xy(
+            k0 * a.x() + k1 * c.x() + k2 * b.x(),
+            k0 * a.y() + k1 * c.y() + k2 * b.y(),
+        ));
+    }
+    points.push(b);
+}
+
+fn flatten_cubic<P: FloatPointCompatible<T>, T: FloatNumber>(
+    a: P,
+    c0: P,
+    c1: P,
+    b: P,
+    tolerance: T,
+    points: &mut Vec<P>,
+) {
+    let two = T::from_float(2.0);
+    let three = T::from_float(3.0);
+    let d0 = length(a.x() - two * c0.x() + c1.x(), a.y() - two * c0.y() + c1.y());
+    let d1 = length(c0.x() - two * c1.x() + b.x(), c0.y() - two * c1.y() + b.y());
+    let n = chord_count(T::from_float(6.0) * d0.max(d1), tolerance);
+    let step = T::from_float(1.0) / T::from_usize(n);
+    for i in 1..n {
+        let t = T::from_usize(i) * step;
+        let s = T::from_float(1.0) - t;
+        let (k0, k1, k2, k3) = (s * s * s, three * s * s * t, three * s * t * t, t * t * t);
+        points.push(P::from_xy(
+            k0 * a.x() + k1 * c0.x() + k2 * c1.x() + k3 * b.x(),
+            k0 * a.y() + k1 * c0.y() + k2 * c1.y() + k3 * b.y(),
+        ));
+    }
+    points.push(b);
+}
+
+// signed angle from `u` to `v`
+pub(crate) fn angle<T: FloatNumber>(ux: T, uy: T, vx: T, vy: T) -> T {
+    let lengths = length(ux, uy) * length(vx, vy);
+    if lengths == T::from_float(0.0) {
+        return T::from_float(0.0);
+    }
+    let cos = ((ux * vx + uy * vy) / lengths)
+        .max(T::from_float(-1.0))
+        .min(T::from_float(1.0));
+    let a = cos.acos();
+    if ux * vy - uy * vx < T::from_float(0.0) { -a } else { a }
+}
+
+/// An elliptical arc in the center parameterization.
+pub(crate) struct EllipticalArc<T> {
+    pub(crate) cx: T,
+    pub(crate) cy: T,
+    pub(crate) rx: T,
+    pub(crate) ry: T,
+    sin_r: T,
+    cos_r: T,
+    start: T,
+    pub(crate) delta: T,
+}
+
+impl<T: FloatNumber> EllipticalArc<T> {
+    // the endpoint to center conversion follows the SVG implementation notes
+    pub(crate) fn new<P: FloatPointCompatible<T>>(
+        a: P,
+        radii: P,
+        rotation: T,
+        large_arc: bool,
+        sweep: bool,
+        b: P,
+    ) -> Option<Self> {
+        let zero = T::from_float(0.0);
+        let half = T::from_float(0.5);
+        let mut rx = radii.x().abs();
+        let mut ry = radii.y().abs();
+        if is_same(a, b) || rx == zero || ry == zero {
+            return None;
+        }
+
+        let (sin_r, cos_r) = rotation.sin_cos();
+        let dx = half * (a.x() - b.x());
+        let dy = half * (a.y() - b.y());
+        let x1 = cos_r * dx + sin_r * dy;
+        let y1 = cos_r * dy - sin_r * dx;
+
+        let lambda = x1 * x1 / (rx * rx) + y1 * y1 / (ry * ry);
+        if lambda > T::from_float(1.0) {
+            let s = lambda.sqrt();
+            rx = rx * s;
+            ry = ry * s;
+        }
+
+        let rxy1 = rx * rx * y1 * y1;
+        let ryx1 = ry * ry * x1 * x1;
+        let sq = ((rx * rx * ry * ry - rxy1 - ryx1) / (rxy1 + ryx1)).max(zero);
+        let coef = if large_arc == sweep { -sq.sqrt() } else { sq.sqrt() };
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        let cx = cos_r * cx1 - sin_r * cy1 + half * (a.x() + b.x());
+        let cy = sin_r * cx1 + cos_r * cy1 + half * (a.y() + b.y());
+
+        let ux = (x1 - cx1) / rx;
+        let uy = (y1 - cy1) / ry;
+        let vx = (-x1 - cx1) / rx;
+        let vy = (-y1 - cy1) / ry;
+
+        let start = angle(T::from_float(1.0), zero, ux, uy);
+        let mut delta = angle(ux, uy, vx, vy);
+        let two_pi = T::from_float(2.0 * PI);
+        if !sweep && delta > zero {
+            delta = delta - two_pi;
+        } else if sweep && delta < zero {
+            delta = delta + two_pi;
+        }
+
+        Some(Self {
+            cx,
+            cy,
+            rx,
+            ry,
+            sin_r,
+            cos_r,
+            start,
+            delta,
+        })
+    }
+
+    fn flatten<P: FloatPointCompatible<T>>(&self, b: P, tolerance: T, points: &mut Vec<P>) {
+        let zero = T::from_float(0.0);
+
+        // the sagitta r * (1 - cos(step / 2)) must not exceed the tolerance
+        let r = self.rx.max(self.ry);
+        let n = if tolerance <= zero {
+            MAX_SPLIT_COUNT
+        } else if tolerance >= r {
+            2
+        } else {
+            let step = T::from_float(2.0) * (T::from_float(1.0) - tolerance / r).acos();
+            split_count(self.delta.abs() / step).max(2)
+        };
+
+        let step = self.delta / T::from_usize(n);
+        for i in 1..n {
+            let (sin, cos) = (self.start + T::from_usize(i) * step).sin_cos();
+            let x = self.rx * cos;
+            let y = self.ry * sin;
+            points.push(P::from_xy(
+                self.cos_r * x - self.sin_r * y + self.cx,
+                self.sin_r * x + self.cos_r * y + self.cy,
+            ));
+        }
+        points.push(b);
+    }
+}
+
+/// Appends the flattened segment, which starts at `a`, without its start point.
+///
+/// # Returns
+/// The end point of the segment.
+pub(crate) fn flatten_segment<P: FloatPointCompatible<T>, T: FloatNumber>(
+    a: P,
+    segment: &CurveSegment<P, T>,
+    tolerance: T,
+    points: &mut Vec<P>,
+) -> P {
+    match segment {
+        CurveSegment::Line(b) => {
+            points.push(*b);
+            *b
+        }
+        CurveSegment::Quad(c, b) => {
+            flatten_quad(a, *c, *b, tolerance, points);
+            *b
+        }
+        CurveSegment::Cubic(c0, c1, b) => {
+            flatten_cubic(a, *c0, *c1, *b, tolerance, points);
+            *b
+        }
+        CurveSegment::Arc {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            to,
+        } => {
+            if let Some(arc) = EllipticalArc::new(a, *radii, *rotation, *large_arc, *sweep, *to) {
+                arc.flatten(*to, tolerance, points);
+            } else {
+                points.push(*to);
+            }
+            *to
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::curve::{CurvePath, FlatCurves};
+    use crate::float::single::SingleFloatOverlay;
+    use i_shape::float::area::Area;
+
+    fn max_radius_error(points: &[[f64; 2]], center: [f64; 2], radius: f64) -> f64 {
+        let mut error: f64 = 0.0;
+        for i in 0..points.len() {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+            let m = [0.5 * (a[0] + b[0]), 0.5 * (a[1] + b[1])];
+            for p in [a, m] {
+                let r = ((p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2)).sqrt();
+                error = error.max((r - radius).abs());
+            }
+        }
+        error
+    }
+
+    #[test]
+    fn test_lines() {
+        let path = CurvePath::new([0.0, 0.0])
+            .line_to([1.0, 0.0])
+            .line_to([1.0, 1.0])
+            .line_to([0.0, 0.0]);
+
+        assert_eq!(path.flatten(0.1), vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_arc() {
+        let circle = CurvePath::new([10.0, 0.0])
+            .arc_to([10.0, 10.0], 0.0, false, true, [-10.0, 0.0])
+            .arc_to([10.0, 10.0], 0.0, false, true, [10.0, 0.0]);
+
+        for tolerance in [0.1, 0.01, 0.001] {
+            let points = circle.flatten(tolerance);
+            assert!(max_radius_error(&points, [0.0, 0.0], 10.0) <= tolerance * 1.01);
+            // counterclockwise
+            assert!(points[1][1] > 0.0);
+        }
+
+        let coarse = circle.flatten(0.1).len();
+        let fine = circle.flatten(0.001).len();
+        assert!(fine > 5 * coarse);
+    }
+
+    #[test]
+    fn test_arc_flags() {
+        // a half circle from (0, 0) to (2, 0) goes below with the sweep and above without it
+        let below = CurvePath::new([0.0, 0.0]).arc_to([1.0, 1.0], 0.0, false, true, [2.0, 0.0]);
+        let above = CurvePath::new([0.0, 0.0]).arc_to([1.0, 1.0], 0.0, false, false, [2.0, 0.0]);
+
+        assert!(below.flatten(0.01).iter().all(|p| p[1] <= 1e-9));
+        assert!(above.flatten(0.01).iter().all(|p| p[1] >= -1e-9));
+
+        // too small radii are scaled up
+        let scaled = CurvePath::new([0.0, 0.0]).arc_to([0.5, 0.5], 0.0, false, true, [2.0, 0.0]);
+        assert!(scaled.flatten(0.01).iter().all(|p| ((p[0] - 1.0f64).powi(2) + p[1] * p[1] - 1.0).abs() < 1e-9));
+
+        // a large arc on a bigger circle
+        let large = CurvePath::new([0.0, 0.0]).arc_to([2.0, 2.0], 0.0, true, true, [2.0, 0.0]);
+        let points = large.flatten(0.01);
+        let min_y = points.iter().map(|p| p[1]).fold(f64::MAX, f64::min);
+        assert!((min_y + 2.0 + 3.0f64.sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_quad_and_cubic() {
+        let tolerance = 0.01;
+        let quad = CurvePath::new([0.0, 0.0]).quad_to([1.0, 2.0], [2.0, 0.0]);
+        let points = quad.flatten(tolerance);
+        // the curve reaches y = 1 at t = 0.5
+        let max_y = points.iter().map(|p| p[1]).fold(f64::MIN, f64::max);
+        assert!(max_y <= 1.0 && max_y > 1.0 - tolerance);
+
+        let cubic = CurvePath::new([0.0, 0.0]).cubic_to([0.0, 4.0], [4.0, 4.0], [4.0, 0.0]);
+        let points = cubic.flatten(tolerance);
+        let max_y = points.iter().map(|p| p[1]).fold(f64::MIN, f64::max);
+        assert!(max_y <= 3.0 && max_y > 3.0 - tolerance);
+
+        let fine = cubic.flatten(0.0001);
+        assert!(fine.len() > points.len());
+    }
+
+    #[test]
+    fn test_overlay() {
+        let circle = CurvePath::new([1.0, 0.0])
+            .arc_to([1.0, 1.0], 0.0, false, true, [-1.0, 0.0])
+            .arc_to([1.0, 1.0], 0.0, false, true, [1.0, 0.0]);
+        let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+
+        let curves = FlatCurves::new(&[circle], 0.0001);
+        let shapes = curves.overlay(&square, OverlayRule::Intersect, FillRule::EvenOdd);
+
+        assert_eq!(shapes.len(), 1);
+        let area: f64 = shapes[0][0].area();
+        let area = area.abs();
+        assert!((area - 0.25 * core::f64::consts::PI).abs() < 0.001);
+    }
+}
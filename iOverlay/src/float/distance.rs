@@ -0,0 +1,105 @@
+use core::marker::PhantomData;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::point::FloatPoint;
+use i_shape::source::resource::ShapeResource;
+use crate::core::distance::{DistancePair, DistanceQuery};
+
+/// The same as `DistanceQuery`, but for float points.
+/// Distances are computed directly on the source coordinates in `f64`, without the integer grid.
+///
+/// ```rust
+/// use i_overlay::float::distance::FloatDistanceQuery;
+///
+/// let square = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+/// let line = vec![[1.5, 2.0], [3.0, 2.0]];
+///
+/// let a = FloatDistanceQuery::with_shapes(&square);
+/// let b = FloatDistanceQuery::with_paths(&line);
+///
+/// let nearest = a.distance(&b).unwrap();
+/// assert!((nearest.distance - 1.25f64.sqrt()).abs() < 1e-12);
+/// assert_eq!(nearest.a, [1.0, 1.0]);
+/// assert_eq!(nearest.b, [1.5, 2.0]);
+/// ```
+#[derive(Clone)]
+pub struct FloatDistanceQuery<P: FloatPointCompatible<T>, T: FloatNumber> {
+    query: DistanceQuery,
+    phantom: PhantomData<(P, T)>,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatDistanceQuery<P, T> {
+    /// Creates a query for areas.
+    /// - `resource`: A `ShapeResource` of closed contours, the filled area is found by the even-odd rule.
+    ///   `ShapeResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    pub fn with_shapes<R: ShapeResource<P, T> + ?Sized>(resource: &R) -> Self {
+        Self::new(resource, true)
+    }
+
+    /// Creates a query for open lines.
+    /// - `resource`: A `ShapeResource` of paths, the last point of a path is not connected to the first one.
+    pub fn with_paths<R: ShapeResource<P, T> + ?Sized>(resource: &R) -> Self {
+        Self::new(resource, false)
+    }
+
+    fn new<R: ShapeResource<P, T> + ?Sized>(resource: &R, is_area: bool) -> Self {
+        let paths = resource
+            .iter_paths()
+            .map(|path| path.iter().map(|p| FloatPoint::new(p.x().to_f64(), p.y().to_f64())));
+        Self { query: DistanceQuery::new(paths, is_area), phantom: PhantomData }
+    }
+
+    /// Returns `true` if the query has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Returns the minimum distance and the nearest points, `a` is on this geometry and `b` is on the other one.
+    /// Returns `None` if any geometry is empty.
+    #[inline]
+    pub fn distance(&self, other: &Self) -> Option<DistancePair<P, T>> {
+        self.query.distance(&other.query).map(to_float_pair)
+    }
+
+    /// Returns `true` if the distance between the geometries does not exceed `max_distance`.
+    /// Stops at the first pair of close enough points.
+    #[inline]
+    pub fn is_within_distance(&self, other: &Self, max_distance: T) -> bool {
+        self.query.is_within_distance(&other.query, max_distance.to_f64())
+    }
+
+    /// Returns the nearest point of this geometry to `point`. It is `point` itself if it lies inside the area.
+    /// Returns `None` if the geometry is empty.
+    #[inline]
+    pub fn nearest_point(&self, point: &P) -> Option<DistancePair<P, T>> {
+        let p = FloatPoint::new(point.x().to_f64(), point.y().to_f64());
+        self.query.nearest_point(p).map(to_float_pair)
+    }
+
+    /// Returns the directed discrete Hausdorff distance from this geometry to the other one.
+    /// See `DistanceQuery::directed_discrete_hausdorff`.
+    #[inline]
+    pub fn directed_discrete_hausdorff(&self, other: &Self, max_step: T) -> Option<DistancePair<P, T>> {
+        self.query.directed_discrete_hausdorff(&other.query, max_step.to_f64()).map(to_float_pair)
+    }
+
+    /// Returns the symmetric discrete Hausdorff distance, the larger of both directed distances.
+    /// See `DistanceQuery::discrete_hausdorff`.
+    #[inline]
+    pub fn discrete_hausdorff(&self, other: &Self, max_step: T) -> Option<DistancePair<P, T>> {
+        self.query.discrete_hausdorff(&other.query, max_step.to_f64()).map(to_float_pair)
+    }
+}
+
+#[inline]
+fn to_float_pair<P: FloatPointCompatible<T>, T: FloatNumber>(pair: DistancePair<FloatPoint<f64>, f64>) -> DistancePair<P, T> {
+    DistancePair {
+        distance: T::from_float(pair.distance),
+        a: P::from_xy(T::from_float(pair.a.x), T::from_float(pair.a.y)),
+        b: P::from_xy(T::from_float(pair.b.x), T::from_float(pair.b.y)),
+    }
+}
@@ -0,0 +1,133 @@
+//! This module provides the error of the fallible float API.
+//! Float coordinates are mapped into the integer grid by `FloatPointAdapter`, which silently produces
+//! garbage for non-finite points or for bounds that can not be mapped. The `try_` methods check the input first.
+
+use core::fmt;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+
+/// The reason why the input can not be processed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatInputError {
+    /// The point has a NaN or infinite coordinate.
+    /// - `source`: The index of the input resource: 0 for the subject or the shape, 1 for the clip or the string lines.
+    /// - `path`: The index of the path in the resource.
+    /// - `point`: The index of the point in the path.
+    NonFinitePoint { source: usize, path: usize, point: usize },
+    /// A parameter such as width or offset is NaN or infinite.
+    NonFiniteParameter,
+    /// The bounding box of the input can not be mapped into the integer grid.
+    DegenerateAdapter,
+    /// The grid step is too coarse: snapping a point to the grid moves it further than the allowed error.
+    PrecisionLoss { error: f64, max_error: f64 },
+    /// The step of `FixedPrecision` is not positive or not finite, or its origin is not finite.
+    InvalidPrecision,
+    /// The input or the result is too far from the origin of `FixedPrecision` for its step.
+    OutOfGrid,
+    /// The dash pattern has a negative or non-finite length, or all its lengths are zero.
+    InvalidDashPattern,
+    /// The dash pattern is finer than the grid step, or it splits a path into more than a million dashes.
+    DashPatternTooFine,
+}
+
+impl fmt::Display for FloatInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FloatInputError::NonFinitePoint { source, path, point } => {
+                write!(f, "non-finite point {} of path {} in source {}", point, path, source)
+            }
+            FloatInputError::NonFiniteParameter => write!(f, "non-finite parameter"),
+            FloatInputError::DegenerateAdapter => write!(f, "bounds can not be mapped into the integer grid"),
+            FloatInputError::PrecisionLoss { error, max_error } => {
+                write!(f, "snapping error {} exceeds the allowed error {}", error, max_error)
+            }
+            FloatInputError::InvalidPrecision => write!(f, "invalid step or origin of the fixed precision"),
+            FloatInputError::OutOfGrid => write!(f, "bounds are too far from the origin of the fixed precision"),
+            FloatInputError::InvalidDashPattern => write!(f, "invalid dash pattern"),
+            FloatInputError::DashPatternTooFine => write!(f, "dash pattern is too fine for the grid"),
+        }
+    }
+}
+
+impl core::error::Error for FloatInputError {}
+
+#[inline]
+pub(crate) fn is_finite<T: FloatNumber>(value: T) -> bool {
+    value.to_f64().is_finite()
+}
+
+pub(crate) fn check_finite<'a, P, T, I>(source: usize, paths: I) -> Result<(), FloatInputError>
+where
+    I: Iterator<Item = &'a [P]>,
+    P: FloatPointCompatible<T> + 'a,
+    T: FloatNumber,
+{
+    for (path_index, path) in paths.enumerate() {
+        if let Some(point_index) = path.iter().position(|p| !is_finite(p.x()) || !is_finite(p.y())) {
+            return Err(FloatInputError::NonFinitePoint { source, path: path_index, point: point_index });
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn check_adapter<P, T>(adapter: &FloatPointAdapter<P, T>, max_error: T) -> Result<(), FloatInputError>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    let dir_scale = adapter.dir_scale.to_f64();
+    let inv_scale = adapter.inv_scale.to_f64();
+    let is_valid = dir_scale.is_finite()
+        && inv_scale.is_finite()
+        && dir_scale > 0.0
+        && inv_scale > 0.0
+        && is_finite(adapter.offset.x())
+        && is_finite(adapter.offset.y());
+
+    if !is_valid {
+        return Err(FloatInputError::DegenerateAdapter);
+    }
+
+    // a point is rounded to the nearest grid node
+    let error = 0.5 * inv_scale;
+    let max_error = max_error.to_f64();
+    if error > max_error {
+        return Err(FloatInputError::PrecisionLoss { error, max_error });
+    }
+
+    Ok(())
+}
+
+/// The allowed error of the `try_` methods without an explicit bound.
+#[inline]
+pub(crate) fn no_error_bound<T: FloatNumber>() -> T {
+    T::from_float(f64::INFINITY)
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::adapter::FloatPointAdapter;
+    use i_float::float::rect::FloatRect;
+    use crate::float::error::{check_adapter, check_finite, FloatInputError};
+
+    #[test]
+    fn test_finite() {
+        let paths = [[[0.0, 0.0], [1.0, 1.0]], [[0.0, f64::NAN], [1.0, 1.0]]];
+        let result = check_finite(1, paths.iter().map(|path| path.as_slice()));
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 1, path: 1, point: 0 }));
+    }
+
+    #[test]
+    fn test_precision() {
+        let adapter = FloatPointAdapter::<[f64; 2], f64>::new(FloatRect::new(0.0, 1024.0, 0.0, 1024.0));
+        assert!(check_adapter(&adapter, 0.001).is_ok());
+        assert!(matches!(check_adapter(&adapter, 1e-9), Err(FloatInputError::PrecisionLoss { .. })));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let adapter = FloatPointAdapter::<[f64; 2], f64>::new(FloatRect::new(-f64::MAX, f64::MAX, 0.0, 1.0));
+        assert_eq!(check_adapter(&adapter, 1.0), Err(FloatInputError::DegenerateAdapter));
+    }
+}
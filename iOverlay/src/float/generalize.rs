@@ -0,0 +1,54 @@
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Shape, Shapes};
+use i_shape::float::adapter::{ShapesToFloat, ShapesToInt};
+use crate::core::generalize::{Generalize, GeneralizeMethod};
+
+/// Trait `FloatGeneralize` provides topology-preserving simplification of float shapes with a tolerance.
+/// See `Generalize` for the details.
+pub trait FloatGeneralize<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// Simplifies shapes with a tolerance.
+    ///
+    /// - `method`: The algorithm and its tolerance, a distance for Douglas–Peucker and an area for Visvalingam–Whyatt.
+    /// - `preserve_shared_borders`: If `true`, borders shared by adjacent shapes are simplified identically
+    ///   and shapes never overlap each other. If `false`, every shape is simplified independently.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` with the same layout as the input.
+    ///
+    /// ```rust
+    /// use i_overlay::core::generalize::GeneralizeMethod;
+    /// use i_overlay::float::generalize::FloatGeneralize;
+    ///
+    /// let shapes = vec![vec![vec![[0.0, 0.0], [5.0, 0.1], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]]]];
+    ///
+    /// let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(0.5), false);
+    ///
+    /// assert_eq!(result[0][0].len(), 4);
+    /// ```
+    fn generalize(&self, method: GeneralizeMethod<T>, preserve_shared_borders: bool) -> Shapes<P>;
+}
+
+impl<P, T> FloatGeneralize<P, T> for [Shape<P>]
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    fn generalize(&self, method: GeneralizeMethod<T>, preserve_shared_borders: bool) -> Shapes<P> {
+        let adapter = FloatPointAdapter::with_iter(self.iter().flatten().flatten());
+        let scale = adapter.dir_scale.to_f64();
+        let method = match method {
+            GeneralizeMethod::DouglasPeucker(tolerance) => {
+                GeneralizeMethod::DouglasPeucker((tolerance.to_f64() * scale + 0.5) as i64)
+            }
+            GeneralizeMethod::Visvalingam(area) => {
+                GeneralizeMethod::Visvalingam((area.to_f64() * scale * scale + 0.5) as i64)
+            }
+        };
+
+        self.to_int(&adapter)
+            .generalize(method, preserve_shared_borders)
+            .to_float(&adapter)
+    }
+}
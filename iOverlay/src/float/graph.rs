@@ -3,6 +3,7 @@
 //! based on the overlay rule applied.
 
 use crate::core::graph::OverlayGraph;
+use crate::core::custom_rule::CustomOverlayRule;
 use crate::core::overlay_rule::OverlayRule;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
@@ -62,4 +63,26 @@ impl<'a, P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlayGraph<'a, P, T>
 
         float
     }
+
+    /// Extracts shapes from the overlay graph based on a user-defined overlay rule.
+    /// - `rule`: The custom rule that selects the result boundary links and the filled side.
+    /// - `buffer`: Reusable buffer, optimisation purpose only.
+    /// - Returns: A vector of `Shape<P>`, representing the geometric result of the applied rule.
+    #[inline]
+    pub fn extract_shapes_custom(&self, rule: CustomOverlayRule, buffer: &mut BooleanExtractionBuffer) -> Shapes<P> {
+        let shapes = self
+            .graph
+            .extract_shapes_custom(rule, buffer);
+        let mut float = shapes.to_float(&self.adapter);
+
+        if self.clean_result {
+            if self.graph.options.preserve_output_collinear {
+                float.despike_contour(&self.adapter);
+            } else {
+                float.simplify_contour(&self.adapter);
+            }
+        }
+
+        float
+    }
 }
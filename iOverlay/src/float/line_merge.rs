@@ -0,0 +1,49 @@
+use core::cmp::Ordering;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::Path;
+use crate::string::line_merge::{merge_paths, MergedLines};
+
+/// Trait `FloatLineMerge` joins connected float polylines into maximal chains.
+/// End points are matched by the exact coordinates, where `-0.0` is equal to `0.0`.
+pub trait FloatLineMerge<P, T: FloatNumber> {
+    /// Joins paths that share end points into maximal chains.
+    /// - `directed`: If `true`, a path is joined only to a path that starts where it ends and paths are never reversed.
+    ///   Otherwise a path can be reversed to continue the chain.
+    ///
+    /// ```rust
+    /// use i_overlay::float::line_merge::FloatLineMerge;
+    ///
+    /// let paths = vec![
+    ///     vec![[0.0, 0.0], [1.0, 0.0]],
+    ///     vec![[1.0, 0.0], [1.0, 1.0]],
+    ///     vec![[1.0, 1.0], [0.0, 0.0]],
+    /// ];
+    ///
+    /// let merged = paths.merge_lines(true);
+    /// assert!(merged.paths.is_empty());
+    /// assert_eq!(merged.rings, vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]]);
+    /// ```
+    fn merge_lines(&self, directed: bool) -> MergedLines<P>;
+}
+
+impl<P, T> FloatLineMerge<P, T> for [Path<P>]
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn merge_lines(&self, directed: bool) -> MergedLines<P> {
+        merge_paths(self, directed, |a, b| {
+            compare(a.x().to_f64(), b.x().to_f64()).then_with(|| compare(a.y().to_f64(), b.y().to_f64()))
+        })
+    }
+}
+
+// `-0.0` and `0.0` are the same coordinate, NaN goes after all numbers to keep the order total
+#[inline]
+fn compare(a: f64, b: f64) -> Ordering {
+    // adding zero turns `-0.0` into `0.0`
+    let (a, b) = (a + 0.0, b + 0.0);
+    a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_shape::base::data::Shapes;
+use i_shape::float::adapter::ShapesToFloat;
+use i_shape::float::despike::DeSpikeContour;
+use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::{IntContour, IntShapes};
+use i_shape::source::resource::ShapeResource;
+use crate::core::fill_rule::FillRule;
+use crate::core::minkowski::Minkowski;
+use crate::core::overlay::{Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::float::overlay::OverlayOptions;
+
+/// Trait `FloatMinkowski` provides the Minkowski sum and difference for contours, or shapes.
+/// Inputs are resolved with the `NonZero` fill rule before the operation.
+pub trait FloatMinkowski<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// Computes the Minkowski sum `{a + b | a ∈ self, b ∈ kernel}`.
+    ///
+    /// - `kernel`: The contour, or shapes to sweep over `self`.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the sum.
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    ///
+    /// ```rust
+    /// use i_overlay::float::minkowski::FloatMinkowski;
+    ///
+    /// let square = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+    /// let kernel = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+    ///
+    /// let shapes = square.minkowski_sum(&kernel);
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// assert_eq!(shapes[0][0].len(), 4);
+    /// ```
+    fn minkowski_sum<R: ShapeResource<P, T>>(&self, kernel: &R) -> Shapes<P>;
+
+    /// Computes the Minkowski sum `{a + b | a ∈ self, b ∈ kernel}`.
+    ///
+    /// - `kernel`: The contour, or shapes to sweep over `self`.
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the sum.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn minkowski_sum_custom<R: ShapeResource<P, T>>(&self, kernel: &R, options: OverlayOptions<T>) -> Shapes<P>;
+
+    /// Computes the Minkowski difference `{a - b | a ∈ self, b ∈ kernel}`, the sum with the reflected kernel.
+    ///
+    /// - `kernel`: The contour, or shapes to reflect and sweep over `self`.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the difference.
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    fn minkowski_diff<R: ShapeResource<P, T>>(&self, kernel: &R) -> Shapes<P>;
+
+    /// Computes the Minkowski difference `{a - b | a ∈ self, b ∈ kernel}`, the sum with the reflected kernel.
+    ///
+    /// - `kernel`: The contour, or shapes to reflect and sweep over `self`.
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the difference.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn minkowski_diff_custom<R: ShapeResource<P, T>>(&self, kernel: &R, options: OverlayOptions<T>) -> Shapes<P>;
+}
+
+impl<S, P, T> FloatMinkowski<P, T> for S
+where
+    S: ShapeResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn minkowski_sum<R: ShapeResource<P, T>>(&self, kernel: &R) -> Shapes<P> {
+        minkowski(self, kernel, false, Default::default())
+    }
+
+    #[inline]
+    fn minkowski_sum_custom<R: ShapeResource<P, T>>(&self, kernel: &R, options: OverlayOptions<T>) -> Shapes<P> {
+        minkowski(self, kernel, false, options)
+    }
+
+    #[inline]
+    fn minkowski_diff<R: ShapeResource<P, T>>(&self, kernel: &R) -> Shapes<P> {
+        minkowski(self, kernel, true, Default::default())
+    }
+
+    #[inline]
+    fn minkowski_diff_custom<R: ShapeResource<P, T>>(&self, kernel: &R, options: OverlayOptions<T>) -> Shapes<P> {
+        minkowski(self, kernel, true, options)
+    }
+}
+
+fn minkowski<S, R, P, T>(source: &S, kernel: &R, reflect: bool, options: OverlayOptions<T>) -> Shapes<P>
+where
+    S: ShapeResource<P, T>,
+    R: ShapeResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    let kernel_point = |p: &P| if reflect { P::from_xy(-p.x(), -p.y()) } else { *p };
+
+    let source_rect = if let Some(rect) = FloatRect::with_iter(source.iter_paths().flatten()) { rect } else { return Vec::new() };
+    let kernel_rect = {
+        let points: Vec<P> = kernel.iter_paths().flatten().map(kernel_point).collect();
+        if let Some(rect) = FloatRect::with_points(&points) { rect } else { return Vec::new() }
+    };
+
+    let rect = FloatRect::new(
+        source_rect.min_x + kernel_rect.min_x,
+        source_rect.max_x + kernel_rect.max_x,
+        source_rect.min_y + kernel_rect.min_y,
+        source_rect.max_y + kernel_rect.max_y,
+    );
+    let adapter = FloatPointAdapter::<P, T>::new(rect);
+
+    // the kernel is centered at the origin in the int space, its center moves to the source,
+    // so the int sum of points is the int image of the float sum
+    let half = T::from_float(0.5);
+    let cx = half * (kernel_rect.min_x + kernel_rect.max_x);
+    let cy = half * (kernel_rect.min_y + kernel_rect.max_y);
+    let ox = adapter.offset.x();
+    let oy = adapter.offset.y();
+
+    let source_shapes = resolve(source.iter_paths().map(|path| {
+        path.iter().map(|p| adapter.float_to_int(&P::from_xy(p.x() + cx, p.y() + cy))).collect()
+    }));
+    let kernel_shapes = resolve(kernel.iter_paths().map(|path| {
+        path.iter()
+            .map(|p| {
+                let q = kernel_point(p);
+                adapter.float_to_int(&P::from_xy(q.x() - cx + ox, q.y() - cy + oy))
+            })
+            .collect()
+    }));
+
+    let preserve_output_collinear = options.preserve_output_collinear;
+    let shapes = source_shapes.minkowski_sum(&kernel_shapes, options.int_with_adapter(&adapter));
+    let mut float = shapes.to_float(&adapter);
+
+    if options.clean_result {
+        if preserve_output_collinear {
+            float.despike_contour(&adapter);
+        } else {
+            float.simplify_contour(&adapter);
+        }
+    }
+
+    float
+}
+
+fn resolve<I: Iterator<Item = IntContour>>(paths: I) -> IntShapes {
+    let mut overlay = Overlay::new(64);
+    for path in paths {
+        overlay.add_contour(&path, ShapeType::Subject);
+    }
+    overlay.overlay(OverlayRule::Subject, FillRule::NonZero)
+}
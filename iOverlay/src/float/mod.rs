@@ -5,4 +5,14 @@ pub mod string_overlay;
 pub mod string_graph;
 pub mod slice;
 pub mod clip;
-pub mod single;
\ No newline at end of file
+pub mod single;
+pub mod minkowski;
+pub mod generalize;
+pub mod curve;
+pub mod arc_recovery;
+pub mod union_all;
+pub mod validate;
+pub mod error;
+pub mod precision;
+pub mod distance;
+pub mod line_merge;
@@ -13,9 +13,12 @@ use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::simple::SimplifyContour;
 use crate::core::fill_rule::FillRule;
 use crate::core::overlay::{ContourDirection, IntOverlayOptions, Overlay, ShapeType};
+use crate::core::custom_rule::CustomOverlayRule;
 use crate::core::overlay_rule::OverlayRule;
 use crate::core::solver::Solver;
+use crate::float::error::{check_finite, no_error_bound, FloatInputError};
 use crate::float::graph::FloatOverlayGraph;
+use crate::float::precision::{FixedPrecision, GridMode};
 
 #[derive(Debug, Clone, Copy)]
 pub struct OverlayOptions<T: FloatNumber> {
@@ -178,6 +181,116 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
             .unsafe_add_source(subj, ShapeType::Subject)
     }
 
+    /// Creates a new `FloatOverlay` instance like `with_subj_and_clip`, but checks the input first.
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    ///
+    /// # Errors
+    /// `FloatInputError` if a point is not finite or the bounds can not be mapped into the integer grid.
+    ///
+    /// ```rust
+    /// use i_overlay::float::error::FloatInputError;
+    /// use i_overlay::float::overlay::FloatOverlay;
+    ///
+    /// let subj = vec![[0.0, 0.0], [0.0, 1.0], [1.0, f64::NAN]];
+    /// let clip = vec![[1.0, 0.0], [1.0, 1.0], [2.0, 1.0]];
+    ///
+    /// let result = FloatOverlay::try_with_subj_and_clip(&subj, &clip);
+    ///
+    /// assert_eq!(result.err(), Some(FloatInputError::NonFinitePoint { source: 0, path: 0, point: 2 }));
+    /// ```
+    #[inline]
+    pub fn try_with_subj_and_clip<R0, R1>(subj: &R0, clip: &R1) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T> +?Sized,
+        R1: ShapeResource<P, T> +?Sized,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::try_with_subj_and_clip_custom(subj, clip, Default::default(), Default::default(), no_error_bound())
+    }
+
+    /// Creates a new `FloatOverlay` instance like `with_subj_and_clip_custom`, but checks the input first.
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Errors
+    /// `FloatInputError` if a point is not finite, the bounds can not be mapped into the integer grid
+    /// or the grid is too coarse for `max_error`.
+    pub fn try_with_subj_and_clip_custom<R0, R1>(
+        subj: &R0,
+        clip: &R1,
+        options: OverlayOptions<T>,
+        solver: Solver,
+        max_error: T,
+    ) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T> +?Sized,
+        R1: ShapeResource<P, T> +?Sized,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::checked_with_subj_and_clip(subj, clip, options, solver, GridMode::Checked(max_error))
+    }
+
+    /// Creates a new `FloatOverlay` instance like `with_subj_and_clip_custom`, but snaps the input to the fixed grid.
+    /// Results of all operations made with the same `precision` lie on the same grid.
+    /// - `subj`: A `ShapeResource` that define the subject.
+    /// - `clip`: A `ShapeResource` that define the clip.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    /// - `precision`: The grid step and origin.
+    ///
+    /// # Errors
+    /// `FloatInputError` if a point is not finite, the precision is not valid
+    /// or the input is too far from the origin of the grid.
+    #[inline]
+    pub fn with_subj_and_clip_precision<R0, R1>(
+        subj: &R0,
+        clip: &R1,
+        options: OverlayOptions<T>,
+        solver: Solver,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T> +?Sized,
+        R1: ShapeResource<P, T> +?Sized,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::checked_with_subj_and_clip(subj, clip, options, solver, GridMode::Fixed(precision))
+    }
+
+    fn checked_with_subj_and_clip<R0, R1>(
+        subj: &R0,
+        clip: &R1,
+        options: OverlayOptions<T>,
+        solver: Solver,
+        grid: GridMode<T>,
+    ) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T> +?Sized,
+        R1: ShapeResource<P, T> +?Sized,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        check_finite(0, subj.iter_paths())?;
+        check_finite(1, clip.iter_paths())?;
+
+        let iter = subj.iter_paths().chain(clip.iter_paths()).flatten();
+        let adapter = grid.adapter(FloatRect::with_iter(iter).unwrap_or(FloatRect::zero()))?;
+
+        let subj_capacity = subj.iter_paths().fold(0, |s, c| s + c.len());
+        let clip_capacity = clip.iter_paths().fold(0, |s, c| s + c.len());
+
+        Ok(Self::new_custom(adapter, options, solver, subj_capacity + clip_capacity)
+            .unsafe_add_source(subj, ShapeType::Subject)
+            .unsafe_add_source(clip, ShapeType::Clip))
+    }
+
     /// Adds a shapes to the overlay.
     /// - `resource`: A `ShapeResource` that define subject or clip.
     ///   `ShapeResource` can be one of the following:
@@ -315,6 +428,29 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
 
         float
     }
+
+    /// Executes a single Boolean operation on the current geometry using a user-defined overlay rule.
+    /// - `rule`: The custom rule, see [`CustomOverlayRule`].
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - Returns: A vector of `Shapes<P>`, representing the geometric result of the applied rule.
+    ///
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    #[inline]
+    pub fn overlay_custom(&mut self, rule: CustomOverlayRule, fill_rule: FillRule) -> Shapes<P> {
+        let preserve_output_collinear = self.overlay.options.preserve_output_collinear;
+        let shapes = self.overlay.overlay_custom(rule, fill_rule);
+        let mut float = shapes.to_float(&self.adapter);
+
+        if self.clean_result {
+            if preserve_output_collinear {
+                float.despike_contour(&self.adapter);
+            } else {
+                float.simplify_contour(&self.adapter);
+            }
+        }
+
+        float
+    }
 }
 
 impl<T: FloatNumber> Default for OverlayOptions<T> {
@@ -0,0 +1,163 @@
+//! This module provides a fixed precision model for the float API.
+//! By default every float operation fits its own integer grid to the bounding box of the input,
+//! so the results of two separate operations are snapped to different grids.
+//! `FixedPrecision` describes one grid with a fixed step and origin, the results of all operations
+//! made with the same model lie on the same grid and their shared vertices are bitwise equal.
+
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use crate::float::error::{check_adapter, is_finite, FloatInputError};
+
+/// The integer grid with a fixed step and origin.
+/// A float point `p` is mapped to the grid node `round((p - origin) * (1 / step))`
+/// and a grid node `i` is mapped back to `i * step + origin`.
+///
+/// The input together with the result must stay within `2^30` steps from the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPrecision<T: FloatNumber> {
+    /// The distance between two neighboring grid nodes, must be positive.
+    pub step: T,
+    pub origin_x: T,
+    pub origin_y: T,
+}
+
+impl<T: FloatNumber> FixedPrecision<T> {
+    /// The max distance from the origin in steps.
+    const MAX_STEPS: f64 = (1 << 30) as f64;
+
+    /// Creates a grid with the origin at zero.
+    /// - `step`: The distance between two neighboring grid nodes, e.g. `1e-6`.
+    #[inline]
+    pub fn new(step: T) -> Self {
+        Self::with_origin(step, T::from_float(0.0), T::from_float(0.0))
+    }
+
+    /// Creates a grid with the given origin.
+    /// - `step`: The distance between two neighboring grid nodes.
+    /// - `origin_x`, `origin_y`: The grid node mapped to zero, usually a point near the data.
+    #[inline]
+    pub fn with_origin(step: T, origin_x: T, origin_y: T) -> Self {
+        Self { step, origin_x, origin_y }
+    }
+
+    /// Returns the grid node nearest to the point, the same as any operation with this model does.
+    /// The point must be within `2^30` steps from the origin.
+    #[inline]
+    pub fn snap<P: FloatPointCompatible<T>>(&self, point: &P) -> P {
+        // the same conversion as the operations, the scaled difference is not always equal to the divided one
+        let mut rect = FloatRect::with_point(*point);
+        rect.add_offset(self.step);
+        let adapter = self.grid_adapter(rect);
+        adapter.int_to_float(&adapter.float_to_int(point))
+    }
+
+    /// Returns the adapter of this grid for the given bounds.
+    /// - `rect`: The bounds of the input extended by how far the result can go from it.
+    ///
+    /// # Returns
+    /// The adapter or `FloatInputError::InvalidPrecision` if the step or the origin is not valid,
+    /// `FloatInputError::OutOfGrid` if the bounds are too far from the origin.
+    pub fn adapter<P: FloatPointCompatible<T>>(&self, rect: FloatRect<T>) -> Result<FloatPointAdapter<P, T>, FloatInputError> {
+        let step = self.step.to_f64();
+        if !step.is_finite() || step <= 0.0 || !is_finite(self.origin_x) || !is_finite(self.origin_y) {
+            return Err(FloatInputError::InvalidPrecision);
+        }
+
+        let dir_scale = 1.0 / step;
+        if !dir_scale.is_finite() {
+            return Err(FloatInputError::InvalidPrecision);
+        }
+
+        let ox = self.origin_x.to_f64();
+        let oy = self.origin_y.to_f64();
+        let max = (rect.min_x.to_f64() - ox).abs()
+            .max((rect.max_x.to_f64() - ox).abs())
+            .max((rect.min_y.to_f64() - oy).abs())
+            .max((rect.max_y.to_f64() - oy).abs());
+
+        let steps = max * dir_scale;
+        if steps.is_nan() || steps > Self::MAX_STEPS {
+            return Err(FloatInputError::OutOfGrid);
+        }
+
+        // a snapped point can leave the bounds by a half of the step
+        let mut rect = rect;
+        rect.add_offset(self.step);
+
+        Ok(self.grid_adapter(rect))
+    }
+
+    #[inline]
+    fn grid_adapter<P: FloatPointCompatible<T>>(&self, rect: FloatRect<T>) -> FloatPointAdapter<P, T> {
+        FloatPointAdapter {
+            dir_scale: T::from_float(1.0 / self.step.to_f64()),
+            inv_scale: self.step,
+            offset: P::from_xy(self.origin_x, self.origin_y),
+            rect,
+        }
+    }
+}
+
+/// How a float operation maps its input into the integer grid.
+#[derive(Clone, Copy)]
+pub(crate) enum GridMode<'a, T: FloatNumber> {
+    /// The grid is fitted to the bounds, the input is not checked.
+    Auto,
+    /// The grid is fitted to the bounds, the input is checked and the snapping error is limited.
+    Checked(T),
+    /// The fixed grid, the input is checked.
+    Fixed(&'a FixedPrecision<T>),
+}
+
+impl<T: FloatNumber> GridMode<'_, T> {
+    #[inline]
+    pub(crate) fn is_checked(&self) -> bool {
+        !matches!(self, GridMode::Auto)
+    }
+
+    /// Returns the adapter for the bounds of the input.
+    pub(crate) fn adapter<P: FloatPointCompatible<T>>(&self, rect: FloatRect<T>) -> Result<FloatPointAdapter<P, T>, FloatInputError> {
+        match self {
+            GridMode::Auto => Ok(FloatPointAdapter::new(rect)),
+            GridMode::Checked(max_error) => {
+                let adapter = FloatPointAdapter::new(rect);
+                check_adapter(&adapter, *max_error)?;
+                Ok(adapter)
+            }
+            GridMode::Fixed(precision) => precision.adapter(rect),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::float::rect::FloatRect;
+    use crate::float::error::FloatInputError;
+    use crate::float::precision::FixedPrecision;
+
+    #[test]
+    fn test_snap() {
+        let precision = FixedPrecision::with_origin(0.25, 1.0, -1.0);
+        assert_eq!(precision.snap(&[1.3, -0.6]), [1.25, -0.5]);
+        assert_eq!(precision.snap(&[0.8, -1.1]), [0.75, -1.0]);
+    }
+
+    #[test]
+    fn test_adapter() {
+        let precision = FixedPrecision::new(1.0e-6);
+        let adapter = precision.adapter::<[f64; 2]>(FloatRect::new(-100.0, 100.0, 0.0, 1.0)).unwrap();
+        assert_eq!(adapter.float_to_int(&[1.0, 0.5]).x, 1_000_000);
+        assert_eq!(adapter.int_to_float(&adapter.float_to_int(&[1.0, 0.5])), [1.0, 0.5]);
+
+        let result = precision.adapter::<[f64; 2]>(FloatRect::new(0.0, 2000.0, 0.0, 1.0));
+        assert_eq!(result.err(), Some(FloatInputError::OutOfGrid));
+
+        let result = FixedPrecision::new(0.0).adapter::<[f64; 2]>(FloatRect::new(0.0, 1.0, 0.0, 1.0));
+        assert_eq!(result.err(), Some(FloatInputError::InvalidPrecision));
+
+        let result = FixedPrecision::new(f64::NAN).adapter::<[f64; 2]>(FloatRect::new(0.0, 1.0, 0.0, 1.0));
+        assert_eq!(result.err(), Some(FloatInputError::InvalidPrecision));
+    }
+}
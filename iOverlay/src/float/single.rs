@@ -4,7 +4,9 @@ use i_shape::base::data::Shapes;
 use i_shape::source::resource::ShapeResource;
 use crate::core::fill_rule::FillRule;
 use crate::core::overlay_rule::OverlayRule;
+use crate::float::error::FloatInputError;
 use crate::float::overlay::FloatOverlay;
+use crate::float::precision::FixedPrecision;
 
 /// Trait `SingleFloatOverlay` provides methods for overlay operations between various geometric entities.
 /// This trait supports boolean operations on contours, shapes, and collections of shapes, using customizable overlay and build rules.
@@ -26,6 +28,32 @@ where
     /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
     /// - Returns: A vector of `Shapes<P>` representing the cleaned-up geometric result.
     fn overlay(&self, source: &R1, overlay_rule: OverlayRule, fill_rule: FillRule) -> Shapes<P>;
+
+    /// The same as `overlay`, but checks the input first.
+    ///
+    /// - `resource`: A `ShapeResource` specifying the type of geometric entity to overlay with.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph, such as union or intersection.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - Returns: A vector of `Shapes<P>` representing the cleaned-up geometric result,
+    ///   or `FloatInputError` if a point is not finite or the bounds can not be mapped into the integer grid.
+    fn try_overlay(&self, source: &R1, overlay_rule: OverlayRule, fill_rule: FillRule) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `try_overlay`, but snaps the input to the fixed grid,
+    /// so results of all operations made with the same `precision` lie on the same grid.
+    ///
+    /// - `resource`: A `ShapeResource` specifying the type of geometric entity to overlay with.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph, such as union or intersection.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `precision`: The grid step and origin.
+    /// - Returns: A vector of `Shapes<P>` representing the cleaned-up geometric result,
+    ///   or `FloatInputError` if a point is not finite, the precision is not valid or the input is too far from the origin of the grid.
+    fn overlay_with_precision(
+        &self,
+        source: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Shapes<P>, FloatInputError>;
 }
 
 impl<R0, R1, P, T> SingleFloatOverlay<R0, R1, P, T> for R0
@@ -39,6 +67,23 @@ where
     fn overlay(&self, resource: &R1, overlay_rule: OverlayRule, fill_rule: FillRule) -> Shapes<P> {
         FloatOverlay::with_subj_and_clip(self, resource).overlay(overlay_rule, fill_rule)
     }
+
+    #[inline]
+    fn try_overlay(&self, resource: &R1, overlay_rule: OverlayRule, fill_rule: FillRule) -> Result<Shapes<P>, FloatInputError> {
+        FloatOverlay::try_with_subj_and_clip(self, resource).map(|mut overlay| overlay.overlay(overlay_rule, fill_rule))
+    }
+
+    #[inline]
+    fn overlay_with_precision(
+        &self,
+        resource: &R1,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        FloatOverlay::with_subj_and_clip_precision(self, resource, Default::default(), Default::default(), precision)
+            .map(|mut overlay| overlay.overlay(overlay_rule, fill_rule))
+    }
 }
 
 #[cfg(test)]
@@ -4,7 +4,9 @@ use i_shape::base::data::Shapes;
 use i_shape::source::resource::ShapeResource;
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
+use crate::float::error::{no_error_bound, FloatInputError};
 use crate::float::overlay::OverlayOptions;
+use crate::float::precision::FixedPrecision;
 use crate::float::string_overlay::FloatStringOverlay;
 use crate::string::rule::StringRule;
 
@@ -44,6 +46,45 @@ where
     ///
     /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
     fn slice_custom_by(&self, resource: &R, fill_rule: FillRule, options: OverlayOptions<T>, solver: Solver) -> Shapes<P>;
+
+    /// The same as `slice_by`, but checks the input first.
+    ///
+    /// - `resource`: A string lines.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    ///
+    /// Returns a `Shapes<P>` collection representing the sliced geometry,
+    /// or `FloatInputError` if a point is not finite or the bounds can not be mapped into the integer grid.
+    fn try_slice_by(&self, resource: &R, fill_rule: FillRule) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `slice_custom_by`, but checks the input first.
+    ///
+    /// - `resource`: A string lines.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// Returns a `Shapes<P>` collection representing the sliced geometry, or `FloatInputError`
+    /// if a point is not finite, the bounds can not be mapped into the integer grid or the grid is too coarse for `max_error`.
+    fn try_slice_custom_by(
+        &self,
+        resource: &R,
+        fill_rule: FillRule,
+        options: OverlayOptions<T>,
+        solver: Solver,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `try_slice_by`, but snaps the input to the fixed grid,
+    /// so results of all operations made with the same `precision` lie on the same grid.
+    ///
+    /// - `resource`: A string lines.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `precision`: The grid step and origin.
+    ///
+    /// Returns a `Shapes<P>` collection representing the sliced geometry, or `FloatInputError`
+    /// if a point is not finite, the precision is not valid or the input is too far from the origin of the grid.
+    fn slice_by_with_precision(&self, resource: &R, fill_rule: FillRule, precision: &FixedPrecision<T>) -> Result<Shapes<P>, FloatInputError>;
 }
 
 
@@ -69,6 +110,34 @@ where
             .map(|graph|graph.extract_shapes_custom(StringRule::Slice, options))
             .unwrap_or_default()
     }
+
+    #[inline]
+    fn try_slice_by(&self, resource: &R0, fill_rule: FillRule) -> Result<Shapes<P>, FloatInputError> {
+        self.try_slice_custom_by(resource, fill_rule, Default::default(), Default::default(), no_error_bound())
+    }
+
+    fn try_slice_custom_by(
+        &self,
+        resource: &R0,
+        fill_rule: FillRule,
+        options: OverlayOptions<T>,
+        solver: Solver,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        let shapes = FloatStringOverlay::try_with_shape_and_string(self, resource, max_error)?
+            .build_graph_view_with_solver(fill_rule, solver)
+            .map(|graph|graph.extract_shapes_custom(StringRule::Slice, options))
+            .unwrap_or_default();
+        Ok(shapes)
+    }
+
+    fn slice_by_with_precision(&self, resource: &R0, fill_rule: FillRule, precision: &FixedPrecision<T>) -> Result<Shapes<P>, FloatInputError> {
+        let shapes = FloatStringOverlay::with_shape_and_string_precision(self, resource, precision)?
+            .build_graph_view(fill_rule)
+            .map(|graph|graph.extract_shapes(StringRule::Slice))
+            .unwrap_or_default();
+        Ok(shapes)
+    }
 }
 
 
@@ -1,14 +1,19 @@
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
 use i_shape::base::data::Paths;
 use i_shape::float::adapter::ShapeToFloat;
 use i_shape::source::resource::ShapeResource;
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
+use crate::float::error::{check_finite, FloatInputError};
+use crate::float::precision::{FixedPrecision, GridMode};
 use crate::float::string_graph::FloatStringGraph;
 use crate::string::clip::ClipRule;
 use crate::string::overlay::StringOverlay;
+use crate::string::piece::StringPiece;
+use alloc::vec::Vec;
 
 /// The `FloatStringOverlay` struct is a builder for overlaying geometric shapes by converting
 /// floating-point geometry to integer space. It provides methods for adding paths and shapes,
@@ -59,6 +64,64 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
             .unsafe_add_string_lines(string)
     }
 
+    /// Creates a new `FloatStringOverlay` instance like `with_shape_and_string`, but checks the input first.
+    /// - `shape`: A `ShapeResource` define the shape.
+    /// - `string`: A `ShapeResource` define the string paths.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Errors
+    /// `FloatInputError` if a point is not finite, the bounds can not be mapped into the integer grid
+    /// or the grid is too coarse for `max_error`.
+    pub fn try_with_shape_and_string<R0, R1>(shape: &R0, string: &R1, max_error: T) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T>,
+        R1: ShapeResource<P, T>,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::checked_with_shape_and_string(shape, string, GridMode::Checked(max_error))
+    }
+
+    /// Creates a new `FloatStringOverlay` instance like `with_shape_and_string`, but snaps the input to the fixed grid.
+    /// - `shape`: A `ShapeResource` define the shape.
+    /// - `string`: A `ShapeResource` define the string paths.
+    /// - `precision`: The grid step and origin.
+    ///
+    /// # Errors
+    /// `FloatInputError` if a point is not finite, the precision is not valid
+    /// or the input is too far from the origin of the grid.
+    #[inline]
+    pub fn with_shape_and_string_precision<R0, R1>(shape: &R0, string: &R1, precision: &FixedPrecision<T>) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T>,
+        R1: ShapeResource<P, T>,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::checked_with_shape_and_string(shape, string, GridMode::Fixed(precision))
+    }
+
+    fn checked_with_shape_and_string<R0, R1>(shape: &R0, string: &R1, grid: GridMode<T>) -> Result<Self, FloatInputError>
+    where
+        R0: ShapeResource<P, T>,
+        R1: ShapeResource<P, T>,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        check_finite(0, shape.iter_paths())?;
+        check_finite(1, string.iter_paths())?;
+
+        let iter = shape.iter_paths().chain(string.iter_paths()).flatten();
+        let adapter = grid.adapter(FloatRect::with_iter(iter).unwrap_or(FloatRect::zero()))?;
+
+        let shape_capacity = shape.iter_paths().fold(0, |s, c| s + c.len());
+        let string_capacity = string.iter_paths().fold(0, |s, c| s + c.len());
+
+        Ok(Self::with_adapter(adapter, shape_capacity + string_capacity)
+            .unsafe_add_shapes(shape)
+            .unsafe_add_string_lines(string))
+    }
+
     /// Adds a shapes to the overlay.
     /// - `source`: A `ShapeResource` that define shape.
     ///   `ShapeResource` can be one of the following:
@@ -102,12 +165,7 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
     /// - **Safety**: Marked `unsafe` because it assumes each path is fully contained within the bounding box.
     #[inline]
     pub fn unsafe_add_string_line(mut self, path: &[P]) -> Self {
-        for w in path.windows(2) {
-            let a = self.adapter.float_to_int(&w[0]);
-            let b = self.adapter.float_to_int(&w[1]);
-            self.overlay.add_string_line([a, b]);
-        }
-
+        self.overlay.add_string_path_iter(path.iter().map(|p| self.adapter.float_to_int(p)));
         self
     }
 
@@ -143,4 +201,25 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
         let paths = self.overlay.clip_string_lines_with_solver(fill_rule, clip_rule, solver);
         paths.to_float(&self.adapter)
     }
-}
\ No newline at end of file
+
+    /// Clips lines like `clip_string_lines_with_solver`, and tells for every piece which source line it comes from and where on it.
+    /// The string paths are numbered from 0 in the order they were added.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how the boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    /// - Returns: The pieces ordered by the source index and then along the source line,
+    ///   the arc lengths are measured on the source lines as they are snapped to the integer grid.
+    pub fn clip_string_lines_with_sources(self, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<StringPiece<P, T>> {
+        let scale = self.adapter.inv_scale.to_f64();
+        self.overlay
+            .clip_string_lines_with_sources_and_solver(fill_rule, clip_rule, solver)
+            .into_iter()
+            .map(|piece| StringPiece {
+                path: piece.path.iter().map(|p| self.adapter.int_to_float(p)).collect(),
+                source: piece.source,
+                start: T::from_float(piece.start * scale),
+                end: T::from_float(piece.end * scale),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Shape, Shapes};
+use i_shape::float::adapter::{ShapeToInt, ShapesToFloat};
+use i_shape::float::despike::DeSpikeContour;
+use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShape;
+use crate::core::solver::Solver;
+use crate::core::union_all::UnionAll;
+use crate::float::overlay::OverlayOptions;
+
+/// Trait `FloatUnionAll` provides the union of many float shapes.
+/// See `UnionAll` for the details.
+pub trait FloatUnionAll<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// Unites all shapes into non-overlapping shapes.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the union.
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    ///
+    /// ```rust
+    /// use i_overlay::float::union_all::FloatUnionAll;
+    ///
+    /// let shapes: Vec<_> = (0..100)
+    ///     .map(|i| {
+    ///         let x = i as f64;
+    ///         vec![vec![[x, 0.0], [x + 1.5, 0.0], [x + 1.5, 1.0], [x, 1.0]]]
+    ///     })
+    ///     .collect();
+    ///
+    /// let result = shapes.union_all();
+    ///
+    /// assert_eq!(result.len(), 1);
+    /// assert_eq!(result[0][0].len(), 4);
+    /// ```
+    fn union_all(&self) -> Shapes<P>;
+
+    /// Unites all shapes into non-overlapping shapes.
+    ///
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the union.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn union_all_custom(&self, options: OverlayOptions<T>, solver: Solver) -> Shapes<P>;
+}
+
+impl<P, T> FloatUnionAll<P, T> for [Shape<P>]
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn union_all(&self) -> Shapes<P> {
+        self.union_all_custom(Default::default(), Default::default())
+    }
+
+    fn union_all_custom(&self, options: OverlayOptions<T>, solver: Solver) -> Shapes<P> {
+        let adapter = FloatPointAdapter::with_iter(self.iter().flatten().flatten());
+        let shapes: Vec<IntShape> = self.iter().map(|shape| shape.to_int(&adapter)).collect();
+
+        let result = shapes.union_all(options.int_with_adapter(&adapter), solver);
+        let mut float = result.to_float(&adapter);
+
+        if options.clean_result {
+            if options.preserve_output_collinear {
+                float.despike_contour(&adapter);
+            } else {
+                float.simplify_contour(&adapter);
+            }
+        }
+
+        float
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::Shape;
+use i_shape::float::adapter::ShapeToInt;
+use i_shape::int::shape::IntShape;
+use crate::core::overlay::ContourDirection;
+use crate::core::validate::{Validate, ValidationIssue, VertexIndex};
+
+/// Trait `FloatValidate` checks float shapes without changing them.
+/// See `Validate` for the details.
+pub trait FloatValidate<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// Returns all problems of the shapes.
+    ///
+    /// The shapes are validated on the same integer grid as the overlay uses,
+    /// so points closer than the grid step are reported as duplicated.
+    /// Points of vertex issues are the source points, intersection points are converted back from the grid.
+    /// - `main_direction`: The expected direction of outer contours.
+    ///
+    /// ```rust
+    /// use i_overlay::core::overlay::ContourDirection;
+    /// use i_overlay::core::validate::ValidationIssue;
+    /// use i_overlay::float::validate::FloatValidate;
+    ///
+    /// // a clockwise square
+    /// let shapes = vec![vec![vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]]]];
+    ///
+    /// let issues = shapes.validate(ContourDirection::CounterClockwise);
+    ///
+    /// assert_eq!(issues, vec![ValidationIssue::WrongOrientation { shape: 0, contour: 0 }]);
+    /// ```
+    fn validate(&self, main_direction: ContourDirection) -> Vec<ValidationIssue<P>>;
+}
+
+impl<P, T> FloatValidate<P, T> for [Shape<P>]
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    fn validate(&self, main_direction: ContourDirection) -> Vec<ValidationIssue<P>> {
+        let adapter = FloatPointAdapter::with_iter(self.iter().flatten().flatten());
+        let shapes: Vec<IntShape> = self.iter().map(|shape| shape.to_int(&adapter)).collect();
+
+        let source = |index: VertexIndex| self[index.shape][index.contour][index.vertex];
+
+        shapes
+            .validate(main_direction)
+            .into_iter()
+            .map(|issue| match issue {
+                ValidationIssue::Degenerate { shape, contour } => ValidationIssue::Degenerate { shape, contour },
+                ValidationIssue::DuplicatePoint { vertex, .. } => ValidationIssue::DuplicatePoint {
+                    vertex,
+                    point: source(vertex),
+                },
+                ValidationIssue::Spike { vertex, .. } => ValidationIssue::Spike {
+                    vertex,
+                    point: source(vertex),
+                },
+                ValidationIssue::SelfIntersection { a, b, point } => ValidationIssue::SelfIntersection {
+                    a,
+                    b,
+                    point: adapter.int_to_float(&point),
+                },
+                ValidationIssue::OverlappingEdges { a, b } => ValidationIssue::OverlappingEdges { a, b },
+                ValidationIssue::WrongOrientation { shape, contour } => {
+                    ValidationIssue::WrongOrientation { shape, contour }
+                }
+                ValidationIssue::HoleOutside { shape, contour, point } => ValidationIssue::HoleOutside {
+                    shape,
+                    contour,
+                    point: adapter.int_to_float(&point),
+                },
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+use crate::geom::predicate::cmp_by_angle;
+
+/// A planar graph with numbered nodes and edges.
+/// The half edge `2 * e` goes along the edge `e` from its first node to the second one, `2 * e + 1` goes back.
+pub(crate) trait PlanarGraph {
+    fn nodes_count(&self) -> usize;
+
+    fn edges_count(&self) -> usize;
+
+    /// The node where the half edge starts and its point.
+    fn half_start(&self, half: usize) -> (usize, IntPoint);
+}
+
+/// The faces of a planar graph, every face is a ring of half edges with the face on the left side.
+/// So the bounded faces are counterclockwise and the outer border of every connected part is clockwise.
+pub(crate) struct FaceRings {
+    pub(crate) rings: Vec<Vec<usize>>,
+    /// The ring of every half edge, `usize::MAX` for the half edges of the skipped edges.
+    pub(crate) ring_for_half: Vec<usize>,
+}
+
+impl FaceRings {
+    /// Traces the faces formed by the edges marked in `is_active`.
+    pub(crate) fn trace<G: PlanarGraph>(graph: &G, is_active: &[bool]) -> Self {
+        let half_count = 2 * graph.edges_count();
+
+        // outgoing half edges of every node in counterclockwise order
+        let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes_count()];
+        for half in 0..half_count {
+            if is_active[half >> 1] {
+                outgoing[graph.half_start(half).0].push(half);
+            }
+        }
+
+        let mut position = vec![0; half_count];
+        for (node, halves) in outgoing.iter_mut().enumerate() {
+            if halves.len() > 1 {
+                let origin = graph.half_start(halves[0]).1;
+                halves.sort_unstable_by(|&h0, &h1| {
+                    cmp_by_angle(origin, graph.half_start(h0 ^ 1).1, graph.half_start(h1 ^ 1).1)
+                });
+            }
+            debug_assert!(halves.iter().all(|&half| graph.half_start(half).0 == node));
+            for (i, &half) in halves.iter().enumerate() {
+                position[half] = i;
+            }
+        }
+
+        let mut ring_for_half = vec![usize::MAX; half_count];
+        let mut rings = Vec::new();
+        for start in 0..half_count {
+            if !is_active[start >> 1] || ring_for_half[start] != usize::MAX {
+                continue;
+            }
+            let ring_index = rings.len();
+            let mut ring = Vec::new();
+            let mut half = start;
+            while ring_for_half[half] == usize::MAX {
+                ring_for_half[half] = ring_index;
+                ring.push(half);
+
+                // the next half edge is the first one clockwise from the way back
+                let twin = half ^ 1;
+                let halves = &outgoing[graph.half_start(twin).0];
+                let i = position[twin];
+                half = halves[(i + halves.len() - 1) % halves.len()];
+            }
+            rings.push(ring);
+        }
+
+        Self { rings, ring_for_half }
+    }
+}
+
+/// A counterclockwise rectangle one unit around the points. It stands for the unbounded face.
+pub(crate) fn frame<I: IntoIterator<Item = IntPoint>>(points: I) -> IntContour {
+    let mut min = IntPoint::new(i32::MAX, i32::MAX);
+    let mut max = IntPoint::new(i32::MIN, i32::MIN);
+    for p in points {
+        min = IntPoint::new(min.x.min(p.x), min.y.min(p.y));
+        max = IntPoint::new(max.x.max(p.x), max.y.max(p.y));
+    }
+    let (x0, y0) = (min.x.saturating_sub(1), min.y.saturating_sub(1));
+    let (x1, y1) = (max.x.saturating_add(1), max.y.saturating_add(1));
+
+    vec![
+        IntPoint::new(x0, y0),
+        IntPoint::new(x1, y0),
+        IntPoint::new(x1, y1),
+        IntPoint::new(x0, y1),
+    ]
+}
@@ -2,4 +2,7 @@ pub(crate) mod x_segment;
 pub(crate) mod v_segment;
 pub(crate) mod end;
 pub(crate) mod line_range;
-pub(crate) mod id_point;
\ No newline at end of file
+pub(crate) mod id_point;
+pub(crate) mod predicate;
+pub(crate) mod face;
+pub(crate) mod overlap;
@@ -0,0 +1,48 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+
+/// Groups the rectangles into connected components of overlapped rectangles, touching ones overlap too.
+/// Items of different groups can not cross each other. Groups and their items are sorted by the first index.
+pub(crate) fn overlap_groups(rects: &[IntRect]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_by_key(|&i| rects[i].min_x);
+
+    let mut parents: Vec<usize> = (0..rects.len()).collect();
+    let mut active: Vec<usize> = Vec::new();
+    for &i in order.iter() {
+        let rect = &rects[i];
+        active.retain(|&j| rects[j].max_x >= rect.min_x);
+        for &j in active.iter() {
+            if is_overlap(&rects[j], rect) {
+                let a = root(&mut parents, i);
+                let b = root(&mut parents, j);
+                parents[a] = b;
+            }
+        }
+        active.push(i);
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..rects.len() {
+        let r = root(&mut parents, i);
+        groups.entry(r).or_default().push(i);
+    }
+
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    groups.sort_unstable_by_key(|group| group[0]);
+    groups
+}
+
+#[inline]
+pub(crate) fn is_overlap(a: &IntRect, b: &IntRect) -> bool {
+    a.min_x <= b.max_x && b.min_x <= a.max_x && a.min_y <= b.max_y && b.min_y <= a.max_y
+}
+
+fn root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
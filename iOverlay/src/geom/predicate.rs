@@ -0,0 +1,42 @@
+use core::cmp::Ordering;
+use i_float::int::point::IntPoint;
+
+/// The sign of the turn from `a`-`b` to `p`: positive for a counter-clockwise turn, zero if collinear.
+#[inline]
+pub(crate) fn orient(a: IntPoint, b: IntPoint, p: IntPoint) -> i128 {
+    let ab = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let ap = (p.x as i64 - a.x as i64, p.y as i64 - a.y as i64);
+    (ab.0 as i128 * ap.1 as i128 - ab.1 as i128 * ap.0 as i128).signum()
+}
+
+/// `p` lies on the segment `a`-`b`, if it is collinear with it.
+#[inline]
+pub(crate) fn is_within(a: IntPoint, b: IntPoint, p: IntPoint) -> bool {
+    a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y)
+}
+
+/// The even-odd test, a point on an edge of the ring counts as inside.
+pub(crate) fn is_inside_or_on(p: IntPoint, ring: &[IntPoint]) -> bool {
+    let mut is_inside = false;
+    let mut a = ring[ring.len() - 1];
+    for &b in ring.iter() {
+        let d = orient(a, b, p);
+        if d == 0 && is_within(a, b, p) {
+            return true;
+        }
+        if (a.y > p.y) != (b.y > p.y) && (d > 0) == (b.y > a.y) {
+            is_inside = !is_inside;
+        }
+        a = b;
+    }
+    is_inside
+}
+
+/// Compares the directions from `o` to `a` and to `b` by the counterclockwise angle from the positive x axis in `[0, 2π)`.
+#[inline]
+pub(crate) fn cmp_by_angle(o: IntPoint, a: IntPoint, b: IntPoint) -> Ordering {
+    let (ax, ay) = (a.x as i64 - o.x as i64, a.y as i64 - o.y as i64);
+    let (bx, by) = (b.x as i64 - o.x as i64, b.y as i64 - o.y as i64);
+    let half = |x: i64, y: i64| y < 0 || (y == 0 && x < 0);
+    half(ax, ay).cmp(&half(bx, by)).then_with(|| 0.cmp(&(ax as i128 * by as i128 - ay as i128 * bx as i128)))
+}
@@ -14,10 +14,13 @@ pub(crate) struct VSegment {
 impl VSegment {
     #[inline(always)]
     fn is_under_segment_order(&self, other: &VSegment) -> Ordering {
+        // if the start of one segment lies on the other one, they are ordered by the end
         match self.a.cmp(&other.a) {
-            Ordering::Less => Triangle::clock_order_point(self.a, other.a, self.b),
+            Ordering::Less => Triangle::clock_order_point(self.a, other.a, self.b)
+                .then_with(|| Triangle::clock_order_point(self.a, other.b, self.b)),
             Ordering::Equal => Triangle::clock_order_point(self.a, other.b, self.b),
-            Ordering::Greater => Triangle::clock_order_point(other.a, other.b, self.a),
+            Ordering::Greater => Triangle::clock_order_point(other.a, other.b, self.a)
+                .then_with(|| Triangle::clock_order_point(other.a, other.b, self.b)),
         }
     }
 
@@ -103,4 +106,16 @@ mod tests {
         let order = s.is_under_point_order(p);
         assert_eq!(order, Ordering::Less);
     }
+
+    #[test]
+    fn test_start_on_segment() {
+        let s = VSegment { a: IntPoint::new(-17, -1), b: IntPoint::new(-9, -1) };
+        let down = VSegment { a: IntPoint::new(-11, -1), b: IntPoint::new(-9, -12) };
+        let up = VSegment { a: IntPoint::new(-11, -1), b: IntPoint::new(-10, 3) };
+
+        assert_eq!(down.cmp(&s), Ordering::Less);
+        assert_eq!(s.cmp(&down), Ordering::Greater);
+        assert_eq!(up.cmp(&s), Ordering::Greater);
+        assert_eq!(s.cmp(&up), Ordering::Less);
+    }
 }
\ No newline at end of file
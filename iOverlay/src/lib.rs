@@ -8,7 +8,7 @@
 //! - **Polygons**: with holes, self-intersections, and multiple contours.
 //! - **Simplification**: removes degenerate vertices and merges collinear edges.
 //! - **Fill Rules**: even-odd, non-zero, positive and negative.
-//! - **Data Types**: Supports i32, f32, and f64 APIs.
+//! - **Data Types**: Supports i32, i64, f32, and f64 APIs.
 //!
 //! ## Simple Example
 //! ![Simple Example](https://raw.githubusercontent.com/iShape-Rust/iOverlay/main/readme/example_union.svg)
@@ -197,6 +197,8 @@ pub mod vector;
 pub mod float;
 pub mod string;
 pub mod segm;
+#[cfg(feature = "wide")]
+pub mod wide;
 
 pub(crate) mod split;
 pub(crate) mod bind;
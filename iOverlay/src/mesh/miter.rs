@@ -57,4 +57,44 @@ impl Miter {
             SharpMiter::AcB(ia, ic, ib)
         }
     }
+
+    /// Miter for lines with different offsets: the intersection of `pa + t * va` and `pb - s * vb`.
+    /// Falls back to the plain `ab` connection if the lines are parallel or `t` is outside `[0, max_length]`.
+    #[inline]
+    pub(super) fn cross<T: FloatNumber, P: FloatPointCompatible<T>>(
+        pa: P,
+        pb: P,
+        va: P,
+        vb: P,
+        max_length: T,
+        adapter: &FloatPointAdapter<P, T>,
+    ) -> SharpMiter {
+        let ia = adapter.float_to_int(&pa);
+        let ib = adapter.float_to_int(&pb);
+
+        if ia == ib {
+            return SharpMiter::Degenerate;
+        }
+
+        let cross = va.x() * vb.y() - va.y() * vb.x();
+        if cross.abs() < T::from_float(0.000_001) {
+            return SharpMiter::AB(ia, ib);
+        }
+
+        let dx = pb.x() - pa.x();
+        let dy = pb.y() - pa.y();
+        let t = (dx * vb.y() - dy * vb.x()) / cross;
+        if t < T::from_float(0.0) || t > max_length {
+            return SharpMiter::AB(ia, ib);
+        }
+
+        let c = P::from_xy(pa.x() + t * va.x(), pa.y() + t * va.y());
+        let ic = adapter.float_to_int(&c);
+
+        if ia == ic || ib == ic {
+            SharpMiter::AB(ia, ib)
+        } else {
+            SharpMiter::AcB(ia, ic, ib)
+        }
+    }
 }
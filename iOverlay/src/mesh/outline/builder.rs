@@ -93,7 +93,7 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
 
         // build segments only from points which are not equal in int space
         let i0 = path.len() - 1;
-        let i1 = Self::next_unique_point(i0, 0, path, adapter);
+        let i1 = next_unique_point(i0, 0, path, adapter);
         if i1 == usize::MAX { return }
 
         let start = Section::new(self.radius, &path[i0], &path[i1]);
@@ -101,13 +101,13 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
         segments.add_section(&s0, adapter);
 
         let mut i = i1;
-        i = Self::next_unique_point(i, i + 1, path, adapter);
+        i = next_unique_point(i, i + 1, path, adapter);
         while i != usize::MAX {
             let si = Section::new(self.radius, &s0.b, &path[i]);
             self.join_builder.add_join(&s0, &si, adapter, segments);
             segments.add_section(&si, adapter);
 
-            i = Self::next_unique_point(i, i + 1, path, adapter);
+            i = next_unique_point(i, i + 1, path, adapter);
             s0 = si;
         }
 
@@ -125,17 +125,103 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
     }
 }
 
-impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> Builder<J, P, T> {
-    #[inline]
-    fn next_unique_point(start: usize, index: usize, path: &[P], adapter: &FloatPointAdapter<P, T>) -> usize {
-        let a = adapter.float_to_int(&path[start]);
-        for (j, p) in path.iter().enumerate().skip(index) {
-            let b = adapter.float_to_int(p);
-            if a != b {
-                return j;
-            }
+pub(super) struct VariableOutlineBuilder<P: FloatPointCompatible<T>, T: FloatNumber> {
+    join: LineJoin<T>,
+    _phantom: PhantomData<P>,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> VariableOutlineBuilder<P, T> {
+    pub(super) fn new(join: LineJoin<T>) -> Self {
+        Self { join, _phantom: Default::default() }
+    }
+
+    /// Builds segments for a path where every edge has its own radius.
+    /// - `radii`: The radius of each edge, `radii[i]` is used for the edge `path[i] -> path[i + 1]`.
+    pub(super) fn build(
+        &self,
+        path: &[P],
+        radii: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        if path.len() < 2 { return; }
+
+        // build segments only from points which are not equal in int space
+        // a section gets the radius of the last edge on its way, previous edges are degenerate
+        let n = path.len();
+        let i0 = n - 1;
+        let i1 = next_unique_point(i0, 0, path, adapter);
+        if i1 == usize::MAX { return }
+
+        let start = Section::new(radii[(i1 + n - 1) % n], &path[i0], &path[i1]);
+        let mut s0 = start.clone();
+        segments.add_section(&s0, adapter);
+
+        let mut i = i1;
+        i = next_unique_point(i, i + 1, path, adapter);
+        while i != usize::MAX {
+            let si = Section::new(radii[i - 1], &s0.b, &path[i]);
+            self.add_join(&s0, &si, adapter, segments);
+            segments.add_section(&si, adapter);
+
+            i = next_unique_point(i, i + 1, path, adapter);
+            s0 = si;
+        }
+
+        self.add_join(&s0, &start, adapter, segments);
+    }
+
+    fn add_join(
+        &self,
+        s0: &Section<P, T>,
+        s1: &Section<P, T>,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        let zero = T::from_float(0.0);
+        if (s0.radius >= zero) != (s1.radius >= zero) {
+            // sections are on the opposite sides of the path
+            BevelJoinBuilder.add_join(s0, s1, adapter, segments);
+            return;
         }
 
-        usize::MAX
+        let radius = if s0.radius.abs() > s1.radius.abs() { s0.radius } else { s1.radius };
+        match self.join {
+            LineJoin::Bevel => BevelJoinBuilder.add_join(s0, s1, adapter, segments),
+            LineJoin::Miter(ratio) => MiterJoinBuilder::new(ratio, radius).add_join(s0, s1, adapter, segments),
+            LineJoin::Round(ratio) => RoundJoinBuilder::new(ratio, radius).add_join(s0, s1, adapter, segments),
+        }
     }
-}
\ No newline at end of file
+
+    pub(super) fn capacity(&self, points_count: usize) -> usize {
+        let join_capacity = match self.join {
+            LineJoin::Bevel => 2,
+            LineJoin::Miter(_) => 4,
+            LineJoin::Round(ratio) => JoinBuilder::<P, T>::capacity(&RoundJoinBuilder::new(ratio, T::from_float(1.0))),
+        };
+        join_capacity * points_count
+    }
+
+    /// Returns the additional offset for the max absolute radius.
+    pub(super) fn additional_offset(&self, radius: T) -> T {
+        let radius = radius.abs();
+        match self.join {
+            LineJoin::Bevel => JoinBuilder::<P, T>::additional_offset(&BevelJoinBuilder, radius),
+            LineJoin::Miter(ratio) => JoinBuilder::<P, T>::additional_offset(&MiterJoinBuilder::new(ratio, radius), radius),
+            LineJoin::Round(ratio) => JoinBuilder::<P, T>::additional_offset(&RoundJoinBuilder::new(ratio, radius), radius),
+        }
+    }
+}
+
+#[inline]
+fn next_unique_point<P: FloatPointCompatible<T>, T: FloatNumber>(start: usize, index: usize, path: &[P], adapter: &FloatPointAdapter<P, T>) -> usize {
+    let a = adapter.float_to_int(&path[start]);
+    for (j, p) in path.iter().enumerate().skip(index) {
+        let b = adapter.float_to_int(p);
+        if a != b {
+            return j;
+        }
+    }
+
+    usize::MAX
+}
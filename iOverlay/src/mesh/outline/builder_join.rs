@@ -162,7 +162,12 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
                 segments.push(Segment::bold_subject_ab(ibc, ib));
             }
         } else {
-            match Miter::sharp(pa, pb, s0.dir, s1.dir, adapter) {
+            let miter = if s0.radius == s1.radius {
+                Miter::sharp(pa, pb, s0.dir, s1.dir, adapter)
+            } else {
+                Miter::cross(pa, pb, s0.dir, s1.dir, self.max_length, adapter)
+            };
+            match miter {
                 SharpMiter::AB(a, b) => segments.push(Segment::bold_subject_ab(a, b)),
                 SharpMiter::AcB(a, c, b) => {
                     segments.push(Segment::bold_subject_ab(a, c));
@@ -187,7 +192,6 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
 pub(super) struct RoundJoinBuilder<T> {
     inv_ratio: T,
     average_count: usize,
-    radius: T,
     limit_dot_product: T,
     expand: bool,
     rot_dir: T,
@@ -208,7 +212,6 @@ impl<T: FloatNumber> RoundJoinBuilder<T> {
         Self {
             inv_ratio: T::from_float(1.0) / fixed_ratio,
             average_count,
-            radius,
             limit_dot_product,
             expand,
             rot_dir,
@@ -247,12 +250,31 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for RoundJoin
 
         let rotator = Rotator::<T>::with_angle(self.rot_dir * delta_angle);
 
+        // sections can have different radii, then the arc is an affine image of the circle arc
+        // from `start` to `end`, or a spiral if the turn is close to a U-turn
+        let sin_angle = angle.sin();
+        let is_affine = s0.radius != s1.radius && sin_angle > T::from_float(0.1);
+        let radius_step = (s1.radius - s0.radius) / T::from_usize(n);
+
         let center = s0.b;
+        let va = FloatPointMath::sub(&start, &center);
+        let vb = FloatPointMath::sub(&end, &center);
+
         let mut v = dir;
+        let mut radius = s0.radius;
         let mut a = adapter.float_to_int(&start);
-        for _ in 1..n {
+        for i in 1..n {
             v = rotator.rotate(&v);
-            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, self.radius));
+            radius = radius + radius_step;
+            let p = if is_affine {
+                let t = delta_angle * T::from_usize(i);
+                let ka = (angle - t).sin() / sin_angle;
+                let kb = t.sin() / sin_angle;
+                let offset = FloatPointMath::add(&FloatPointMath::scale(&va, ka), &FloatPointMath::scale(&vb, kb));
+                FloatPointMath::add(&center, &offset)
+            } else {
+                FloatPointMath::add(&center, &FloatPointMath::scale(&v, radius))
+            };
 
             let b = adapter.float_to_int(&p);
             if a != b {
@@ -4,9 +4,11 @@ use alloc::vec::Vec;
 use crate::core::fill_rule::FillRule;
 use crate::core::overlay::{ContourDirection, Overlay, ShapeType};
 use crate::core::overlay_rule::OverlayRule;
+use crate::float::error::{check_finite, is_finite, no_error_bound, FloatInputError};
 use crate::float::overlay::OverlayOptions;
-use crate::mesh::outline::builder::OutlineBuilder;
-use crate::mesh::style::OutlineStyle;
+use crate::float::precision::{FixedPrecision, GridMode};
+use crate::mesh::outline::builder::{OutlineBuilder, VariableOutlineBuilder};
+use crate::mesh::style::{LineJoin, OutlineStyle};
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -17,6 +19,8 @@ use i_shape::float::int_area::IntArea;
 use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::simple::SimplifyContour;
 use crate::mesh::overlay::OffsetOverlay;
+use crate::segm::offset::ShapeCountOffset;
+use crate::segm::segment::Segment;
 
 pub trait OutlineOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// Generates an outline shapes for contours, or shapes.
@@ -37,6 +41,83 @@ pub trait OutlineOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// A collection of `Shapes<P>` representing the outline geometry.
     /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
     fn outline_custom(&self, style: &OutlineStyle<T>, options: OverlayOptions<T>) -> Shapes<P>;
+
+    /// The same as `outline`, but checks the input first.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry,
+    /// or `FloatInputError` if a point or an offset is not finite or the bounds can not be mapped into the integer grid.
+    fn try_outline(&self, style: &OutlineStyle<T>) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `outline_custom`, but checks the input first.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    /// - `options`: Adjust custom behavior.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry, or `FloatInputError` if a point or an offset is not finite,
+    /// the bounds can not be mapped into the integer grid or the grid is too coarse for `max_error`.
+    fn try_outline_custom(
+        &self,
+        style: &OutlineStyle<T>,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `try_outline`, but snaps the geometry to the fixed grid,
+    /// so results of all operations made with the same `precision` lie on the same grid.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    /// - `precision`: The grid step and origin.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry, or `FloatInputError` if a point or an offset is not finite,
+    /// the precision is not valid or the outline is too far from the origin of the grid.
+    fn outline_with_precision(&self, style: &OutlineStyle<T>, precision: &FixedPrecision<T>) -> Result<Shapes<P>, FloatInputError>;
+
+    /// Generates an outline shapes for contours, or shapes where every edge has its own offset.
+    ///
+    /// - `offset`: Returns the offset for an edge by the path index and the edge index.
+    ///   Paths are indexed in the order of `iter_paths`, the edge `i` goes from the point `i` to the point `i + 1`.
+    ///   A positive offset moves the edge outward, away from the shape body, for outer paths and holes alike.
+    ///   Edges of the same path should have offsets of the same sign.
+    /// - `join`: The join style for corners.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry.
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    ///
+    /// ```rust
+    /// use i_overlay::mesh::outline::offset::OutlineOffset;
+    /// use i_overlay::mesh::style::LineJoin;
+    ///
+    /// let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+    /// let offsets = [[1.0, 2.0, 3.0, 4.0]];
+    ///
+    /// let shapes = path.outline_with_offsets(|p, e| offsets[p][e], &LineJoin::Miter(0.1));
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// ```
+    fn outline_with_offsets<F: Fn(usize, usize) -> T>(&self, offset: F, join: &LineJoin<T>) -> Shapes<P>;
+
+    /// Generates an outline shapes for contours, or shapes where every edge has its own offset, with optional filtering.
+    ///
+    /// - `offset`: Returns the offset for an edge by the path index and the edge index, see [`OutlineOffset::outline_with_offsets`].
+    /// - `join`: The join style for corners.
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry.
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn outline_with_offsets_custom<F: Fn(usize, usize) -> T>(
+        &self,
+        offset: F,
+        join: &LineJoin<T>,
+        options: OverlayOptions<T>,
+    ) -> Shapes<P>;
 }
 
 impl<S, P, T> OutlineOffset<P, T> for S
@@ -50,148 +131,267 @@ where
     }
 
     fn outline_custom(&self, style: &OutlineStyle<T>, options: OverlayOptions<T>) -> Shapes<P> {
-        let (points_count, paths_count) = {
-            let mut points_count = 0;
-            let mut paths_count = 0;
-            for path in self.iter_paths() {
-                points_count += path.len();
-                paths_count += 1;
-            }
-            (points_count, paths_count)
-        };
+        outline_shapes(self, style, options, GridMode::Auto).unwrap_or_default()
+    }
 
-        let join = style.join.clone().normalize();
+    #[inline]
+    fn try_outline(&self, style: &OutlineStyle<T>) -> Result<Shapes<P>, FloatInputError> {
+        self.try_outline_custom(style, Default::default(), no_error_bound())
+    }
 
-        let outer_builder = OutlineBuilder::new(-style.outer_offset, &join);
-        let inner_builder = OutlineBuilder::new(style.inner_offset, &join);
+    #[inline]
+    fn try_outline_custom(
+        &self,
+        style: &OutlineStyle<T>,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        outline_shapes(self, style, options, GridMode::Checked(max_error))
+    }
 
-        let adapter = {
-            let outer_radius = style.outer_offset;
-            let inner_radius = style.inner_offset;
+    #[inline]
+    fn outline_with_precision(&self, style: &OutlineStyle<T>, precision: &FixedPrecision<T>) -> Result<Shapes<P>, FloatInputError> {
+        outline_shapes(self, style, Default::default(), GridMode::Fixed(precision))
+    }
 
-            let outer_additional_offset = outer_builder.additional_offset(outer_radius);
-            let inner_additional_offset = inner_builder.additional_offset(inner_radius);
+    fn outline_with_offsets<F: Fn(usize, usize) -> T>(&self, offset: F, join: &LineJoin<T>) -> Shapes<P> {
+        self.outline_with_offsets_custom(offset, join, Default::default())
+    }
+
+    fn outline_with_offsets_custom<F: Fn(usize, usize) -> T>(
+        &self,
+        offset: F,
+        join: &LineJoin<T>,
+        options: OverlayOptions<T>,
+    ) -> Shapes<P> {
+        let builder = VariableOutlineBuilder::new(join.clone().normalize());
+
+        let mut max_offset = T::from_float(0.0);
+        for (path_index, path) in self.iter_paths().enumerate() {
+            for edge_index in 0..path.len() {
+                max_offset = max_offset.max(offset(path_index, edge_index).abs());
+            }
+        }
+        let additional_offset = builder.additional_offset(max_offset);
+
+        let mut radii = Vec::new();
+
+        let adapter = FloatPointAdapter::new(outline_rect(self, additional_offset));
+
+        build_outline(
+            self,
+            &adapter,
+            options,
+            |points_count| builder.capacity(points_count),
+            |path_index, path, is_outer, adapter, segments| {
+                let n = path.len();
+                radii.clear();
+                if is_outer {
+                    radii.extend((0..n).map(|i| -offset(path_index, i)));
+                } else {
+                    // the path is reversed, its edge `i` is the original edge `n - 2 - i`
+                    radii.extend((0..n).map(|i| offset(path_index, (2 * n - 2 - i) % n)));
+                }
+                builder.build(path, &radii, adapter, segments);
+            },
+        )
+    }
+}
 
-            let additional_offset = outer_additional_offset.abs() + inner_additional_offset.abs();
+// the input is checked unless the grid mode is `Auto`
+fn outline_shapes<S, P, T>(
+    source: &S,
+    style: &OutlineStyle<T>,
+    options: OverlayOptions<T>,
+    grid: GridMode<T>,
+) -> Result<Shapes<P>, FloatInputError>
+where
+    S: ShapeResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+{
+    if grid.is_checked() {
+        check_finite(0, source.iter_paths())?;
+        if !is_finite(style.outer_offset) || !is_finite(style.inner_offset) {
+            return Err(FloatInputError::NonFiniteParameter);
+        }
+    }
 
-            let mut rect =
-                FloatRect::with_iter(self.iter_paths().flatten()).unwrap_or(FloatRect::zero());
-            rect.add_offset(additional_offset);
+    let join = style.join.clone().normalize();
 
-            FloatPointAdapter::new(rect)
-            // FloatPointAdapter::with_scale(rect, 1.0) // Debug !!!
-        };
+    let outer_builder = OutlineBuilder::new(-style.outer_offset, &join);
+    let inner_builder = OutlineBuilder::new(style.inner_offset, &join);
 
-        let int_min_area = adapter.sqr_float_to_int(options.min_output_area).max(1);
+    let outer_additional_offset = outer_builder.additional_offset(style.outer_offset);
+    let inner_additional_offset = inner_builder.additional_offset(style.inner_offset);
+    let additional_offset = outer_additional_offset.abs() + inner_additional_offset.abs();
 
-        let shapes = if paths_count <= 1 {
-            // fast solution for a single path
+    let adapter = grid.adapter(outline_rect(source, additional_offset))?;
 
-            let path = if let Some(first) = self.iter_paths().next() {
-                first
+    Ok(build_outline(
+        source,
+        &adapter,
+        options,
+        |points_count| outer_builder.capacity(points_count),
+        |_, path, is_outer, adapter, segments| {
+            if is_outer {
+                outer_builder.build(path, adapter, segments);
             } else {
-                return vec![];
-            };
-
-            let area = path.unsafe_int_area(&adapter);
-            if area >= -1 {
-                // single path must be clock-wised
-                return vec![];
+                inner_builder.build(path, adapter, segments);
             }
+        },
+    ))
+}
+
+/// Returns the source bounding box extended by `additional_offset`,
+/// how far the outline can go from the source.
+fn outline_rect<S, P, T>(source: &S, additional_offset: T) -> FloatRect<T>
+where
+    S: ShapeResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    let mut rect =
+        FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+    rect.add_offset(additional_offset);
+    rect
+}
+
+/// Builds outline segments for every path, resolves self-intersections and unions the result.
+/// - `adapter`: The adapter for the bounds made by `outline_rect`.
+/// - `capacity`: Returns an estimated segments count for a path with the given points count.
+/// - `build`: Builds segments for a path. Receives the path index, the path (holes are already reversed),
+///   `true` for outer paths, the adapter and a buffer for the segments.
+fn build_outline<S, P, T, C, B>(
+    source: &S,
+    adapter: &FloatPointAdapter<P, T>,
+    options: OverlayOptions<T>,
+    capacity: C,
+    mut build: B,
+) -> Shapes<P>
+where
+    S: ShapeResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+    C: Fn(usize) -> usize,
+    B: FnMut(usize, &[P], bool, &FloatPointAdapter<P, T>, &mut Vec<Segment<ShapeCountOffset>>),
+{
+    let (points_count, paths_count) = {
+        let mut points_count = 0;
+        let mut paths_count = 0;
+        for path in source.iter_paths() {
+            points_count += path.len();
+            paths_count += 1;
+        }
+        (points_count, paths_count)
+    };
+
+    let int_min_area = adapter.sqr_float_to_int(options.min_output_area).max(1);
 
-            let capacity = outer_builder.capacity(path.len());
-            let mut segments = Vec::with_capacity(capacity);
-            outer_builder.build(path, &adapter, &mut segments);
+    let shapes = if paths_count <= 1 {
+        // fast solution for a single path
 
-            OffsetOverlay::with_segments(segments)
-                .build_graph_view_with_solver(Default::default())
-                .map(|graph| graph.extract_offset(options.output_direction, int_min_area))
-                .unwrap_or_default()
+        let path = if let Some(first) = source.iter_paths().next() {
+            first
         } else {
-            let total_capacity = outer_builder.capacity(points_count);
+            return vec![];
+        };
 
-            let mut overlay = Overlay::new_custom(total_capacity, options.int_with_adapter(&adapter), Default::default());
-            let mut offset_overlay = OffsetOverlay::new(128);
+        let area = path.unsafe_int_area(adapter);
+        if area >= -1 {
+            // single path must be clock-wised
+            return vec![];
+        }
 
-            let mut segments = Vec::new();
+        let mut segments = Vec::with_capacity(capacity(path.len()));
+        build(0, path, true, adapter, &mut segments);
 
-            for path in self.iter_paths() {
-                let area = path.unsafe_int_area(&adapter);
-                if area.abs() <= 1 {
-                    // ignore degenerate paths
-                    continue;
-                }
+        OffsetOverlay::with_segments(segments)
+            .build_graph_view_with_solver(Default::default())
+            .map(|graph| graph.extract_offset(options.output_direction, int_min_area))
+            .unwrap_or_default()
+    } else {
+        let total_capacity = capacity(points_count);
 
-                if area < 0 {
-                    let capacity = outer_builder.capacity(path.len());
-                    let additional = capacity.saturating_sub(segments.capacity());
-                    if additional > 0 {
-                        segments.reserve(additional);
-                    }
-                    segments.clear();
+        let mut overlay = Overlay::new_custom(total_capacity, options.int_with_adapter(adapter), Default::default());
+        let mut offset_overlay = OffsetOverlay::new(128);
 
-                    outer_builder.build(path, &adapter, &mut segments);
+        let mut segments = Vec::new();
 
-                    offset_overlay.clear();
-                    offset_overlay.add_segments(&segments);
+        for (path_index, path) in source.iter_paths().enumerate() {
+            let area = path.unsafe_int_area(adapter);
+            if area.abs() <= 1 {
+                // ignore degenerate paths
+                continue;
+            }
 
-                    let shapes = offset_overlay
-                        .build_graph_view_with_solver(Default::default())
-                        .map(|graph| graph.extract_offset(ContourDirection::CounterClockwise, 0))
-                        .unwrap_or_default();
+            if area < 0 {
+                let additional = capacity(path.len()).saturating_sub(segments.capacity());
+                if additional > 0 {
+                    segments.reserve(additional);
+                }
+                segments.clear();
 
-                    overlay.add_shapes(&shapes, ShapeType::Subject);
-                } else {
-                    // TODO switch to reverse
-                    let mut inverted = Vec::with_capacity(path.len());
-                    for p in path.iter().rev() {
-                        inverted.push(*p);
-                    }
+                build(path_index, path, true, adapter, &mut segments);
 
-                    let capacity = inner_builder.capacity(inverted.len());
-                    let additional = capacity.saturating_sub(segments.capacity());
-                    if additional > 0 {
-                        segments.reserve(additional);
-                    }
-                    segments.clear();
+                offset_overlay.clear();
+                offset_overlay.add_segments(&segments);
+
+                let shapes = offset_overlay
+                    .build_graph_view_with_solver(Default::default())
+                    .map(|graph| graph.extract_offset(ContourDirection::CounterClockwise, 0))
+                    .unwrap_or_default();
+
+                overlay.add_shapes(&shapes, ShapeType::Subject);
+            } else {
+                // TODO switch to reverse
+                let mut inverted = Vec::with_capacity(path.len());
+                for p in path.iter().rev() {
+                    inverted.push(*p);
+                }
 
-                    inner_builder.build(&inverted, &adapter, &mut segments);
+                let additional = capacity(inverted.len()).saturating_sub(segments.capacity());
+                if additional > 0 {
+                    segments.reserve(additional);
+                }
+                segments.clear();
 
-                    offset_overlay.clear();
-                    offset_overlay.add_segments(&segments);
+                build(path_index, &inverted, false, adapter, &mut segments);
 
-                    let mut shapes = offset_overlay
-                        .build_graph_view_with_solver(Default::default())
-                        .map(|graph| graph.extract_offset(ContourDirection::CounterClockwise, 0))
-                        .unwrap_or_default();
+                offset_overlay.clear();
+                offset_overlay.add_segments(&segments);
 
-                    for shape in shapes.iter_mut() {
-                        for path in shape.iter_mut() {
-                            path.reverse();
-                        }
-                    }
+                let mut shapes = offset_overlay
+                    .build_graph_view_with_solver(Default::default())
+                    .map(|graph| graph.extract_offset(ContourDirection::CounterClockwise, 0))
+                    .unwrap_or_default();
 
-                    overlay.add_shapes(&shapes, ShapeType::Subject);
+                for shape in shapes.iter_mut() {
+                    for path in shape.iter_mut() {
+                        path.reverse();
+                    }
                 }
+
+                overlay.add_shapes(&shapes, ShapeType::Subject);
             }
+        }
 
-            overlay.overlay(
-                OverlayRule::Subject,
-                FillRule::Positive
-            )
-        };
+        overlay.overlay(
+            OverlayRule::Subject,
+            FillRule::Positive
+        )
+    };
 
-        if options.clean_result {
-            let mut float = shapes.to_float(&adapter);
-            if options.preserve_output_collinear {
-                float.despike_contour(&adapter);
-            } else {
-                float.simplify_contour(&adapter);
-            }
-            float
+    if options.clean_result {
+        let mut float = shapes.to_float(adapter);
+        if options.preserve_output_collinear {
+            float.despike_contour(adapter);
         } else {
-            shapes.to_float(&adapter)
+            float.simplify_contour(adapter);
         }
+        float
+    } else {
+        shapes.to_float(adapter)
     }
 }
 
@@ -331,4 +531,81 @@ use crate::mesh::outline::offset::OutlineOffset;
         let path = shape.first().unwrap();
         assert_eq!(path.len(), 8);
     }
+
+    fn bounds(path: &[[f64; 2]]) -> [f64; 4] {
+        let mut b = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+        for p in path.iter() {
+            b[0] = b[0].min(p[0]);
+            b[1] = b[1].min(p[1]);
+            b[2] = b[2].max(p[0]);
+            b[3] = b[3].max(p[1]);
+        }
+        b
+    }
+
+    fn assert_bounds(path: &[[f64; 2]], expected: [f64; 4]) {
+        let b = bounds(path);
+        for (v, e) in b.iter().zip(expected.iter()) {
+            assert!((v - e).abs() < 0.001, "{:?} != {:?}", b, expected);
+        }
+    }
+
+    #[test]
+    fn test_square_variable_offsets() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let offsets = [1.0, 2.0, 3.0, 4.0];
+
+        let shapes = path.outline_with_offsets(|_, e| offsets[e], &LineJoin::Miter(0.1));
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+
+        assert_bounds(&shapes[0][0], [-4.0, -1.0, 12.0, 13.0]);
+    }
+
+    #[test]
+    fn test_square_variable_offsets_round() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let offsets = [1.0, 2.0, 3.0, 4.0];
+
+        let shapes = path.outline_with_offsets(|_, e| offsets[e], &LineJoin::Round(0.1));
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_bounds(&shapes[0][0], [-4.0, -1.0, 12.0, 13.0]);
+    }
+
+    #[test]
+    fn test_uniform_offsets() {
+        let path = [[0.0, 0.0f64], [10.0, 0.0], [5.0, 3.0], [10.0, 10.0], [0.0, 10.0]];
+        let style = OutlineStyle::new(2.0).line_join(LineJoin::Round(0.2));
+
+        let uniform = path.outline(&style);
+        let variable = path.outline_with_offsets(|_, _| 2.0, &LineJoin::Round(0.2));
+
+        assert_eq!(uniform.len(), 1);
+        assert_eq!(uniform[0].len(), 1);
+        assert_eq!(uniform[0][0].len(), variable[0][0].len());
+        for (a, b) in uniform[0][0].iter().zip(variable[0][0].iter()) {
+            assert!((a[0] - b[0]).abs() < 0.001 && (a[1] - b[1]).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_window_variable_offsets() {
+        let window = vec![
+            vec![[-10.0, -10.0], [10.0, -10.0], [10.0, 10.0], [-10.0, 10.0]],
+            vec![[-5.0, -5.0], [-5.0, 5.0], [5.0, 5.0], [5.0, -5.0]],
+        ];
+        let offsets = [[0.0, 0.0, 0.0, 0.0], [1.0, 2.0, 3.0, 4.0]];
+
+        let shapes = window.outline_with_offsets(|p, e| offsets[p][e], &LineJoin::Miter(0.1));
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+
+        assert_bounds(&shapes[0][0], [-10.0, -10.0, 10.0, 10.0]);
+        // a positive offset moves hole edges inside the hole
+        assert_bounds(&shapes[0][1], [-4.0, -1.0, 2.0, 3.0]);
+    }
 }
@@ -1,5 +1,4 @@
 use alloc::vec::Vec;
-use core::marker::PhantomData;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -14,7 +13,7 @@ pub(super) struct Section<P: FloatPointCompatible<T>, T: FloatNumber> {
     pub(super) a_top: P,
     pub(super) b_top: P,
     pub(super) dir: P,
-    _phantom: PhantomData<T>,
+    pub(super) radius: T,
 }
 
 impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
@@ -30,7 +29,7 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
             a_top,
             b_top,
             dir,
-            _phantom: Default::default(),
+            radius,
         }
     }
 }
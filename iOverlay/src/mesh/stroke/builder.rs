@@ -21,6 +21,8 @@ trait StrokeBuild<P: FloatPointCompatible<T>, T: FloatNumber> {
         segments: &mut Vec<Segment<ShapeCountOffset>>,
     );
 
+    fn build_dot(&self, point: &P, dir: &P, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>);
+
     fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize;
     fn additional_offset(&self, radius: T) -> T;
 }
@@ -52,7 +54,7 @@ impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> StrokeBuild
             }),
             LineJoin::Round(ratio) => Box::new(Builder {
                 radius,
-                join_builder: RoundJoinBuilder::new(ratio, radius),
+                join_builder: RoundJoinBuilder::new(ratio),
                 start_cap_builder,
                 end_cap_builder,
             }),
@@ -78,6 +80,12 @@ impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> StrokeBuild
         self.builder.build(path, is_closed_path, adapter, segments);
     }
 
+    /// Builds only the caps of a zero length path at `point` going in the direction `dir`.
+    #[inline]
+    pub(super) fn build_dot(&self, point: &P, dir: &P, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
+        self.builder.build_dot(point, dir, adapter, segments);
+    }
+
     #[inline]
     pub(super) fn capacity(
         &self,
@@ -111,6 +119,13 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> StrokeBui
         }
     }
 
+    fn build_dot(&self, point: &P, dir: &P, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
+        // butt caps cancel each other out
+        let section = Section::with_dir(self.radius, point, dir);
+        self.start_cap_builder.add_to_start(&section, adapter, segments);
+        self.end_cap_builder.add_to_end(&section, adapter, segments);
+    }
+
     #[inline]
     fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize {
         if is_closed_path {
@@ -192,7 +207,7 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> Builder<J
 
         // build segments only from points which are not equal in int space
         let i0 = path.len() - 1;
-        let i1 = Self::next_unique_point(i0, 0, path, adapter);
+        let i1 = next_unique_point(i0, 0, path, adapter);
         if i1 == usize::MAX { return }
 
         let start = Section::new(self.radius, &path[i0], &path[i1]);
@@ -200,30 +215,190 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> Builder<J
         segments.add_section(&s0, adapter);
 
         let mut i = i1;
-        i = Self::next_unique_point(i, i + 1, path, adapter);
+        i = next_unique_point(i, i + 1, path, adapter);
         while i != usize::MAX {
             let si = Section::new(self.radius, &s0.b, &path[i]);
             self.join_builder.add_join(&s0, &si, adapter, segments);
             segments.add_section(&si, adapter);
 
-            i = Self::next_unique_point(i, i + 1, path, adapter);
+            i = next_unique_point(i, i + 1, path, adapter);
             s0 = si;
         }
 
         self.join_builder.add_join(&s0, &start, adapter, segments);
     }
+}
 
-    #[inline]
-    fn next_unique_point(start: usize, index: usize, path: &[P], adapter: &FloatPointAdapter<P, T>) -> usize {
-        let a = adapter.float_to_int(&path[start]);
-        for (j, p) in path.iter().enumerate().skip(index) {
-            let b = adapter.float_to_int(p);
-            if a != b {
-                return j;
+pub(super) struct VariableStrokeBuilder<P: FloatPointCompatible<T>, T: FloatNumber> {
+    join: LineJoin<T>,
+    // caps for the unit radius, they are scaled by the radius at the path end
+    start_cap_builder: CapBuilder<P, T>,
+    end_cap_builder: CapBuilder<P, T>,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> VariableStrokeBuilder<P, T> {
+    pub(super) fn new(style: StrokeStyle<P, T>) -> Self {
+        let unit = T::from_float(1.0);
+        Self {
+            join: style.join.normalize(),
+            start_cap_builder: CapBuilder::new(style.start_cap.normalize(), unit),
+            end_cap_builder: CapBuilder::new(style.end_cap.normalize(), unit),
+        }
+    }
+
+    /// Builds segments for a path where every point has its own radius.
+    /// - `radii`: The half width at each point of the path.
+    pub(super) fn build(
+        &self,
+        path: &[P],
+        radii: &[T],
+        is_closed_path: bool,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        if is_closed_path {
+            self.closed_segments(path, radii, adapter, segments);
+        } else {
+            self.open_segments(path, radii, adapter, segments);
+        }
+    }
+
+    pub(super) fn capacity(&self, paths_count: usize, points_count: usize, is_closed_path: bool) -> usize {
+        let join_capacity = match &self.join {
+            LineJoin::Bevel => 2,
+            LineJoin::Miter(_) => 4,
+            LineJoin::Round(ratio) => JoinBuilder::<P, T>::capacity(&RoundJoinBuilder::new(*ratio)),
+        };
+        if is_closed_path {
+            join_capacity * points_count - 2
+        } else {
+            join_capacity * (points_count.saturating_sub(1))
+                + paths_count
+                    * (self.end_cap_builder.capacity() + self.start_cap_builder.capacity())
+        }
+    }
+
+    /// Returns the additional offset for the max radius.
+    pub(super) fn additional_offset(&self, radius: T) -> T {
+        let start_cap = self.start_cap_builder.additional_offset() * radius;
+        let end_cap = self.end_cap_builder.additional_offset() * radius;
+        let join = match &self.join {
+            LineJoin::Bevel => JoinBuilder::<P, T>::additional_offset(&BevelJoinBuilder, radius),
+            LineJoin::Miter(ratio) => JoinBuilder::<P, T>::additional_offset(&MiterJoinBuilder::new(*ratio, radius), radius),
+            LineJoin::Round(ratio) => JoinBuilder::<P, T>::additional_offset(&RoundJoinBuilder::new(*ratio), radius),
+        };
+        join.max(start_cap.max(end_cap))
+    }
+
+    fn add_join(
+        &self,
+        s0: &Section<P, T>,
+        s1: &Section<P, T>,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        // both sections have the same radius at the joint point
+        let radius = s0.b_radius;
+        match &self.join {
+            LineJoin::Bevel => BevelJoinBuilder.add_join(s0, s1, adapter, segments),
+            LineJoin::Miter(ratio) => MiterJoinBuilder::new(*ratio, radius).add_join(s0, s1, adapter, segments),
+            LineJoin::Round(ratio) => RoundJoinBuilder::new(*ratio).add_join(s0, s1, adapter, segments),
+        }
+    }
+
+    fn open_segments(
+        &self,
+        path: &[P],
+        radii: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        // build segments only from points which are not equal in int space
+
+        let n = path.len();
+        if n < 2 {
+            return
+        }
+
+        let mut ip0 = adapter.float_to_int(&path[0]);
+        let mut ip = adapter.float_to_int(&path[1]);
+        let mut j = 1;
+        while ip == ip0 {
+            j += 1;
+            if j >= n { return; }
+            ip = adapter.float_to_int(&path[j]);
+        }
+
+        let mut s0 = Section::with_radii(radii[0], radii[j], &path[0], &path[j]);
+
+        self.start_cap_builder.add_scaled_to_start(&s0, s0.a_radius, adapter, segments);
+
+        segments.add_section(&s0, adapter);
+
+        ip0 = ip;
+        j += 1;
+        'main_loop:
+        while j < n {
+            let mut p = &path[j];
+            ip = adapter.float_to_int(p);
+            while ip == ip0 {
+                j += 1;
+                if j >= n { break 'main_loop; }
+                p = &path[j];
+                ip = adapter.float_to_int(p);
             }
+            let s1 = Section::with_radii(s0.b_radius, radii[j], &s0.b, p);
+            self.add_join(&s0, &s1, adapter, segments);
+            segments.add_section(&s1, adapter);
+            s0 = s1;
+            ip0 = ip;
         }
 
-        usize::MAX
+        self.end_cap_builder.add_scaled_to_end(&s0, s0.b_radius, adapter, segments);
     }
 
-}
\ No newline at end of file
+    fn closed_segments(
+        &self,
+        path: &[P],
+        radii: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<ShapeCountOffset>>,
+    ) {
+        if path.len() < 2 { return; }
+
+        // build segments only from points which are not equal in int space
+        let i0 = path.len() - 1;
+        let i1 = next_unique_point(i0, 0, path, adapter);
+        if i1 == usize::MAX { return }
+
+        let start = Section::with_radii(radii[i0], radii[i1], &path[i0], &path[i1]);
+        let mut s0 = start.clone();
+        segments.add_section(&s0, adapter);
+
+        let mut i = i1;
+        i = next_unique_point(i, i + 1, path, adapter);
+        while i != usize::MAX {
+            let si = Section::with_radii(s0.b_radius, radii[i], &s0.b, &path[i]);
+            self.add_join(&s0, &si, adapter, segments);
+            segments.add_section(&si, adapter);
+
+            i = next_unique_point(i, i + 1, path, adapter);
+            s0 = si;
+        }
+
+        self.add_join(&s0, &start, adapter, segments);
+    }
+}
+
+#[inline]
+fn next_unique_point<P: FloatPointCompatible<T>, T: FloatNumber>(start: usize, index: usize, path: &[P], adapter: &FloatPointAdapter<P, T>) -> usize {
+    let a = adapter.float_to_int(&path[start]);
+    for (j, p) in path.iter().enumerate().skip(index) {
+        let b = adapter.float_to_int(p);
+        if a != b {
+            return j;
+        }
+    }
+
+    usize::MAX
+}
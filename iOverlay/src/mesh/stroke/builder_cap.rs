@@ -69,13 +69,24 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> CapBuilder<P, T> {
         scaled
     }
 
+    #[inline]
     pub(super) fn add_to_start(&self, section: &Section<P, T>, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
+        self.add_scaled_to_start(section, T::from_float(1.0), adapter, segments);
+    }
+
+    #[inline]
+    pub(super) fn add_to_end(&self, section: &Section<P, T>, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
+        self.add_scaled_to_end(section, T::from_float(1.0), adapter, segments);
+    }
+
+    /// Adds the start cap with template points scaled by `scale`.
+    pub(super) fn add_scaled_to_start(&self, section: &Section<P, T>, scale: T, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
         let mut a = adapter.float_to_int(&section.a_top);
         if let Some(points) = &self.points {
             let dir = P::from_xy(-section.dir.x(), -section.dir.y());
             let rotator = Rotator::with_vector(&dir);
             for p in points.iter() {
-                let r = rotator.rotate(p);
+                let r = rotator.rotate(&FloatPointMath::scale(p, scale));
                 let q = FloatPointMath::add(&r, &section.a);
                 let b = adapter.float_to_int(&q);
                 segments.push(Segment::bold_subject_ab(a, b));
@@ -86,12 +97,13 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> CapBuilder<P, T> {
         segments.push(Segment::bold_subject_ab(a, last));
     }
 
-    pub(super) fn add_to_end(&self, section: &Section<P, T>, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
+    /// Adds the end cap with template points scaled by `scale`.
+    pub(super) fn add_scaled_to_end(&self, section: &Section<P, T>, scale: T, adapter: &FloatPointAdapter<P, T>, segments: &mut Vec<Segment<ShapeCountOffset>>) {
         let mut a = adapter.float_to_int(&section.b_bot);
         if let Some(points) = &self.points {
             let rotator = Rotator::with_vector(&section.dir);
             for p in points.iter() {
-                let r = rotator.rotate(p);
+                let r = rotator.rotate(&FloatPointMath::scale(p, scale));
                 let q = FloatPointMath::add(&r, &section.b);
                 let b = adapter.float_to_int(&q);
                 segments.push(Segment::bold_subject_ab(a, b));
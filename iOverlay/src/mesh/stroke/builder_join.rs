@@ -220,12 +220,11 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
 pub(super) struct RoundJoinBuilder<T> {
     inv_ratio: T,
     average_count: usize,
-    radius: T,
     limit_dot_product: T,
 }
 
 impl<T: FloatNumber> RoundJoinBuilder<T> {
-    pub(super) fn new(ratio: T, radius: T) -> Self {
+    pub(super) fn new(ratio: T) -> Self {
         // ratio = A / R
         let fixed_ratio = ratio.min(T::from_float(0.25 * PI));
         let limit_dot_product = fixed_ratio.cos();
@@ -233,7 +232,6 @@ impl<T: FloatNumber> RoundJoinBuilder<T> {
         Self {
             inv_ratio: T::from_float(1.0) / fixed_ratio,
             average_count,
-            radius,
             limit_dot_product,
         }
     }
@@ -269,12 +267,14 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for RoundJoin
         };
         let rotator = Rotator::<T>::with_angle(-delta_angle);
 
+        // both sections have the same radius at the joint point
+        let radius = s0.b_radius;
         let center = s0.b;
         let mut v = dir;
         let mut a = adapter.float_to_int(&start);
         for _ in 1..n {
             v = rotator.rotate(&v);
-            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, self.radius));
+            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, radius));
 
             let b = adapter.float_to_int(&p);
             if a != b {
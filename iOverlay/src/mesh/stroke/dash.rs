@@ -0,0 +1,252 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::vector::FloatPointMath;
+use crate::float::error::FloatInputError;
+
+// a path can not be split into more dashes
+const MAX_DASH_COUNT: f64 = 1_000_000.0;
+
+pub(super) struct DashPattern<T> {
+    // even indices are dashes, odd indices are gaps
+    pattern: Vec<T>,
+    total: f64,
+    start_index: usize,
+    start_rest: T,
+}
+
+impl<T: FloatNumber> DashPattern<T> {
+    /// Creates a pattern, returns `None` for an empty pattern, which draws a solid line.
+    /// - `min_total`: The pattern can not be drawn if its total length is smaller, e.g. less than the grid step.
+    pub(super) fn new(dash_array: &[T], dash_phase: T, min_total: f64) -> Result<Option<Self>, FloatInputError> {
+        if dash_array.is_empty() {
+            return Ok(None);
+        }
+        let zero = T::from_float(0.0);
+        let is_valid = dash_phase.to_f64().is_finite() && dash_array.iter().all(|&d| d >= zero && d.to_f64().is_finite());
+        if !is_valid {
+            return Err(FloatInputError::InvalidDashPattern);
+        }
+
+        let mut pattern = dash_array.to_vec();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(dash_array);
+        }
+
+        let total = pattern.iter().fold(0.0, |s, d| s + d.to_f64());
+        if total <= 0.0 || !total.is_finite() {
+            return Err(FloatInputError::InvalidDashPattern);
+        }
+        if total < min_total {
+            return Err(FloatInputError::DashPatternTooFine);
+        }
+
+        let mut offset = dash_phase.to_f64() % total;
+        if offset < 0.0 {
+            offset += total;
+        }
+
+        // a zero length dash at the phase is kept, it is drawn as a dot
+        let mut start_index = 0;
+        while offset > 0.0 && offset >= pattern[start_index].to_f64() {
+            offset -= pattern[start_index].to_f64();
+            start_index = (start_index + 1) % pattern.len();
+        }
+        let start_rest = pattern[start_index] - T::from_float(offset);
+
+        Ok(Some(Self { pattern, total, start_index, start_rest }))
+    }
+
+    /// Splits a path into dashes. A zero length dash is added to `dots` as a point and the direction of the path there.
+    /// Returns `false` if a closed path is covered by a single dash, so it must be drawn solid,
+    /// or `DashPatternTooFine` if the path is too long for the pattern. Nothing is added in both cases.
+    pub(super) fn split<P: FloatPointCompatible<T>>(
+        &self,
+        path: &[P],
+        is_closed_path: bool,
+        dashes: &mut Vec<Vec<P>>,
+        dots: &mut Vec<(P, P)>,
+    ) -> Result<bool, FloatInputError> {
+        let n = path.len();
+        if n < 2 {
+            return Ok(true);
+        }
+
+        let edges_count = if is_closed_path { n } else { n - 1 };
+        let length = (0..edges_count).fold(0.0, |s, i| {
+            s + FloatPointMath::length(&FloatPointMath::sub(&path[(i + 1) % n], &path[i])).to_f64()
+        });
+        if length > MAX_DASH_COUNT * self.total {
+            return Err(FloatInputError::DashPatternTooFine);
+        }
+
+        let first_dash = dashes.len();
+        // a path starting with a dot is joined with nothing
+        let starts_with_dash = is_dash(self.start_index) && self.start_rest > T::from_float(0.0);
+        let mut is_split = false;
+
+        let mut index = self.start_index;
+        let mut rest = self.start_rest;
+        let mut current = if starts_with_dash { vec![path[0]] } else { Vec::new() };
+
+        for i in 0..edges_count {
+            let a = path[i];
+            let b = path[(i + 1) % n];
+            let len = FloatPointMath::length(&FloatPointMath::sub(&b, &a));
+            if len <= T::from_float(0.0) {
+                continue;
+            }
+
+            let mut pos = T::from_float(0.0);
+            loop {
+                let left = len - pos;
+                if rest > left {
+                    rest = rest - left;
+                    break;
+                }
+
+                pos = pos + rest;
+                let t = pos / len;
+                let p = P::from_xy(a.x() + t * (b.x() - a.x()), a.y() + t * (b.y() - a.y()));
+
+                if is_dash(index) && self.pattern[index] == T::from_float(0.0) {
+                    // a dot, it gets only caps
+                    let dir = FloatPointMath::scale(&FloatPointMath::sub(&b, &a), T::from_float(1.0) / len);
+                    dots.push((p, dir));
+                    current.clear();
+                    is_split = true;
+                } else if is_dash(index) {
+                    // the dash ends
+                    current.push(p);
+                    if current.len() >= 2 {
+                        dashes.push(mem::take(&mut current));
+                    } else {
+                        current.clear();
+                    }
+                    is_split = true;
+                } else {
+                    // the gap ends
+                    current.clear();
+                    current.push(p);
+                }
+
+                index = (index + 1) % self.pattern.len();
+                rest = self.pattern[index];
+            }
+
+            if is_dash(index) {
+                current.push(b);
+            }
+        }
+
+        if !is_closed_path {
+            if is_dash(index) && current.len() >= 2 {
+                dashes.push(current);
+            }
+            return Ok(true);
+        }
+
+        if !is_split && starts_with_dash {
+            // the whole path is a single dash
+            return Ok(false);
+        }
+
+        if is_dash(index) && current.len() >= 2 {
+            if starts_with_dash && first_dash < dashes.len() {
+                // the last dash goes through the start point, join it with the first one
+                let first = dashes.remove(first_dash);
+                current.extend(first.into_iter().skip(1));
+            }
+            dashes.push(current);
+        }
+
+        Ok(true)
+    }
+}
+
+#[inline(always)]
+fn is_dash(index: usize) -> bool {
+    index & 1 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use crate::float::error::FloatInputError;
+    use crate::mesh::stroke::dash::DashPattern;
+
+    #[test]
+    fn test_open() {
+        let pattern = DashPattern::new(&[2.0, 1.0], 0.0, 0.0).unwrap().unwrap();
+        let mut dashes = Vec::new();
+        pattern.split(&[[0.0, 0.0], [4.0, 0.0], [4.0, 3.0]], false, &mut dashes, &mut Vec::new()).unwrap();
+
+        assert_eq!(dashes.len(), 3);
+        assert_eq!(dashes[0], [[0.0, 0.0], [2.0, 0.0]]);
+        assert_eq!(dashes[1], [[3.0, 0.0], [4.0, 0.0], [4.0, 1.0]]);
+        assert_eq!(dashes[2], [[4.0, 2.0], [4.0, 3.0]]);
+    }
+
+    #[test]
+    fn test_phase() {
+        let pattern = DashPattern::new(&[2.0], 3.0, 0.0).unwrap().unwrap();
+        let mut dashes = Vec::new();
+        pattern.split(&[[0.0, 0.0], [6.0, 0.0]], false, &mut dashes, &mut Vec::new()).unwrap();
+
+        assert_eq!(dashes.len(), 2);
+        assert_eq!(dashes[0], [[1.0, 0.0], [3.0, 0.0]]);
+        assert_eq!(dashes[1], [[5.0, 0.0], [6.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_closed() {
+        let pattern = DashPattern::new(&[3.0, 2.0], 1.0, 0.0).unwrap().unwrap();
+        let mut dashes = Vec::new();
+        let is_split = pattern.split(&[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]], true, &mut dashes, &mut Vec::new()).unwrap();
+
+        assert!(is_split);
+        assert_eq!(dashes.len(), 3);
+        // the dash through the start point is merged
+        assert_eq!(dashes[2], [[0.0, 2.0], [0.0, 0.0], [2.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_solid() {
+        assert!(DashPattern::<f64>::new(&[], 0.0, 0.0).unwrap().is_none());
+        assert_eq!(DashPattern::new(&[0.0, 0.0], 0.0, 0.0).err(), Some(FloatInputError::InvalidDashPattern));
+        assert_eq!(DashPattern::new(&[1.0, -1.0], 0.0, 0.0).err(), Some(FloatInputError::InvalidDashPattern));
+        assert_eq!(DashPattern::new(&[1.0, 1.0], f64::NAN, 0.0).err(), Some(FloatInputError::InvalidDashPattern));
+
+        let pattern = DashPattern::new(&[100.0, 1.0], 0.0, 0.0).unwrap().unwrap();
+        let mut dashes: Vec<Vec<[f64; 2]>> = Vec::new();
+        let is_split = pattern.split(&[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0]], true, &mut dashes, &mut Vec::new()).unwrap();
+        assert!(!is_split);
+        assert!(dashes.is_empty());
+
+        // the pattern is finer than the grid
+        assert_eq!(DashPattern::new(&[1e-9, 1e-9], 0.0, 1e-6).err(), Some(FloatInputError::DashPatternTooFine));
+    }
+
+    #[test]
+    fn test_dots() {
+        let pattern = DashPattern::new(&[0.0, 2.0], 0.0, 0.0).unwrap().unwrap();
+        let mut dashes: Vec<Vec<[f64; 2]>> = Vec::new();
+        let mut dots = Vec::new();
+        pattern.split(&[[0.0, 0.0], [3.0, 0.0], [3.0, 3.0]], false, &mut dashes, &mut dots).unwrap();
+
+        assert!(dashes.is_empty());
+        assert_eq!(dots, [([0.0, 0.0], [1.0, 0.0]), ([2.0, 0.0], [1.0, 0.0]), ([3.0, 1.0], [0.0, 1.0]), ([3.0, 3.0], [0.0, 1.0])]);
+    }
+
+    #[test]
+    fn test_too_many_dashes() {
+        let pattern = DashPattern::new(&[1e-9, 1e-9], 0.0, 0.0).unwrap().unwrap();
+        let mut dashes: Vec<Vec<[f64; 2]>> = Vec::new();
+        let result = pattern.split(&[[0.0, 0.0], [1000.0, 0.0]], false, &mut dashes, &mut Vec::new());
+
+        assert_eq!(result, Err(FloatInputError::DashPatternTooFine));
+        assert!(dashes.is_empty());
+    }
+}
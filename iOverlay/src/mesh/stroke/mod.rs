@@ -2,4 +2,5 @@ pub mod offset;
 mod builder_join;
 mod section;
 mod builder;
-mod builder_cap;
\ No newline at end of file
+mod builder_cap;
+mod dash;
\ No newline at end of file
@@ -1,9 +1,12 @@
 use crate::i_shape::source::resource::ShapeResource;
 use crate::mesh::stroke::offset::vec::Vec;
 use alloc::vec;
+use crate::float::error::{check_finite, is_finite, no_error_bound, FloatInputError};
 use crate::float::overlay::OverlayOptions;
-use crate::mesh::stroke::builder::StrokeBuilder;
-use crate::mesh::style::StrokeStyle;
+use crate::float::precision::{FixedPrecision, GridMode};
+use crate::mesh::stroke::builder::{StrokeBuilder, VariableStrokeBuilder};
+use crate::mesh::stroke::dash::DashPattern;
+use crate::mesh::style::{DashStyle, StrokeStyle};
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -13,6 +16,8 @@ use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::despike::DeSpikeContour;
 use i_shape::float::simple::SimplifyContour;
 use crate::mesh::overlay::OffsetOverlay;
+use crate::segm::offset::ShapeCountOffset;
+use crate::segm::segment::Segment;
 
 pub trait StrokeOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// Generates a stroke shapes for paths, contours, or shapes.
@@ -42,6 +47,181 @@ pub trait StrokeOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
         is_closed_path: bool,
         options: OverlayOptions<T>,
     ) -> Shapes<P>;
+
+    /// The same as `stroke`, but checks the input first.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry,
+    /// or `FloatInputError` if a point or the width is not finite or the bounds can not be mapped into the integer grid.
+    fn try_stroke(&self, style: StrokeStyle<P, T>, is_closed_path: bool) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `stroke_custom`, but checks the input first.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `options`: Adjust custom behavior.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry, or `FloatInputError` if a point or the width is not finite,
+    /// the bounds can not be mapped into the integer grid or the grid is too coarse for `max_error`.
+    fn try_stroke_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `try_stroke`, but snaps the geometry to the fixed grid,
+    /// so results of all operations made with the same `precision` lie on the same grid.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `precision`: The grid step and origin.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry, or `FloatInputError` if a point or the width is not finite,
+    /// the precision is not valid or the stroke is too far from the origin of the grid.
+    fn stroke_with_precision(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// Generates a dashed stroke shapes for paths, contours, or shapes.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    ///   Every dash gets caps at both ends and joins inside.
+    /// - `dash`: Defines the dash pattern. A pattern that can not be drawn gives a solid line,
+    ///   use `try_stroke_dashed` to get the reason instead.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry.
+    ///
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    ///
+    /// ```rust
+    /// use i_overlay::mesh::stroke::offset::StrokeOffset;
+    /// use i_overlay::mesh::style::{DashStyle, StrokeStyle};
+    ///
+    /// let path = [[0.0, 0.0], [10.0, 0.0]];
+    /// let dash = DashStyle::new(vec![3.0, 1.0]);
+    /// let shapes = path.stroke_dashed(StrokeStyle::new(1.0), &dash, false);
+    ///
+    /// assert_eq!(shapes.len(), 3);
+    /// ```
+    fn stroke_dashed(&self, style: StrokeStyle<P, T>, dash: &DashStyle<T>, is_closed_path: bool) -> Shapes<P>;
+
+    /// Generates a dashed stroke shapes for paths, contours, or shapes with optional filtering and scaling.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `dash`: Defines the dash pattern. A pattern that can not be drawn gives a solid line.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry.
+    ///
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn stroke_dashed_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+    ) -> Shapes<P>;
+
+    /// The same as `stroke_dashed`, but checks the input first.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `dash`: Defines the dash pattern.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry, or `FloatInputError` if a point or the width is not finite,
+    /// the bounds can not be mapped into the integer grid or the dash pattern can not be drawn.
+    fn try_stroke_dashed(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// The same as `stroke_dashed_custom`, but checks the input first.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `dash`: Defines the dash pattern.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `options`: Adjust custom behavior.
+    /// - `max_error`: The maximum allowed distance between a point and its position on the integer grid.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry, or `FloatInputError` if a point or the width is not finite,
+    /// the bounds can not be mapped into the integer grid, the grid is too coarse for `max_error` or the dash pattern can not be drawn.
+    fn try_stroke_dashed_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError>;
+
+    /// Generates a stroke shapes for paths, contours, or shapes where the width changes along the path.
+    ///
+    /// - `width`: Returns the stroke width at a point by the path index and the point index.
+    ///   Paths are indexed in the order of `iter_paths`. The width is interpolated linearly between points.
+    /// - `style`: Defines line caps and joins, `style.width` is ignored.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry.
+    ///
+    /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    ///
+    /// ```rust
+    /// use i_overlay::mesh::stroke::offset::StrokeOffset;
+    /// use i_overlay::mesh::style::{LineCap, StrokeStyle};
+    ///
+    /// let path = [[0.0, 0.0], [10.0, 0.0], [20.0, 5.0]];
+    /// let widths = [[1.0, 2.0, 4.0]];
+    ///
+    /// let style = StrokeStyle::default().end_cap(LineCap::Round(0.1));
+    /// let shapes = path.stroke_with_widths(|p, i| widths[p][i], style, false);
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// ```
+    fn stroke_with_widths<F: Fn(usize, usize) -> T>(
+        &self,
+        width: F,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+    ) -> Shapes<P>;
+
+    /// Generates a stroke shapes for paths, contours, or shapes where the width changes along the path, with optional filtering.
+    ///
+    /// - `width`: Returns the stroke width at a point by the path index and the point index, see [`StrokeOffset::stroke_with_widths`].
+    /// - `style`: Defines line caps and joins, `style.width` is ignored.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `options`: Adjust custom behavior.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the stroke geometry.
+    ///
+    /// Note: Outer boundary paths have a **main_direction** order, and holes have an opposite to **main_direction** order.
+    fn stroke_with_widths_custom<F: Fn(usize, usize) -> T>(
+        &self,
+        width: F,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+    ) -> Shapes<P>;
 }
 
 impl<S, P, T> StrokeOffset<P, T> for S
@@ -59,20 +239,106 @@ where
         style: StrokeStyle<P, T>,
         is_closed_path: bool,
         options: OverlayOptions<T>,
+    ) -> Shapes<P> {
+        stroke_shapes(self, style, None, is_closed_path, options, GridMode::Auto).unwrap_or_default()
+    }
+
+    #[inline]
+    fn try_stroke(&self, style: StrokeStyle<P, T>, is_closed_path: bool) -> Result<Shapes<P>, FloatInputError> {
+        self.try_stroke_custom(style, is_closed_path, Default::default(), no_error_bound())
+    }
+
+    #[inline]
+    fn try_stroke_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        stroke_shapes(self, style, None, is_closed_path, options, GridMode::Checked(max_error))
+    }
+
+    #[inline]
+    fn stroke_with_precision(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        precision: &FixedPrecision<T>,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        stroke_shapes(self, style, None, is_closed_path, Default::default(), GridMode::Fixed(precision))
+    }
+
+    #[inline]
+    fn stroke_dashed(&self, style: StrokeStyle<P, T>, dash: &DashStyle<T>, is_closed_path: bool) -> Shapes<P> {
+        self.stroke_dashed_custom(style, dash, is_closed_path, Default::default())
+    }
+
+    fn stroke_dashed_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+    ) -> Shapes<P> {
+        stroke_shapes(self, style, Some(dash), is_closed_path, options, GridMode::Auto).unwrap_or_default()
+    }
+
+    #[inline]
+    fn try_stroke_dashed(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        self.try_stroke_dashed_custom(style, dash, is_closed_path, Default::default(), no_error_bound())
+    }
+
+    #[inline]
+    fn try_stroke_dashed_custom(
+        &self,
+        style: StrokeStyle<P, T>,
+        dash: &DashStyle<T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
+        max_error: T,
+    ) -> Result<Shapes<P>, FloatInputError> {
+        stroke_shapes(self, style, Some(dash), is_closed_path, options, GridMode::Checked(max_error))
+    }
+
+    fn stroke_with_widths<F: Fn(usize, usize) -> T>(
+        &self,
+        width: F,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+    ) -> Shapes<P> {
+        self.stroke_with_widths_custom(width, style, is_closed_path, Default::default())
+    }
+
+    fn stroke_with_widths_custom<F: Fn(usize, usize) -> T>(
+        &self,
+        width: F,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        options: OverlayOptions<T>,
     ) -> Shapes<P> {
         let mut paths_count = 0;
         let mut points_count = 0;
-        for path in self.iter_paths() {
+        let mut max_width = T::from_float(0.0);
+        for (path_index, path) in self.iter_paths().enumerate() {
             paths_count += 1;
             points_count += path.len();
+            for i in 0..path.len() {
+                max_width = max_width.max(width(path_index, i));
+            }
         }
 
         if paths_count == 0 {
             return vec![];
         }
 
-        let r = T::from_float(0.5 * style.width.to_f64());
-        let builder = StrokeBuilder::new(style);
+        let r = T::from_float(0.5 * max_width.to_f64());
+        let builder = VariableStrokeBuilder::new(style);
         let a = builder.additional_offset(r);
 
         let mut rect =
@@ -89,35 +355,141 @@ where
         let capacity = builder.capacity(paths_count, points_count, is_closed_path);
         let mut segments = Vec::with_capacity(capacity);
 
-        for path in self.iter_paths() {
-            builder.build(path, is_closed_path, &adapter, &mut segments);
+        let half = T::from_float(0.5);
+        let zero = T::from_float(0.0);
+        let mut radii = Vec::new();
+        for (path_index, path) in self.iter_paths().enumerate() {
+            radii.clear();
+            radii.extend((0..path.len()).map(|i| half * width(path_index, i).max(zero)));
+            builder.build(path, &radii, is_closed_path, &adapter, &mut segments);
+        }
+
+        segments_to_shapes(segments, &adapter, options)
+    }
+}
+
+// the input is checked unless the grid mode is `Auto`, without checks a dash pattern that can not be drawn gives a solid line
+fn stroke_shapes<S, P, T>(
+    source: &S,
+    style: StrokeStyle<P, T>,
+    dash: Option<&DashStyle<T>>,
+    is_closed_path: bool,
+    options: OverlayOptions<T>,
+    grid: GridMode<T>,
+) -> Result<Shapes<P>, FloatInputError>
+where
+    S: ShapeResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+{
+    if grid.is_checked() {
+        check_finite(0, source.iter_paths())?;
+        if !is_finite(style.width) {
+            return Err(FloatInputError::NonFiniteParameter);
         }
+    }
 
-        let min_area = adapter.sqr_float_to_int(options.min_output_area);
-        let shapes = OffsetOverlay::with_segments(segments)
-            .build_graph_view_with_solver(Default::default())
-            .map(|graph| graph.extract_offset(options.output_direction, min_area))
-            .unwrap_or_default();
+    let mut paths_count = 0;
+    let mut points_count = 0;
+    for path in source.iter_paths() {
+        paths_count += 1;
+        points_count += path.len();
+    }
 
-        let mut float = shapes.to_float(&adapter);
+    if paths_count == 0 {
+        return Ok(vec![]);
+    }
 
-        if options.clean_result {
-            if options.preserve_output_collinear {
-                float.despike_contour(&adapter);
-            } else {
-                float.simplify_contour(&adapter);
-            }
-        };
+    let r = T::from_float(0.5 * style.width.to_f64());
+    let builder = StrokeBuilder::new(style);
+    let a = builder.additional_offset(r);
 
-        float
+    let mut rect =
+        FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+    rect.add_offset(a);
+    let adapter = grid.adapter(rect)?;
+
+    let ir = adapter.len_float_to_int(r).abs();
+    if ir <= 1 {
+        // offset is too small
+        return Ok(vec![]);
     }
+
+    let capacity = builder.capacity(paths_count, points_count, is_closed_path);
+    let mut segments = Vec::with_capacity(capacity);
+
+    let pattern = if let Some(dash) = dash {
+        // a pattern finer than the grid step can not be drawn
+        let min_total = 1.0 / adapter.dir_scale.to_f64();
+        match DashPattern::new(&dash.dash_array, dash.dash_phase, min_total) {
+            Ok(pattern) => pattern,
+            Err(error) if grid.is_checked() => return Err(error),
+            Err(_) => None,
+        }
+    } else {
+        None
+    };
+
+    if let Some(pattern) = pattern {
+        // all dashes go to the same overlay
+        let mut dashes = Vec::new();
+        let mut dots = Vec::new();
+        for path in source.iter_paths() {
+            dashes.clear();
+            dots.clear();
+            match pattern.split(path, is_closed_path, &mut dashes, &mut dots) {
+                Ok(true) => {
+                    for dash_path in dashes.iter() {
+                        builder.build(dash_path, false, &adapter, &mut segments);
+                    }
+                    for (point, dir) in dots.iter() {
+                        builder.build_dot(point, dir, &adapter, &mut segments);
+                    }
+                }
+                Err(error) if grid.is_checked() => return Err(error),
+                _ => builder.build(path, is_closed_path, &adapter, &mut segments),
+            }
+        }
+    } else {
+        for path in source.iter_paths() {
+            builder.build(path, is_closed_path, &adapter, &mut segments);
+        }
+    }
+
+    Ok(segments_to_shapes(segments, &adapter, options))
+}
+
+fn segments_to_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(
+    segments: Vec<Segment<ShapeCountOffset>>,
+    adapter: &FloatPointAdapter<P, T>,
+    options: OverlayOptions<T>,
+) -> Shapes<P> {
+    let min_area = adapter.sqr_float_to_int(options.min_output_area);
+    let shapes = OffsetOverlay::with_segments(segments)
+        .build_graph_view_with_solver(Default::default())
+        .map(|graph| graph.extract_offset(options.output_direction, min_area))
+        .unwrap_or_default();
+
+    let mut float = shapes.to_float(adapter);
+
+    if options.clean_result {
+        if options.preserve_output_collinear {
+            float.despike_contour(adapter);
+        } else {
+            float.simplify_contour(adapter);
+        }
+    };
+
+    float
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
     use alloc::vec::Vec;
     use crate::mesh::stroke::offset::StrokeOffset;
-    use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
+    use crate::float::error::FloatInputError;
+    use crate::mesh::style::{DashStyle, LineCap, LineJoin, StrokeStyle};
     use core::f32::consts::PI;
 
     #[test]
@@ -287,4 +659,171 @@ mod tests {
 
         assert_eq!(shapes.len(), 0);
     }
+
+    fn area(path: &[[f64; 2]]) -> f64 {
+        let mut area = 0.0;
+        let mut a = path[path.len() - 1];
+        for &b in path.iter() {
+            area += a[0] * b[1] - a[1] * b[0];
+            a = b;
+        }
+        0.5 * area
+    }
+
+    #[test]
+    fn test_tapered_line() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        let widths = [2.0, 6.0];
+
+        let shapes = path.stroke_with_widths(|_, i| widths[i], StrokeStyle::default(), false);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+
+        let contour = &shapes[0][0];
+        assert_eq!(contour.len(), 4);
+        assert!((area(contour) - 40.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_tapered_round_cap() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+        let widths = [2.0, 4.0];
+
+        let style = StrokeStyle::default().end_cap(LineCap::Round(0.1));
+        let shapes = path.stroke_with_widths(|_, i| widths[i], style, false);
+
+        assert_eq!(shapes.len(), 1);
+
+        let max_x = shapes[0][0].iter().fold(f64::MIN, |m, p| m.max(p[0]));
+        assert!((max_x - 12.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_uniform_widths() {
+        let path = [[-10.0, 0.0f64], [0.0, 0.0], [0.0, 10.0], [7.0, 3.0]];
+
+        let style = StrokeStyle::new(2.0)
+            .line_join(LineJoin::Round(0.2))
+            .start_cap(LineCap::Round(0.2))
+            .end_cap(LineCap::Square);
+        let uniform = path.stroke(style, false);
+
+        let style = StrokeStyle::default()
+            .line_join(LineJoin::Round(0.2))
+            .start_cap(LineCap::Round(0.2))
+            .end_cap(LineCap::Square);
+        let variable = path.stroke_with_widths(|_, _| 2.0, style, false);
+
+        assert_eq!(uniform.len(), 1);
+        assert_eq!(uniform[0].len(), variable[0].len());
+        assert_eq!(uniform[0][0].len(), variable[0][0].len());
+        for (a, b) in uniform[0][0].iter().zip(variable[0][0].iter()) {
+            assert!((a[0] - b[0]).abs() < 0.001 && (a[1] - b[1]).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_variable_closed() {
+        let path = [[-5.0, -5.0], [-5.0, 5.0], [5.0, 5.0], [5.0, -5.0]];
+        let widths = [1.0, 2.0, 3.0, 2.0];
+
+        let style = StrokeStyle::default().line_join(LineJoin::Miter(0.1));
+        let shapes = path.stroke_with_widths(|_, i| widths[i], style, true);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+    }
+
+    #[test]
+    fn test_dash() {
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+
+        let dash = DashStyle::new(vec![3.0, 2.0]);
+        let shapes = path.stroke_dashed(StrokeStyle::new(1.0), &dash, false);
+
+        // dashes: [0, 3], [5, 8], [10, 13] through the corner, [15, 18]
+        assert_eq!(shapes.len(), 4);
+        let total_area: f64 = shapes.iter().map(|shape| area(&shape[0]).abs()).sum();
+        assert!((total_area - 12.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_dash_round_caps() {
+        let path = [[0.0, 0.0], [10.0, 0.0]];
+
+        let style = StrokeStyle::new(1.0)
+            .start_cap(LineCap::Round(0.2))
+            .end_cap(LineCap::Round(0.2));
+        let dash = DashStyle::new(vec![2.0, 3.0]).phase(1.0);
+        let shapes = path.stroke_dashed(style, &dash, false);
+
+        assert_eq!(shapes.len(), 3);
+        for shape in shapes.iter() {
+            let min_x = shape[0].iter().fold(f64::MAX, |m, p| m.min(p[0]));
+            let max_x = shape[0].iter().fold(f64::MIN, |m, p| m.max(p[0]));
+            // caps stick out of every dash
+            assert!(max_x - min_x > 0.9);
+            assert!(min_x > -0.6);
+        }
+    }
+
+    #[test]
+    fn test_dash_closed() {
+        let path = [[-5.0, -5.0], [5.0, -5.0], [5.0, 5.0], [-5.0, 5.0]];
+
+        let style = StrokeStyle::new(1.0).line_join(LineJoin::Miter(0.1));
+        let dash = DashStyle::new(vec![4.0, 1.0]).phase(2.0);
+        let shapes = path.stroke_dashed(style, &dash, true);
+
+        // 8 dashes, the dash through the start point is not split
+        assert_eq!(shapes.len(), 8);
+
+        let dash = DashStyle::new(vec![100.0, 1.0]);
+        let shapes = path.stroke_dashed(StrokeStyle::new(1.0), &dash, true);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+    }
+
+    #[test]
+    fn test_dash_dots() {
+        let path = [[0.0, 0.0], [20.0, 0.0]];
+
+        let style = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Round(0.2))
+            .end_cap(LineCap::Round(0.2));
+        let dash = DashStyle::new(vec![0.0, 10.0]);
+        let shapes = path.stroke_dashed(style, &dash, false);
+
+        // a round dot at 0, 10 and 20
+        assert_eq!(shapes.len(), 3);
+        for shape in shapes.iter() {
+            let a = area(&shape[0]).abs();
+            assert!(a > 2.5 && a < 3.2);
+        }
+
+        // butt caps draw nothing
+        assert!(path.stroke_dashed(StrokeStyle::new(2.0), &dash, false).is_empty());
+    }
+
+    #[test]
+    fn test_dash_too_fine() {
+        let path = [[0.0, 0.0], [1000.0, 0.0]];
+
+        // the pattern can not be resolved, the line is solid
+        let dash = DashStyle::new(vec![1e-9, 1e-9]);
+        let shapes = path.stroke_dashed(StrokeStyle::new(2.0), &dash, false);
+
+        assert_eq!(shapes.len(), 1);
+        assert!((area(&shapes[0][0]).abs() - 2000.0).abs() < 1.0);
+
+        // the checked version reports it
+        let result = path.try_stroke_dashed(StrokeStyle::new(2.0), &dash, false);
+        assert_eq!(result, Err(FloatInputError::DashPatternTooFine));
+
+        let dash = DashStyle::new(vec![1.0, -1.0]);
+        let result = path.try_stroke_dashed(StrokeStyle::new(2.0), &dash, false);
+        assert_eq!(result, Err(FloatInputError::InvalidDashPattern));
+    }
 }
@@ -1,5 +1,4 @@
 use alloc::vec::Vec;
-use core::marker::PhantomData;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -17,19 +16,45 @@ pub(super) struct Section<P: FloatPointCompatible<T>, T: FloatNumber> {
     pub(super) a_bot: P,
     pub(super) b_bot: P,
     pub(super) dir: P,
-    _phantom: PhantomData<T>,
+    pub(super) a_radius: T,
+    pub(super) b_radius: T,
 }
 
 impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
     pub(crate) fn new(radius: T, a: &P, b: &P) -> Self {
+        Self::with_radii(radius, radius, a, b)
+    }
+
+    /// Creates a zero length section at `p`, the direction is the unit vector `dir`.
+    pub(crate) fn with_dir(radius: T, p: &P, dir: &P) -> Self {
+        let t = Math::ortho_and_scale(dir, radius);
+        let top = FloatPointMath::add(p, &t);
+        let bot = FloatPointMath::sub(p, &t);
+
+        Section {
+            a: *p,
+            b: *p,
+            a_top: top,
+            b_top: top,
+            a_bot: bot,
+            b_bot: bot,
+            dir: *dir,
+            a_radius: radius,
+            b_radius: radius,
+        }
+    }
+
+    /// Creates a section with the half width changing linearly from `a_radius` at `a` to `b_radius` at `b`.
+    pub(crate) fn with_radii(a_radius: T, b_radius: T, a: &P, b: &P) -> Self {
         let dir = Math::normal(b, a);
-        let t = Math::ortho_and_scale(&dir, radius);
+        let ta = Math::ortho_and_scale(&dir, a_radius);
+        let tb = Math::ortho_and_scale(&dir, b_radius);
 
-        let a_top = FloatPointMath::add(a, &t);
-        let a_bot = FloatPointMath::sub(a, &t);
+        let a_top = FloatPointMath::add(a, &ta);
+        let a_bot = FloatPointMath::sub(a, &ta);
 
-        let b_top = FloatPointMath::add(b, &t);
-        let b_bot = FloatPointMath::sub(b, &t);
+        let b_top = FloatPointMath::add(b, &tb);
+        let b_bot = FloatPointMath::sub(b, &tb);
 
         Section {
             a: *a,
@@ -39,7 +64,8 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
             a_bot,
             b_bot,
             dir,
-            _phantom: Default::default(),
+            a_radius,
+            b_radius,
         }
     }
 }
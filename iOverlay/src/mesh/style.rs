@@ -45,6 +45,16 @@ pub struct StrokeStyle<P: FloatPointCompatible<T>, T: FloatNumber> {
     pub join: LineJoin<T>,
 }
 
+/// Defines the dash pattern of a stroke, see `StrokeOffset::stroke_dashed`.
+#[derive(Debug, Clone)]
+pub struct DashStyle<T: FloatNumber> {
+    /// Alternating lengths of dashes and gaps, starting with a dash. An empty array draws a solid line.
+    /// An array of odd length is repeated to get an even one. A zero length dash is drawn as a dot made of its caps.
+    pub dash_array: Vec<T>,
+    /// The distance into the dash pattern at which the stroke starts.
+    pub dash_phase: T,
+}
+
 /// Defines the outline style for offsetting shapes.
 #[derive(Debug)]
 pub struct OutlineStyle<T: FloatNumber> {
@@ -163,4 +173,18 @@ impl<T: FloatNumber> Default for OutlineStyle<T> {
             join: LineJoin::Bevel
         }
     }
-}
\ No newline at end of file
+}
+
+impl<T: FloatNumber> DashStyle<T> {
+    /// Creates a new `DashStyle` that starts at the beginning of the pattern.
+    /// - `dash_array`: Alternating lengths of dashes and gaps, starting with a dash.
+    pub fn new(dash_array: Vec<T>) -> Self {
+        Self { dash_array, dash_phase: T::from_float(0.0) }
+    }
+
+    /// Sets the distance into the pattern at which the stroke starts.
+    pub fn phase(mut self, dash_phase: T) -> Self {
+        self.dash_phase = dash_phase;
+        self
+    }
+}
@@ -5,27 +5,47 @@ use crate::segm::winding::WindingCount;
 use alloc::vec::Vec;
 use i_float::int::point::IntPoint;
 
-pub(crate) trait BuildSegments {
+pub(crate) trait BuildSegments<C> {
     fn append_path_iter<I: Iterator<Item = IntPoint>>(
         &mut self,
         iter: I,
         shape_type: ShapeType,
         keep_same_line_points: bool,
     ) -> bool;
+
+    fn append_path_iter_with_counts<I: Iterator<Item = IntPoint>>(
+        &mut self,
+        iter: I,
+        direct: C,
+        invert: C,
+        keep_same_line_points: bool,
+    ) -> bool;
 }
 
-impl<C: WindingCount> BuildSegments for Vec<Segment<C>> {
+impl<C: WindingCount> BuildSegments<C> for Vec<Segment<C>> {
     #[inline]
     fn append_path_iter<I: Iterator<Item = IntPoint>>(
         &mut self,
         iter: I,
         shape_type: ShapeType,
         keep_same_line_points: bool,
+    ) -> bool {
+        let (direct, invert) = C::with_shape_type(shape_type);
+        self.append_path_iter_with_counts(iter, direct, invert, keep_same_line_points)
+    }
+
+    #[inline]
+    fn append_path_iter_with_counts<I: Iterator<Item = IntPoint>>(
+        &mut self,
+        iter: I,
+        direct: C,
+        invert: C,
+        keep_same_line_points: bool,
     ) -> bool {
         if keep_same_line_points {
-            build_segments_with_filter::<DropOppositeCollinear, I, C>(self, iter, shape_type)
+            build_segments_with_filter::<DropOppositeCollinear, I, C>(self, iter, direct, invert)
         } else {
-            build_segments_with_filter::<DropCollinear, I, C>(self, iter, shape_type)
+            build_segments_with_filter::<DropCollinear, I, C>(self, iter, direct, invert)
         }
     }
 }
@@ -33,7 +53,8 @@ impl<C: WindingCount> BuildSegments for Vec<Segment<C>> {
 fn build_segments_with_filter<F: PointFilter, I: Iterator<Item = IntPoint>, C: WindingCount>(
     segments: &mut Vec<Segment<C>>,
     mut iter: I,
-    shape_type: ShapeType,
+    direct: C,
+    invert: C,
 ) -> bool {
     // our goal add all not degenerate segments
     let mut p0 = if let Some(p) = iter.next() { p } else { return false; };
@@ -55,8 +76,6 @@ fn build_segments_with_filter<F: PointFilter, I: Iterator<Item = IntPoint>, C: W
 
     let q1 = p0;
 
-    let (direct, invert) = C::with_shape_type(shape_type);
-
     // We close the loop with the first two points
     for p2 in &mut iter.chain([q0, q1]) {
         if !F::include_point(p0, p1, p2) {
@@ -133,7 +152,7 @@ mod tests {
     use crate::core::overlay::ShapeType;
     use crate::segm::boolean::ShapeCountBoolean;
     use crate::segm::build::BuildSegments;
-    use crate::segm::merge::ShapeSegmentsMerge;
+    use crate::segm::merge::{AddCount, ShapeSegmentsMerge};
     use crate::segm::segment::Segment;
     use alloc::vec::Vec;
     use i_float::int::point::IntPoint;
@@ -393,7 +412,7 @@ mod tests {
             ShapeType::Subject,
             keep_same_line_points,
         );
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         assert_eq!(segments.len(), count);
     }
@@ -408,7 +427,7 @@ mod tests {
                 ShapeType::Subject,
                 keep_same_line_points,
             );
-            segments.merge_if_needed();
+            segments.merge_if_needed(&mut AddCount);
 
             assert_eq!(segments.len(), count);
 
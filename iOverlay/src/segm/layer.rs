@@ -0,0 +1,196 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::core::overlay::ShapeType;
+use crate::segm::merge::CountMerge;
+use crate::segm::winding::WindingCount;
+
+const NEGATIVE_BIT: u32 = 1 << 31;
+
+/// Sparse winding counts of a segment or a region for any number of layers.
+/// The value is an index into `LayerCounts`, the high bit marks that all counts are negated,
+/// so the size of the count does not depend on the number of layers.
+/// The index 0 is reserved for all counts equal to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ShapeCountLayer(u32);
+
+impl ShapeCountLayer {
+    pub(crate) const EMPTY: Self = Self(0);
+
+    #[inline(always)]
+    fn index(&self) -> usize {
+        (self.0 & !NEGATIVE_BIT) as usize
+    }
+
+    #[inline(always)]
+    fn sign(&self) -> i32 {
+        if self.0 & NEGATIVE_BIT == 0 { 1 } else { -1 }
+    }
+}
+
+impl WindingCount for ShapeCountLayer {
+    #[inline(always)]
+    fn is_not_empty(&self) -> bool { self.index() != 0 }
+
+    #[inline(always)]
+    fn new(subj: i32, clip: i32) -> Self {
+        debug_assert!(subj == 0 && clip == 0, "only the empty count can be created without LayerCounts");
+        Self::EMPTY
+    }
+
+    #[inline(always)]
+    fn with_shape_type(_: ShapeType) -> (Self, Self) {
+        (Self::EMPTY, Self::EMPTY)
+    }
+
+    // the counts are summed up only by `LayerCounts`
+
+    #[inline(always)]
+    fn add(self, _: Self) -> Self {
+        unreachable!("layer counts are summed up by LayerCounts")
+    }
+
+    #[inline(always)]
+    fn apply(&mut self, _: Self) {
+        unreachable!("layer counts are summed up by LayerCounts")
+    }
+
+    #[inline(always)]
+    fn invert(self) -> Self {
+        if self.is_not_empty() { Self(self.0 ^ NEGATIVE_BIT) } else { self }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LayerCount {
+    layer: u32,
+    count: i32,
+}
+
+/// Storage of the sparse counts: only the layers with a non-zero count are kept, sorted by layer id.
+pub(crate) struct LayerCounts {
+    ranges: Vec<(u32, u32)>,
+    items: Vec<LayerCount>,
+    dense: Vec<i32>,
+}
+
+impl LayerCounts {
+    pub(crate) fn new(layer_count: usize) -> Self {
+        Self {
+            ranges: vec![(0, 0)],
+            items: Vec::new(),
+            dense: vec![0; layer_count],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.ranges.truncate(1);
+        self.items.clear();
+    }
+
+    /// The counts of a segment that goes once in the positive direction of the layer.
+    #[inline]
+    pub(crate) fn with_layer(&mut self, layer: usize) -> (ShapeCountLayer, ShapeCountLayer) {
+        let start = self.items.len();
+        self.items.push(LayerCount { layer: layer as u32, count: 1 });
+        let direct = self.finish(start);
+        (direct, direct.invert())
+    }
+
+    /// The sum of two counts.
+    pub(crate) fn add(&mut self, a: ShapeCountLayer, b: ShapeCountLayer) -> ShapeCountLayer {
+        if !a.is_not_empty() {
+            return b;
+        }
+        if !b.is_not_empty() {
+            return a;
+        }
+
+        let (a0, a1) = self.ranges[a.index()];
+        let (b0, b1) = self.ranges[b.index()];
+        let (mut i, mut j) = (a0 as usize, b0 as usize);
+        let (a1, b1) = (a1 as usize, b1 as usize);
+        let (sa, sb) = (a.sign(), b.sign());
+
+        let start = self.items.len();
+        while i < a1 || j < b1 {
+            let item = if j == b1 || i < a1 && self.items[i].layer < self.items[j].layer {
+                i += 1;
+                let c = self.items[i - 1];
+                LayerCount { layer: c.layer, count: sa * c.count }
+            } else if i == a1 || self.items[j].layer < self.items[i].layer {
+                j += 1;
+                let c = self.items[j - 1];
+                LayerCount { layer: c.layer, count: sb * c.count }
+            } else {
+                i += 1;
+                j += 1;
+                let (ci, cj) = (self.items[i - 1], self.items[j - 1]);
+                LayerCount { layer: ci.layer, count: sa * ci.count + sb * cj.count }
+            };
+            if item.count != 0 {
+                self.items.push(item);
+            }
+        }
+
+        self.finish(start)
+    }
+
+    /// Calls the predicate with the dense counts.
+    pub(crate) fn matches<P: Fn(&[i32]) -> bool>(&mut self, count: ShapeCountLayer, predicate: &P) -> bool {
+        let (start, end) = self.ranges[count.index()];
+        let items = &self.items[start as usize..end as usize];
+        let sign = count.sign();
+        for c in items.iter() {
+            self.dense[c.layer as usize] = sign * c.count;
+        }
+        let result = predicate(&self.dense);
+        for c in items.iter() {
+            self.dense[c.layer as usize] = 0;
+        }
+        result
+    }
+
+    #[inline]
+    fn finish(&mut self, start: usize) -> ShapeCountLayer {
+        let end = self.items.len();
+        if start == end {
+            return ShapeCountLayer::EMPTY;
+        }
+
+        let index = self.ranges.len() as u32;
+        debug_assert!(index < NEGATIVE_BIT);
+        self.ranges.push((start as u32, end as u32));
+        ShapeCountLayer(index)
+    }
+}
+
+impl CountMerge<ShapeCountLayer> for LayerCounts {
+    #[inline]
+    fn merge(&mut self, target: &mut ShapeCountLayer, count: ShapeCountLayer) {
+        *target = self.add(*target, count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let mut counts = LayerCounts::new(3);
+        let (a, a_invert) = counts.with_layer(2);
+        let (b, _) = counts.with_layer(0);
+
+        let ab = counts.add(a, b);
+        let aab = counts.add(ab, a);
+        let b_invert = counts.add(ab.invert(), a);
+
+        assert!(counts.matches(ab, &|c| c == [1, 0, 1]));
+        assert!(counts.matches(aab, &|c| c == [1, 0, 2]));
+        assert!(counts.matches(aab.invert(), &|c| c == [-1, 0, -2]));
+        assert!(counts.matches(b_invert, &|c| c == [-1, 0, 0]));
+        assert_eq!(counts.add(a, a_invert), ShapeCountLayer::EMPTY);
+        assert_eq!(counts.add(ab, ab.invert()), ShapeCountLayer::EMPTY);
+    }
+}
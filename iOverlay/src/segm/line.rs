@@ -0,0 +1,38 @@
+use crate::core::overlay::ShapeType;
+use crate::segm::winding::WindingCount;
+
+/// The number of source segments lying on a segment, the direction is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShapeCountLine {
+    pub count: u32,
+}
+
+impl WindingCount for ShapeCountLine {
+    #[inline(always)]
+    fn is_not_empty(&self) -> bool { self.count != 0 }
+
+    #[inline(always)]
+    fn new(subj: i32, clip: i32) -> Self {
+        Self { count: subj.unsigned_abs() + clip.unsigned_abs() }
+    }
+
+    #[inline(always)]
+    fn with_shape_type(_: ShapeType) -> (Self, Self) {
+        (Self { count: 1 }, Self { count: 1 })
+    }
+
+    #[inline(always)]
+    fn add(self, count: Self) -> Self {
+        Self { count: self.count + count.count }
+    }
+
+    #[inline(always)]
+    fn apply(&mut self, count: Self) {
+        self.count += count.count;
+    }
+
+    #[inline(always)]
+    fn invert(self) -> Self {
+        self
+    }
+}
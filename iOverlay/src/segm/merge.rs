@@ -2,20 +2,35 @@ use alloc::vec::Vec;
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 
-pub(crate) trait ShapeSegmentsMerge {
-    fn merge_if_needed(&mut self) -> bool;
-    fn copy_and_merge(&mut self, resource: &Self);
+/// Combines the count of a segment into the count of an equal segment.
+pub(crate) trait CountMerge<C> {
+    fn merge(&mut self, target: &mut C, count: C);
 }
 
-impl<C: WindingCount> ShapeSegmentsMerge for Vec<Segment<C>> {
-    fn merge_if_needed(&mut self) -> bool {
+/// The default merge: the counts are simply added.
+pub(crate) struct AddCount;
+
+impl<C: WindingCount> CountMerge<C> for AddCount {
+    #[inline(always)]
+    fn merge(&mut self, target: &mut C, count: C) {
+        target.apply(count);
+    }
+}
+
+pub(crate) trait ShapeSegmentsMerge<C> {
+    fn merge_if_needed<M: CountMerge<C>>(&mut self, merge: &mut M) -> bool;
+    fn copy_and_merge<M: CountMerge<C>>(&mut self, resource: &Self, merge: &mut M);
+}
+
+impl<C: WindingCount> ShapeSegmentsMerge<C> for Vec<Segment<C>> {
+    fn merge_if_needed<M: CountMerge<C>>(&mut self, merge: &mut M) -> bool {
         if self.len() < 2 { return false; }
 
         let mut prev = &self[0].x_segment;
         for i in 1..self.len() {
             let this = &self[i].x_segment;
             if prev.eq(this) {
-                let new_len = merge(self, i);
+                let new_len = merge_after(self, i, merge);
                 self.truncate(new_len);
                 return true;
             }
@@ -25,7 +40,7 @@ impl<C: WindingCount> ShapeSegmentsMerge for Vec<Segment<C>> {
         false
     }
 
-    fn copy_and_merge(&mut self, resource: &Self) {
+    fn copy_and_merge<M: CountMerge<C>>(&mut self, resource: &Self, merge: &mut M) {
         self.clear();
         let mut iter = resource.iter();
         let first_item = if let Some(first) = iter.next() {
@@ -38,7 +53,7 @@ impl<C: WindingCount> ShapeSegmentsMerge for Vec<Segment<C>> {
 
         for item in iter {
             if prev.x_segment.eq(&item.x_segment) {
-                prev.count.apply(item.count);
+                merge.merge(&mut prev.count, item.count);
             } else {
                 if prev.count.is_not_empty() {
                     self.push(prev);
@@ -54,14 +69,14 @@ impl<C: WindingCount> ShapeSegmentsMerge for Vec<Segment<C>> {
 
 }
 
-fn merge<C: WindingCount>(segments: &mut [Segment<C>], after: usize) -> usize {
+fn merge_after<C: WindingCount, M: CountMerge<C>>(segments: &mut [Segment<C>], after: usize, merge: &mut M) -> usize {
     let mut i = after;
     let mut j = i - 1;
     let mut prev = segments[j];
 
     while i < segments.len() {
         if prev.x_segment.eq(&segments[i].x_segment) {
-            prev.count.apply(segments[i].count);
+            merge.merge(&mut prev.count, segments[i].count);
         } else {
             if prev.count.is_not_empty() {
                 segments[j] = prev;
@@ -90,7 +105,7 @@ use i_float::int::point::IntPoint;
     #[test]
     fn test_merge_if_needed_empty() {
         let mut segments: Vec<Segment<ShapeCountBoolean>> = Vec::new();
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
         assert!(segments.is_empty(), "Empty vector should remain empty after merge");
     }
 
@@ -101,7 +116,7 @@ use i_float::int::point::IntPoint;
         let count = ShapeCountBoolean::new(1, 1);
         let segment = Segment::create_and_validate(a, b, count);
         let mut segments = vec![segment];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
         assert_eq!(segments.len(), 1, "Single segment should remain unchanged");
         assert_eq!(segments[0], segment, "Segment should be unchanged after merge");
     }
@@ -119,7 +134,7 @@ use i_float::int::point::IntPoint;
         let segment2 = Segment::create_and_validate(a2, b2, count2);
 
         let mut segments = vec![segment1, segment2];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         assert_eq!(segments.len(), 2, "Segments with different x_segments should not be merged");
         assert_eq!(segments[0], segment1, "First segment should remain unchanged");
@@ -139,7 +154,7 @@ use i_float::int::point::IntPoint;
         let segment2 = Segment::create_and_validate(a2, b2, count2);
 
         let mut segments = vec![segment1, segment2];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         assert_eq!(segments.len(), 1, "Segments should be merged into one");
         let merged_count = ShapeCountBoolean::new(1, 1);
@@ -161,7 +176,7 @@ use i_float::int::point::IntPoint;
         let segment3 = Segment::create_and_validate(a, b, count3);
 
         let mut segments = vec![segment1, segment2, segment3];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         assert_eq!(segments.len(), 1, "All segments should be merged into one");
         let merged_count = ShapeCountBoolean::new(3, 3);
@@ -183,7 +198,7 @@ use i_float::int::point::IntPoint;
         let segment2 = Segment::create_and_validate(a2, b2, count2);
 
         let mut segments = vec![segment1, segment2];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         // Both segments should have the same ordered x_segment
         assert_eq!(segments.len(), 1, "Segments with inverted points should be merged");
@@ -211,7 +226,7 @@ use i_float::int::point::IntPoint;
         let segment3 = Segment::create_and_validate(a3, b3, count3);
 
         let mut segments = vec![segment1, segment2, segment3];
-        segments.merge_if_needed();
+        segments.merge_if_needed(&mut AddCount);
 
         assert_eq!(segments.len(), 3, "Segments with different x_segments should not be merged");
         assert_eq!(segments[0], segment1, "First segment should remain unchanged");
@@ -2,6 +2,9 @@ pub mod winding;
 pub mod boolean;
 pub mod string;
 pub mod offset;
-pub(crate) mod segment;
+pub mod line;
+pub(crate) mod layer;
+pub mod segment;
 pub(crate) mod merge;
-pub(crate) mod build;
\ No newline at end of file
+pub(crate) mod build;
+pub(crate) mod track;
@@ -0,0 +1,180 @@
+use alloc::vec::Vec;
+use crate::core::overlay::ShapeType;
+use crate::segm::merge::CountMerge;
+use crate::segm::winding::WindingCount;
+
+const INVERT_BIT: u32 = 1 << 31;
+
+/// A reference to a node of `MergeTree`, the high bit marks that the direction of its leaves is inverted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TrackRef(u32);
+
+impl TrackRef {
+    /// A reference to nothing: the segment has no tracked source.
+    pub(crate) const NONE: Self = Self(u32::MAX);
+
+    #[inline(always)]
+    pub(crate) fn is_none(&self) -> bool {
+        self.0 == u32::MAX
+    }
+
+    #[inline(always)]
+    pub(crate) fn invert(self) -> Self {
+        if self.is_none() { self } else { Self(self.0 ^ INVERT_BIT) }
+    }
+
+    #[inline(always)]
+    fn index(&self) -> usize {
+        (self.0 & !INVERT_BIT) as usize
+    }
+
+    #[inline(always)]
+    fn is_inverted(&self) -> bool {
+        self.0 & INVERT_BIT != 0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TrackNode {
+    Leaf(u32),
+    Union(TrackRef, TrackRef),
+}
+
+/// Records which source segments end up in every split segment.
+/// A leaf is created for a source segment, the split copies its reference,
+/// and the merge of equal segments creates a union node.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MergeTree {
+    nodes: Vec<TrackNode>,
+    stack: Vec<TrackRef>,
+}
+
+impl MergeTree {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    #[inline]
+    pub(crate) fn leaf(&mut self, value: u32) -> TrackRef {
+        self.push(TrackNode::Leaf(value))
+    }
+
+    #[inline]
+    pub(crate) fn union(&mut self, a: TrackRef, b: TrackRef) -> TrackRef {
+        if a.is_none() {
+            b
+        } else if b.is_none() {
+            a
+        } else {
+            self.push(TrackNode::Union(a, b))
+        }
+    }
+
+    /// Calls `f(value, inverted)` for every leaf of the node.
+    /// `inverted` is true if the source segment goes against the direction of the reference.
+    pub(crate) fn for_each_leaf<F: FnMut(u32, bool)>(&mut self, track: TrackRef, mut f: F) {
+        if track.is_none() {
+            return;
+        }
+        self.stack.clear();
+        self.stack.push(track);
+        while let Some(r) = self.stack.pop() {
+            match self.nodes[r.index()] {
+                TrackNode::Leaf(value) => f(value, r.is_inverted()),
+                TrackNode::Union(a, b) => {
+                    let (a, b) = if r.is_inverted() { (a.invert(), b.invert()) } else { (a, b) };
+                    self.stack.push(b);
+                    self.stack.push(a);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, node: TrackNode) -> TrackRef {
+        let index = self.nodes.len() as u32;
+        debug_assert!(index < INVERT_BIT - 1);
+        self.nodes.push(node);
+        TrackRef(index)
+    }
+}
+
+/// A winding count together with the sources of the segment.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TrackCount<C> {
+    pub(crate) count: C,
+    pub(crate) track: TrackRef,
+}
+
+impl<C: WindingCount> WindingCount for TrackCount<C> {
+    #[inline(always)]
+    fn is_not_empty(&self) -> bool {
+        self.count.is_not_empty()
+    }
+
+    #[inline(always)]
+    fn new(subj: i32, clip: i32) -> Self {
+        Self { count: C::new(subj, clip), track: TrackRef::NONE }
+    }
+
+    #[inline(always)]
+    fn with_shape_type(shape_type: ShapeType) -> (Self, Self) {
+        let (direct, invert) = C::with_shape_type(shape_type);
+        (
+            Self { count: direct, track: TrackRef::NONE },
+            Self { count: invert, track: TrackRef::NONE },
+        )
+    }
+
+    // the tracks are combined only by `MergeTree`
+
+    #[inline(always)]
+    fn add(self, count: Self) -> Self {
+        Self { count: self.count.add(count.count), track: self.track }
+    }
+
+    #[inline(always)]
+    fn apply(&mut self, count: Self) {
+        self.count.apply(count.count);
+    }
+
+    #[inline(always)]
+    fn invert(self) -> Self {
+        Self { count: self.count.invert(), track: self.track.invert() }
+    }
+}
+
+impl<C: WindingCount> CountMerge<TrackCount<C>> for MergeTree {
+    #[inline]
+    fn merge(&mut self, target: &mut TrackCount<C>, count: TrackCount<C>) {
+        target.count.apply(count.count);
+        target.track = self.union(target.track, count.track);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use super::*;
+
+    fn leaves(tree: &mut MergeTree, track: TrackRef) -> Vec<(u32, bool)> {
+        let mut result = Vec::new();
+        tree.for_each_leaf(track, |value, inverted| result.push((value, inverted)));
+        result
+    }
+
+    #[test]
+    fn test_union() {
+        let mut tree = MergeTree::default();
+        let a = tree.leaf(3);
+        let b = tree.leaf(5);
+        let ab = tree.union(a, b.invert());
+        let abc = tree.union(ab.invert(), TrackRef::NONE);
+
+        assert_eq!(leaves(&mut tree, ab), [(3, false), (5, true)]);
+        assert_eq!(leaves(&mut tree, abc), [(3, true), (5, false)]);
+        assert!(leaves(&mut tree, TrackRef::NONE).is_empty());
+        assert!(TrackRef::NONE.invert().is_none());
+    }
+}
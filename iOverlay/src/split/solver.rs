@@ -9,7 +9,7 @@ use crate::segm::winding::WindingCount;
 use crate::split::cross_solver::{CrossType, CrossSolver, EndMask};
 use crate::split::line_mark::LineMark;
 use crate::geom::x_segment::XSegment;
-use crate::segm::merge::ShapeSegmentsMerge;
+use crate::segm::merge::{AddCount, CountMerge, ShapeSegmentsMerge};
 use crate::util::sort::SmartBinSort;
 
 #[derive(Clone)]
@@ -25,30 +25,41 @@ impl SplitSolver {
 
     #[inline]
     pub(crate) fn split_segments<C: WindingCount>(&mut self, segments: &mut Vec<Segment<C>>, solver: &Solver) -> bool {
+        self.split_segments_with(segments, solver, &mut AddCount)
+    }
+
+    /// Same as `split_segments`, but the counts of equal segments are combined by `merge`.
+    #[inline]
+    pub(crate) fn split_segments_with<C: WindingCount, M: CountMerge<C>>(
+        &mut self,
+        segments: &mut Vec<Segment<C>>,
+        solver: &Solver,
+        merge: &mut M,
+    ) -> bool {
         if segments.is_empty() {
             return false;
         }
         segments.smart_bin_sort_by(solver, |a, b| a.x_segment.cmp(&b.x_segment));
-        let any_merged = segments.merge_if_needed();
-        let any_intersection = self.split(segments, solver);
+        let any_merged = segments.merge_if_needed(merge);
+        let any_intersection = self.split(segments, solver, merge);
 
         any_merged | any_intersection
     }
 
     #[inline]
-    fn split<C: WindingCount>(&mut self, segments: &mut Vec<Segment<C>>, solver: &Solver) -> bool {
+    fn split<C: WindingCount, M: CountMerge<C>>(&mut self, segments: &mut Vec<Segment<C>>, solver: &Solver, merge: &mut M) -> bool {
         let is_list = solver.is_list_split(segments);
         let snap_radius = solver.snap_radius();
         if is_list {
-            return self.list_split(snap_radius, segments, solver);
+            return self.list_split(snap_radius, segments, solver, merge);
         }
 
         let is_fragmentation = solver.is_fragmentation_required(segments);
 
         if is_fragmentation {
-            self.fragment_split(snap_radius, segments, solver)
+            self.fragment_split(snap_radius, segments, solver, merge)
         } else {
-            self.tree_split(snap_radius, segments, solver)
+            self.tree_split(snap_radius, segments, solver, merge)
         }
     }
 
@@ -95,7 +106,13 @@ impl SplitSolver {
         cross.is_round
     }
 
-    pub(super) fn apply<C: WindingCount>(&mut self, segments: &mut Vec<Segment<C>>, need_to_fix: bool, solver: &Solver) {
+    pub(super) fn apply<C: WindingCount, M: CountMerge<C>>(
+        &mut self,
+        segments: &mut Vec<Segment<C>>,
+        need_to_fix: bool,
+        solver: &Solver,
+        merge: &mut M,
+    ) {
         self.sort_and_filter_marks(segments, solver);
         let min = segments[0].x_segment.a.x;
         let mut max = segments[0].x_segment.b.x;
@@ -110,13 +127,13 @@ impl SplitSolver {
 
         let new_len = segments.len() + self.marks.len();
         if new_len <= 16 {
-            return self.one_bin_merge(segments, need_to_fix);
+            return self.one_bin_merge(segments, need_to_fix, merge);
         };
 
         let layout = if let Some(layout) = BinLayout::new(min..max, new_len) {
             layout
         } else {
-            return self.one_bin_merge(segments, need_to_fix);
+            return self.one_bin_merge(segments, need_to_fix, merge);
         };
 
         let mut bins = Self::init_bins(max, &layout, &self.marks, segments);
@@ -189,7 +206,7 @@ impl SplitSolver {
             }
         }
 
-        segments.copy_and_merge(&buffer);
+        segments.copy_and_merge(&buffer, merge);
     }
 
     #[inline]
@@ -245,7 +262,7 @@ impl SplitSolver {
     }
 
     #[inline]
-    fn one_bin_merge<C: WindingCount>(&mut self, segments: &mut Vec<Segment<C>>, need_to_fix: bool) {
+    fn one_bin_merge<C: WindingCount, M: CountMerge<C>>(&mut self, segments: &mut Vec<Segment<C>>, need_to_fix: bool, merge: &mut M) {
         if need_to_fix {
             segments.reserve(self.marks.len());
         } else {
@@ -275,7 +292,7 @@ impl SplitSolver {
 
         segments.sort_unstable_by(|a, b| a.x_segment.cmp(&b.x_segment));
 
-        segments.merge_if_needed();
+        segments.merge_if_needed(merge);
     }
 
     #[inline]
@@ -1,5 +1,6 @@
 use alloc::vec::Vec;
 use crate::core::solver::Solver;
+use crate::segm::merge::CountMerge;
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 use crate::split::cross_solver::{CrossSolver, CrossType, EndMask};
@@ -10,18 +11,19 @@ use crate::split::snap_radius::SnapRadius;
 use crate::split::solver::SplitSolver;
 
 impl SplitSolver {
-    pub(super) fn fragment_split<C: WindingCount>(
+    pub(super) fn fragment_split<C: WindingCount, M: CountMerge<C>>(
         &mut self,
         snap_radius: SnapRadius,
         segments: &mut Vec<Segment<C>>,
-        solver: &Solver
+        solver: &Solver,
+        merge: &mut M,
     ) -> bool {
         let layout = if let Some(layout) =
             GridLayout::new(segments.iter().map(|it| it.x_segment), segments.len())
         {
             layout
         } else {
-            return self.tree_split(snap_radius, segments, solver);
+            return self.tree_split(snap_radius, segments, solver, merge);
         };
 
         let mut buffer = FragmentBuffer::new(layout);
@@ -65,7 +67,7 @@ impl SplitSolver {
             any_intersection = true;
             buffer.clear();
 
-            self.apply(segments, need_to_fix, solver);
+            self.apply(segments, need_to_fix, solver, merge);
 
             snap_radius.increment();
         }
@@ -1,16 +1,18 @@
 use alloc::vec::Vec;
 use crate::core::solver::Solver;
+use crate::segm::merge::CountMerge;
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 use crate::split::snap_radius::SnapRadius;
 use crate::split::solver::SplitSolver;
 
 impl SplitSolver {
-    pub(super) fn list_split<C: WindingCount>(
+    pub(super) fn list_split<C: WindingCount, M: CountMerge<C>>(
         &mut self,
         snap_radius: SnapRadius,
         segments: &mut Vec<Segment<C>>,
-        solver: &Solver
+        solver: &Solver,
+        merge: &mut M,
     ) -> bool {
         let mut need_to_fix = true;
 
@@ -45,13 +47,13 @@ impl SplitSolver {
                 return any_intersection;
             }
             any_intersection = true;
-            self.apply(segments, need_to_fix, solver);
+            self.apply(segments, need_to_fix, solver, merge);
 
             snap_radius.increment();
 
             if need_to_fix && !solver.is_list_split(segments) {
                 // finish with tree solver if edges is become large
-                self.tree_split(snap_radius, segments, solver);
+                self.tree_split(snap_radius, segments, solver, merge);
                 return true;
             }
         }
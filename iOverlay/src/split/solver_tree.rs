@@ -2,6 +2,7 @@ use alloc::vec::Vec;
 use i_tree::ExpiredVal;
 use crate::geom::line_range::LineRange;
 use crate::geom::x_segment::XSegment;
+use crate::segm::merge::CountMerge;
 use crate::segm::segment::Segment;
 use crate::segm::winding::WindingCount;
 use crate::split::snap_radius::SnapRadius;
@@ -24,17 +25,18 @@ impl ExpiredVal<i32> for IdSegment {
 }
 
 impl SplitSolver {
-    pub(super) fn tree_split<C: WindingCount>(
+    pub(super) fn tree_split<C: WindingCount, M: CountMerge<C>>(
         &mut self,
         snap_radius: SnapRadius,
         segments: &mut Vec<Segment<C>>,
-        solver: &Solver
+        solver: &Solver,
+        merge: &mut M,
     ) -> bool {
         let range: SegRange<i32> = segments.ver_range().into();
         let mut tree: SegExpTree<i32, i32, IdSegment> = if let Some(tree) = SegExpTree::new(range) {
             tree
         } else {
-            return self.list_split(snap_radius, segments, solver);
+            return self.list_split(snap_radius, segments, solver, merge);
         };
 
         let mut need_to_fix = true;
@@ -80,7 +82,7 @@ impl SplitSolver {
             any_intersection = true;
             tree.clear();
 
-            self.apply(segments, need_to_fix, solver);
+            self.apply(segments, need_to_fix, solver, merge);
 
             snap_radius.increment();
         }
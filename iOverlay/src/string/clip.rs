@@ -10,6 +10,7 @@ use crate::segm::segment::SegmentFill;
 use crate::string::graph::StringGraph;
 use crate::string::line::IntLine;
 use crate::string::overlay::StringOverlay;
+use crate::string::piece::StringPiece;
 
 #[derive(Debug, Clone, Copy)]
 pub struct ClipRule {
@@ -77,7 +78,7 @@ impl StringGraph<'_> {
 
 const CLIP_BACK: SegmentFill = STRING_BACK_CLIP << 2;
 const CLIP_FORWARD: SegmentFill = STRING_FORWARD_CLIP << 2;
-const CLIP_ALL: SegmentFill = CLIP_BACK | CLIP_FORWARD;
+pub(crate) const CLIP_ALL: SegmentFill = CLIP_BACK | CLIP_FORWARD;
 
 impl OverlayLink {
     #[inline]
@@ -145,6 +146,17 @@ pub trait IntClip {
     /// # Returns
     /// A vector of `IntPath` instances containing the clipped portions of the input paths.
     fn clip_paths(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<IntPath>;
+
+    /// Clips multiple paths and tells for every piece which path it comes from and where on it.
+    /// - `paths`: A slice of `IntPath` instances, each representing a path to be clipped.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of path segments.
+    /// - `clip_rule`: The rule for clipping, determining how boundary and inversion settings affect the result.
+    ///
+    /// # Returns
+    /// A vector of `StringPiece` instances with the index of the source path and the arc lengths where the piece
+    /// starts and ends on it, ordered by the source index and then along the source path.
+    /// A part shared by several source paths is reported for every one of them.
+    fn clip_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<IntPoint, f64>>;
 }
 
 impl IntClip for IntShapes {
@@ -175,6 +187,13 @@ impl IntClip for IntShapes {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<IntPoint, f64>> {
+        let mut overlay = StringOverlay::with_shapes(self);
+        overlay.add_string_paths(paths);
+        overlay.clip_string_lines_with_sources(fill_rule, clip_rule)
+    }
 }
 
 impl IntClip for IntShape {
@@ -205,6 +224,13 @@ impl IntClip for IntShape {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<IntPoint, f64>> {
+        let mut overlay = StringOverlay::with_shape(self);
+        overlay.add_string_paths(paths);
+        overlay.clip_string_lines_with_sources(fill_rule, clip_rule)
+    }
 }
 
 impl IntClip for [IntPoint] {
@@ -235,6 +261,13 @@ impl IntClip for [IntPoint] {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<IntPoint, f64>> {
+        let mut overlay = StringOverlay::with_shape_contour(self);
+        overlay.add_string_paths(paths);
+        overlay.clip_string_lines_with_sources(fill_rule, clip_rule)
+    }
 }
 
 
@@ -0,0 +1,151 @@
+//! This module joins polylines that share end points into maximal chains.
+//! Paths are joined only at nodes where exactly two path ends meet, so junctions and free ends stay chain ends.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Paths;
+use i_shape::int::path::IntPath;
+
+/// The result of the line merging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedLines<P> {
+    /// Open chains, each starts and ends at a free end or a junction.
+    pub paths: Paths<P>,
+    /// Closed chains without junctions, the last point is not repeated.
+    pub rings: Paths<P>,
+}
+
+/// Trait `LineMerge` joins connected polylines into maximal chains.
+pub trait LineMerge {
+    /// Joins paths that share end points into maximal chains.
+    /// - `directed`: If `true`, a path is joined only to a path that starts where it ends and paths are never reversed.
+    ///   Otherwise a path can be reversed to continue the chain.
+    ///
+    /// ```rust
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::string::line_merge::LineMerge;
+    ///
+    /// let paths = vec![
+    ///     vec![IntPoint::new(0, 0), IntPoint::new(10, 0)],
+    ///     vec![IntPoint::new(20, 0), IntPoint::new(10, 0)],
+    /// ];
+    ///
+    /// let merged = paths.merge_lines(false);
+    /// assert_eq!(merged.paths, vec![vec![IntPoint::new(0, 0), IntPoint::new(10, 0), IntPoint::new(20, 0)]]);
+    ///
+    /// let merged = paths.merge_lines(true);
+    /// assert_eq!(merged.paths.len(), 2);
+    /// ```
+    fn merge_lines(&self, directed: bool) -> MergedLines<IntPoint>;
+}
+
+impl LineMerge for [IntPath] {
+    #[inline]
+    fn merge_lines(&self, directed: bool) -> MergedLines<IntPoint> {
+        merge_paths(self, directed, |a, b| a.cmp(b))
+    }
+}
+
+/// A path end at a node: the path index and `true` for the last point.
+#[derive(Clone, Copy)]
+struct PathEnd {
+    path: usize,
+    is_last: bool,
+}
+
+/// Joins paths at the nodes where exactly two ends meet, `cmp` defines the equal end points.
+pub(crate) fn merge_paths<P: Copy, F: Fn(&P, &P) -> Ordering>(paths: &[Vec<P>], directed: bool, cmp: F) -> MergedLines<P> {
+    let valid: Vec<usize> = (0..paths.len()).filter(|&i| paths[i].len() > 1).collect();
+
+    // every end gets the index of its node
+    let mut ends: Vec<(usize, bool)> = valid.iter().flat_map(|&i| [(i, false), (i, true)]).collect();
+    let point = |(path, is_last): (usize, bool)| {
+        let path = &paths[path];
+        if is_last { &path[path.len() - 1] } else { &path[0] }
+    };
+    ends.sort_unstable_by(|&e0, &e1| cmp(point(e0), point(e1)));
+
+    let mut node_for_end = vec![[usize::MAX; 2]; paths.len()];
+    let mut nodes: Vec<Vec<PathEnd>> = Vec::new();
+    for (i, &end) in ends.iter().enumerate() {
+        if i == 0 || cmp(point(ends[i - 1]), point(end)) != Ordering::Equal {
+            nodes.push(Vec::new());
+        }
+        let node = nodes.len() - 1;
+        nodes[node].push(PathEnd { path: end.0, is_last: end.1 });
+        node_for_end[end.0][end.1 as usize] = node;
+    }
+
+    // a chain goes through a node with two ends, in the directed mode one must come in and one must go out
+    let is_pass = |node: usize| {
+        let node = &nodes[node];
+        node.len() == 2 && (!directed || node[0].is_last != node[1].is_last)
+    };
+
+    let mut visited = vec![false; paths.len()];
+    let mut result = MergedLines { paths: Vec::new(), rings: Vec::new() };
+
+    // chains from the free ends and junctions
+    for &start in valid.iter() {
+        for is_last in [false, true] {
+            if visited[start] || is_pass(node_for_end[start][is_last as usize]) || (directed && is_last) {
+                continue;
+            }
+            let mut chain = Vec::new();
+            let mut end = PathEnd { path: start, is_last };
+            loop {
+                visited[end.path] = true;
+                append(&mut chain, &paths[end.path], end.is_last);
+
+                let node = node_for_end[end.path][!end.is_last as usize];
+                if !is_pass(node) {
+                    break;
+                }
+                let next = next_end(&nodes[node], end.path, !end.is_last);
+                if visited[next.path] {
+                    break;
+                }
+                end = next;
+            }
+            result.paths.push(chain);
+        }
+    }
+
+    // the rest are rings
+    for &start in valid.iter() {
+        if visited[start] {
+            continue;
+        }
+        let mut chain = Vec::new();
+        let mut end = PathEnd { path: start, is_last: false };
+        while !visited[end.path] {
+            visited[end.path] = true;
+            append(&mut chain, &paths[end.path], end.is_last);
+            let node = node_for_end[end.path][!end.is_last as usize];
+            end = next_end(&nodes[node], end.path, !end.is_last);
+        }
+        chain.pop();
+        result.rings.push(chain);
+    }
+
+    result
+}
+
+/// Returns the other end at the pass node, it is the start of the next path.
+#[inline]
+fn next_end(node: &[PathEnd], path: usize, is_last: bool) -> PathEnd {
+    if node[0].path == path && node[0].is_last == is_last { node[1] } else { node[0] }
+}
+
+/// Appends the path starting from its `is_last` end, the common point is not repeated.
+#[inline]
+fn append<P: Copy>(chain: &mut Vec<P>, path: &[P], is_last: bool) {
+    let skip = if chain.is_empty() { 0 } else { 1 };
+    if is_last {
+        chain.extend(path.iter().rev().skip(skip).copied());
+    } else {
+        chain.extend(path.iter().skip(skip).copied());
+    }
+}
@@ -6,4 +6,8 @@ pub mod graph;
 pub mod split;
 pub mod clip;
 pub mod extract;
+pub mod noding;
+pub mod polygonize;
+pub mod line_merge;
+pub mod piece;
 mod filter;
\ No newline at end of file
@@ -0,0 +1,211 @@
+//! This module provides noding of polylines against each other. The lines are split at all mutual
+//! intersections and overlaps, the shared pieces are merged into one edge and the result is a planar
+//! network of nodes and edges.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::IntContour;
+use crate::core::solver::Solver;
+use crate::geom::x_segment::XSegment;
+use crate::segm::line::ShapeCountLine;
+use crate::segm::segment::Segment;
+use crate::split::solver::SplitSolver;
+use crate::string::line::IntLine;
+
+/// A node of the `LineNetwork`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkNode {
+    pub point: IntPoint,
+    /// Indices of the edges that start or end at this node.
+    pub edges: Vec<usize>,
+}
+
+impl NetworkNode {
+    /// The number of edges that meet at this node.
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// An edge of the `LineNetwork` between the nodes `a` and `b`, the point of `a` is always less than the point of `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkEdge {
+    pub a: usize,
+    pub b: usize,
+    /// The number of source segments lying on this edge.
+    pub count: usize,
+}
+
+/// A planar network of noded lines. Edges cross each other only at nodes, and the nodes are sorted by point.
+#[derive(Debug, Clone, Default)]
+pub struct LineNetwork {
+    pub nodes: Vec<NetworkNode>,
+    pub edges: Vec<NetworkEdge>,
+}
+
+impl LineNetwork {
+    /// Returns the end points of the edge.
+    #[inline]
+    pub fn edge_points(&self, index: usize) -> IntLine {
+        let edge = &self.edges[index];
+        [self.nodes[edge.a].point, self.nodes[edge.b].point]
+    }
+
+    /// Returns the other node of the edge.
+    #[inline]
+    pub fn other_node(&self, edge_index: usize, node_index: usize) -> usize {
+        let edge = &self.edges[edge_index];
+        if edge.a == node_index { edge.b } else { edge.a }
+    }
+}
+
+/// Splits polylines at all mutual intersections and overlaps.
+///
+/// ```rust
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::string::noding::LineNoder;
+///
+/// let a = [IntPoint::new(0, 0), IntPoint::new(10, 10)];
+/// let b = [IntPoint::new(0, 10), IntPoint::new(10, 0)];
+///
+/// let mut noder = LineNoder::new(2);
+/// noder.add_path(&a);
+/// noder.add_path(&b);
+/// let network = noder.into_network();
+///
+/// assert_eq!(network.edges.len(), 4);
+/// assert_eq!(network.nodes.len(), 5);
+/// assert_eq!(network.nodes[2].point, IntPoint::new(5, 5));
+/// assert_eq!(network.nodes[2].degree(), 4);
+/// ```
+pub struct LineNoder {
+    segments: Vec<Segment<ShapeCountLine>>,
+    split_solver: SplitSolver,
+}
+
+impl LineNoder {
+    /// Constructs a new `LineNoder` instance, initializing it with a capacity that should closely match the total count of segments.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            segments: Vec::with_capacity(capacity),
+            split_solver: SplitSolver::new(),
+        }
+    }
+
+    /// Creates a new `LineNoder` instance and initializes it with open paths.
+    /// - `paths`: A collection of paths, each representing a polyline.
+    #[inline]
+    pub fn with_paths(paths: &[IntPath]) -> Self {
+        let capacity = paths.iter().map(|path| path.len()).sum();
+        let mut noder = Self::new(capacity);
+        noder.add_paths(paths);
+        noder
+    }
+
+    /// Adds a single line segment, a degenerate segment is skipped.
+    #[inline]
+    pub fn add_line(&mut self, line: IntLine) {
+        let (a, b) = match line[0].cmp(&line[1]) {
+            core::cmp::Ordering::Less => (line[0], line[1]),
+            core::cmp::Ordering::Greater => (line[1], line[0]),
+            core::cmp::Ordering::Equal => return,
+        };
+        self.segments.push(Segment { x_segment: XSegment { a, b }, count: ShapeCountLine { count: 1 } });
+    }
+
+    /// Adds multiple line segments.
+    #[inline]
+    pub fn add_lines(&mut self, lines: &[IntLine]) {
+        for &line in lines {
+            self.add_line(line);
+        }
+    }
+
+    /// Adds an open path.
+    #[inline]
+    pub fn add_path(&mut self, path: &[IntPoint]) {
+        for w in path.windows(2) {
+            self.add_line([w[0], w[1]]);
+        }
+    }
+
+    /// Adds multiple open paths.
+    #[inline]
+    pub fn add_paths(&mut self, paths: &[IntPath]) {
+        for path in paths {
+            self.add_path(path);
+        }
+    }
+
+    /// Adds a closed path, the last point is connected to the first one.
+    #[inline]
+    pub fn add_contour(&mut self, contour: &[IntPoint]) {
+        if let (Some(&first), Some(&last)) = (contour.first(), contour.last()) {
+            self.add_path(contour);
+            self.add_line([last, first]);
+        }
+    }
+
+    /// Adds multiple closed paths.
+    #[inline]
+    pub fn add_contours(&mut self, contours: &[IntContour]) {
+        for contour in contours {
+            self.add_contour(contour);
+        }
+    }
+
+    /// Nodes the lines and returns the planar network.
+    #[inline]
+    pub fn into_network(self) -> LineNetwork {
+        self.into_network_with_solver(Default::default())
+    }
+
+    /// Nodes the lines and returns the planar network.
+    /// - `solver`: A solver type to be used for advanced control over the split process.
+    pub fn into_network_with_solver(self, solver: Solver) -> LineNetwork {
+        let segments = self.into_segments(solver);
+        if segments.is_empty() {
+            return LineNetwork::default();
+        }
+
+        let mut points = Vec::with_capacity(2 * segments.len());
+        for s in segments.iter() {
+            points.push(s.x_segment.a);
+            points.push(s.x_segment.b);
+        }
+        points.sort_unstable();
+        points.dedup();
+
+        let mut nodes: Vec<NetworkNode> = points.into_iter().map(|point| NetworkNode { point, edges: vec![] }).collect();
+        let find = |nodes: &[NetworkNode], p: IntPoint| nodes.binary_search_by(|n| n.point.cmp(&p)).unwrap_or_default();
+
+        let mut edges = Vec::with_capacity(segments.len());
+        for (index, s) in segments.iter().enumerate() {
+            let a = find(&nodes, s.x_segment.a);
+            let b = find(&nodes, s.x_segment.b);
+            nodes[a].edges.push(index);
+            nodes[b].edges.push(index);
+            edges.push(NetworkEdge { a, b, count: s.count.count as usize });
+        }
+
+        LineNetwork { nodes, edges }
+    }
+
+    /// Nodes the lines and returns every edge as a two point path.
+    #[inline]
+    pub fn into_lines(self) -> Vec<IntLine> {
+        self.into_segments(Default::default())
+            .iter()
+            .map(|s| [s.x_segment.a, s.x_segment.b])
+            .collect()
+    }
+
+    fn into_segments(mut self, solver: Solver) -> Vec<Segment<ShapeCountLine>> {
+        self.split_solver.split_segments(&mut self.segments, &solver);
+        self.segments
+    }
+}
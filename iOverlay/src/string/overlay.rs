@@ -22,7 +22,10 @@ pub struct StringOverlay {
     pub options: IntOverlayOptions,
     pub(super) segments: Vec<Segment<ShapeCountString>>,
     pub(crate) split_solver: SplitSolver,
-    pub(crate) graph_builder: GraphBuilder<ShapeCountString, Vec<usize>>
+    pub(crate) graph_builder: GraphBuilder<ShapeCountString, Vec<usize>>,
+    /// The index of the first segment of every source string line, the pieces are traced back to them
+    /// only by `clip_string_lines_with_sources`.
+    pub(crate) line_starts: Vec<usize>,
 }
 
 impl StringOverlay {
@@ -35,7 +38,8 @@ impl StringOverlay {
             options: Default::default(),
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
-            graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>>::new()
+            graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>>::new(),
+            line_starts: Vec::new(),
         }
     }
 
@@ -48,7 +52,8 @@ impl StringOverlay {
             options,
             segments: Vec::with_capacity(capacity),
             split_solver: SplitSolver::new(),
-            graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>>::new()
+            graph_builder: GraphBuilder::<ShapeCountString, Vec<usize>>::new(),
+            line_starts: Vec::new(),
         }
     }
 
@@ -125,15 +130,8 @@ impl StringOverlay {
     /// - `line`: An `IntLine` representing the open line (defined by two points).
     #[inline]
     pub fn add_string_line(&mut self, line: IntLine) {
-        let a = line[0];
-        let b = line[1];
-        let segment = match a.cmp(&b) {
-            core::cmp::Ordering::Less => Segment { x_segment: XSegment { a, b }, count: ShapeCountString { subj: 0, clip: STRING_BACK_CLIP } },
-            core::cmp::Ordering::Greater => Segment { x_segment: XSegment { a: b, b: a }, count: ShapeCountString { subj: 0, clip: STRING_FORWARD_CLIP } },
-            core::cmp::Ordering::Equal => return,
-        };
-
-        self.segments.push(segment);
+        self.line_starts.push(self.segments.len());
+        self.add_source_edge(line[0], line[1]);
     }
 
     /// Adds multiple lines (open paths) to the overlay.
@@ -145,18 +143,23 @@ impl StringOverlay {
         }
     }
 
+    /// Adds a string path to the overlay using an iterator.
+    /// - `iter`: An iterator over the points of a path representing a string line.
+    #[inline]
+    pub fn add_string_path_iter<I: Iterator<Item=IntPoint>>(&mut self, mut iter: I) {
+        self.line_starts.push(self.segments.len());
+        let mut a = if let Some(p) = iter.next() { p } else { return; };
+        for b in iter {
+            self.add_source_edge(a, b);
+            a = b;
+        }
+    }
+
     /// Adds a string path to the overlay.
     /// - `path`: A path representing a string line.
     #[inline]
     pub fn add_string_path(&mut self, path: &[IntPoint]) {
-        if path.len() < 2 {
-            return;
-        }
-        let mut a = if let Some(&p) = path.first() { p } else { return; };
-        for &b in path.iter().skip(1) {
-            self.add_string_line([a, b]);
-            a = b;
-        }
+        self.add_string_path_iter(path.iter().copied());
     }
 
     /// Adds a string line contour to the overlay.
@@ -164,13 +167,10 @@ impl StringOverlay {
     #[inline]
     pub fn add_string_contour(&mut self, contour: &[IntPoint]) {
         if contour.len() < 2 {
+            self.line_starts.push(self.segments.len());
             return;
         }
-        let mut a = if let Some(&p) = contour.last() { p } else { return; };
-        for &b in contour.iter() {
-            self.add_string_line([a, b]);
-            a = b;
-        }
+        self.add_string_path_iter(contour.iter().chain(contour.first()).copied());
     }
 
     /// Adds a string line paths to the overlay.
@@ -191,6 +191,17 @@ impl StringOverlay {
         }
     }
 
+    #[inline]
+    fn add_source_edge(&mut self, a: IntPoint, b: IntPoint) {
+        let segment = match a.cmp(&b) {
+            core::cmp::Ordering::Less => Segment { x_segment: XSegment { a, b }, count: ShapeCountString { subj: 0, clip: STRING_BACK_CLIP } },
+            core::cmp::Ordering::Greater => Segment { x_segment: XSegment { a: b, b: a }, count: ShapeCountString { subj: 0, clip: STRING_FORWARD_CLIP } },
+            core::cmp::Ordering::Equal => return,
+        };
+
+        self.segments.push(segment);
+    }
+
     /// Clips lines according to the specified build and clip rules.
     /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
     /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
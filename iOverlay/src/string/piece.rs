@@ -0,0 +1,217 @@
+//! This module finds where the clipped pieces of string lines come from.
+//! The overlay only remembers where every source line starts, the sources are resolved on request:
+//! every string segment gets a reference to its source line before the split,
+//! split parts copy it and merged segments combine the sources of all their parts.
+//! So every clipped edge is traced back to all source lines it lies on, and the pieces are rebuilt along each of them.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Path;
+use i_shape::int::path::IntPath;
+use crate::core::fill_rule::FillRule;
+use crate::core::solver::Solver;
+use crate::segm::segment::Segment;
+use crate::segm::string::{ShapeCountString, STRING_BACK_CLIP};
+use crate::segm::track::{MergeTree, TrackCount, TrackRef};
+use crate::split::solver::SplitSolver;
+use crate::string::clip::{ClipRule, CLIP_ALL};
+use crate::string::overlay::StringOverlay;
+
+/// A clipped piece of a source string line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringPiece<P, T> {
+    /// The points of the piece in the direction of the source line.
+    pub path: Path<P>,
+    /// The index of the source string line.
+    pub source: usize,
+    /// The arc length along the source line where the piece starts.
+    pub start: T,
+    /// The arc length along the source line where the piece ends.
+    pub end: T,
+}
+
+impl StringOverlay {
+    /// Clips lines like `clip_string_lines`, and tells for every piece which source line it comes from and where on it.
+    /// Every call of `add_string_line`, `add_string_path` or `add_string_contour` adds one source line,
+    /// the lines are numbered from 0 in the order they were added (the batch methods add one per item).
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// # Returns
+    /// The pieces ordered by the source index and then along the source line. A part shared by several
+    /// source lines is reported for every one of them.
+    #[inline]
+    pub fn clip_string_lines_with_sources(self, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<StringPiece<IntPoint, f64>> {
+        self.clip_string_lines_with_sources_and_solver(fill_rule, clip_rule, Default::default())
+    }
+
+    /// Clips lines like `clip_string_lines_with_sources`.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    pub fn clip_string_lines_with_sources_and_solver(
+        mut self,
+        fill_rule: FillRule,
+        clip_rule: ClipRule,
+        solver: Solver,
+    ) -> Vec<StringPiece<IntPoint, f64>> {
+        let mut tracks = StringTracks::new(&self.line_starts, &self.segments);
+        tracks.split(&mut self.split_solver, &mut self.segments, &solver);
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+        let graph = self.graph_builder.build_string_clip(fill_rule, clip_rule, &solver, &self.segments);
+
+        // the links are made from the included segments in the same order
+        let mut edges = Vec::new();
+        let mut index = 0;
+        for link in graph.links.iter() {
+            while index < self.segments.len()
+                && (self.segments[index].x_segment.a != link.a.point || self.segments[index].x_segment.b != link.b.point)
+            {
+                index += 1;
+            }
+            debug_assert!(index < self.segments.len(), "every link must have its segment");
+            if index == self.segments.len() {
+                break;
+            }
+            if link.fill & CLIP_ALL != 0 {
+                tracks.locate(index, link.a.point, link.b.point, &mut edges);
+            }
+        }
+
+        join_edges(edges)
+    }
+}
+
+/// A segment of a source line in the direction of the line.
+#[derive(Debug, Clone, Copy)]
+struct SourceEdge {
+    source: usize,
+    a: IntPoint,
+    /// The arc length of the source line at `a`.
+    offset: f64,
+}
+
+impl SourceEdge {
+    #[inline]
+    fn arc_length(&self, p: IntPoint) -> f64 {
+        self.offset + distance(self.a, p)
+    }
+}
+
+/// The sources of the `StringOverlay` segments, `tracks[i]` belongs to the segment `i`.
+/// The shape segments have no source.
+pub(crate) struct StringTracks {
+    edges: Vec<SourceEdge>,
+    tracks: Vec<TrackRef>,
+    tree: MergeTree,
+}
+
+impl StringTracks {
+    /// Assigns every string segment to its source line, the segments are not split yet.
+    /// - `line_starts`: The index of the first segment of every source line.
+    fn new(line_starts: &[usize], segments: &[Segment<ShapeCountString>]) -> Self {
+        let mut edges = Vec::new();
+        let mut tracks = vec![TrackRef::NONE; segments.len()];
+        let mut tree = MergeTree::default();
+
+        for (source, &start) in line_starts.iter().enumerate() {
+            let end = line_starts.get(source + 1).copied().unwrap_or(segments.len());
+            let mut length = 0.0;
+            for (index, segment) in segments.iter().enumerate().take(end).skip(start) {
+                // the shape segments may be added between the edges of a line
+                if segment.count.clip == 0 {
+                    continue;
+                }
+                let x_segment = &segment.x_segment;
+                let is_direct = segment.count.clip == STRING_BACK_CLIP;
+                let (a, b) = if is_direct { (x_segment.a, x_segment.b) } else { (x_segment.b, x_segment.a) };
+
+                let leaf = tree.leaf(edges.len() as u32);
+                edges.push(SourceEdge { source, a, offset: length });
+                length += distance(a, b);
+                tracks[index] = if is_direct { leaf } else { leaf.invert() };
+            }
+        }
+
+        Self { edges, tracks, tree }
+    }
+
+    /// Splits the segments and keeps `tracks` in sync with them.
+    fn split(&mut self, split_solver: &mut SplitSolver, segments: &mut Vec<Segment<ShapeCountString>>, solver: &Solver) {
+        let mut tracked: Vec<_> = segments
+            .iter()
+            .zip(self.tracks.iter())
+            .map(|(s, &track)| Segment { x_segment: s.x_segment, count: TrackCount { count: s.count, track } })
+            .collect();
+
+        split_solver.split_segments_with(&mut tracked, solver, &mut self.tree);
+
+        segments.clear();
+        self.tracks.clear();
+        for s in tracked.iter() {
+            segments.push(Segment { x_segment: s.x_segment, count: s.count.count });
+            self.tracks.push(s.count.track);
+        }
+    }
+
+    /// Adds an edge for every source line that goes along the split segment `index` from `a` to `b`.
+    fn locate(&mut self, index: usize, a: IntPoint, b: IntPoint, edges: &mut Vec<LocatedEdge>) {
+        let sources = &self.edges;
+        self.tree.for_each_leaf(self.tracks[index], |value, inverted| {
+            let source = &sources[value as usize];
+            let (a, b) = if inverted { (b, a) } else { (a, b) };
+            edges.push(LocatedEdge {
+                a,
+                b,
+                source: source.source,
+                start: source.arc_length(a),
+                end: source.arc_length(b),
+            });
+        });
+    }
+}
+
+struct LocatedEdge {
+    a: IntPoint,
+    b: IntPoint,
+    source: usize,
+    start: f64,
+    end: f64,
+}
+
+/// Orders the edges along each source line and joins the ones that follow each other.
+fn join_edges(mut edges: Vec<LocatedEdge>) -> Vec<StringPiece<IntPoint, f64>> {
+    edges.sort_unstable_by(|e0, e1| {
+        e0.source
+            .cmp(&e1.source)
+            .then(e0.start.total_cmp(&e1.start))
+            .then(e0.end.total_cmp(&e1.end))
+    });
+
+    let mut result: Vec<StringPiece<IntPoint, f64>> = Vec::new();
+    for edge in edges {
+        if let Some(last) = result.last_mut() {
+            // the arc lengths at the same point of a source line are computed the same way, so they are equal
+            let is_next = last.source == edge.source && last.end == edge.start && last.path.last() == Some(&edge.a);
+            if is_next {
+                last.path.push(edge.b);
+                last.end = edge.end;
+                continue;
+            }
+        }
+        let path: IntPath = [edge.a, edge.b].to_vec();
+        result.push(StringPiece { path, source: edge.source, start: edge.start, end: edge.end });
+    }
+
+    result
+}
+
+#[inline]
+fn distance(a: IntPoint, b: IntPoint) -> f64 {
+    let dx = b.x as f64 - a.x as f64;
+    let dy = b.y as f64 - a.y as f64;
+    FloatNumber::sqrt(dx * dx + dy * dy)
+}
@@ -0,0 +1,151 @@
+//! This module builds polygons from an unstructured set of lines. The lines are noded into a `LineNetwork`,
+//! every closed face of the network becomes a shape and the edges that do not bound any face are reported apart.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::{ContourExtension, IntPath};
+use i_shape::int::shape::IntShapes;
+use crate::bind::solver::JoinHoles;
+use crate::geom::face::{frame, FaceRings, PlanarGraph};
+use crate::string::line::IntLine;
+use crate::string::noding::{LineNetwork, LineNoder};
+
+/// The result of the polygonization.
+#[derive(Debug, Clone, Default)]
+pub struct Polygonization {
+    /// The enclosed faces, outer contours are counterclockwise and holes are clockwise.
+    pub shapes: IntShapes,
+    /// Edges that have a free end, directly or through other dangles.
+    pub dangles: Vec<IntLine>,
+    /// Edges with the same face on both sides, they connect closed parts of the network.
+    pub cut_edges: Vec<IntLine>,
+}
+
+impl LineNoder {
+    /// Nodes the lines and builds polygons from the enclosed faces.
+    ///
+    /// ```rust
+    /// use i_float::int::point::IntPoint;
+    /// use i_overlay::string::noding::LineNoder;
+    ///
+    /// let mut noder = LineNoder::new(4);
+    /// noder.add_line([IntPoint::new(0, 0), IntPoint::new(10, 0)]);
+    /// noder.add_line([IntPoint::new(10, 0), IntPoint::new(0, 10)]);
+    /// noder.add_line([IntPoint::new(0, 10), IntPoint::new(0, 0)]);
+    /// noder.add_line([IntPoint::new(10, 0), IntPoint::new(20, 0)]);
+    ///
+    /// let result = noder.polygonize();
+    ///
+    /// assert_eq!(result.shapes.len(), 1);
+    /// assert_eq!(result.dangles, vec![[IntPoint::new(10, 0), IntPoint::new(20, 0)]]);
+    /// assert!(result.cut_edges.is_empty());
+    /// ```
+    #[inline]
+    pub fn polygonize(self) -> Polygonization {
+        self.into_network().polygonize()
+    }
+}
+
+impl LineNetwork {
+    /// Builds polygons from the enclosed faces of the network.
+    /// Every face is traced with the face on the left side, so the faces are counterclockwise
+    /// and the outer border of a connected part is clockwise. The borders become holes of the faces around them.
+    pub fn polygonize(&self) -> Polygonization {
+        let mut alive = vec![true; self.edges.len()];
+        let dangles = self.remove_dangles(&mut alive);
+
+        let mut cut_edges = Vec::new();
+        let rings = loop {
+            let FaceRings { rings, ring_for_half } = FaceRings::trace(self, &alive);
+
+            // an edge on the same ring from both sides is a cut edge
+            let count = cut_edges.len();
+            for (index, is_alive) in alive.iter_mut().enumerate() {
+                if *is_alive && ring_for_half[2 * index] == ring_for_half[2 * index + 1] {
+                    *is_alive = false;
+                    cut_edges.push(self.edge_points(index));
+                }
+            }
+
+            if count == cut_edges.len() {
+                break rings;
+            }
+        };
+
+        let mut shapes = Vec::new();
+        let mut holes = Vec::new();
+        for ring in rings.iter() {
+            let contour: IntPath = ring.iter().map(|&half| self.half_start(half).1).collect();
+            if contour.unsafe_area() == 0 {
+                continue;
+            }
+            if contour.is_clockwise_ordered() {
+                holes.push(contour);
+            } else {
+                shapes.push(vec![contour]);
+            }
+        }
+
+        if !holes.is_empty() {
+            // the outer border of the whole network has no face around it, so a frame is added to catch such holes
+            let frame = frame(holes.iter().flatten().copied());
+            shapes.insert(0, vec![frame]);
+            shapes.join_unsorted_holes(holes, false);
+            shapes.remove(0);
+        }
+
+        Polygonization { shapes, dangles, cut_edges }
+    }
+
+    /// Removes edges with a free end until there are none left.
+    fn remove_dangles(&self, alive: &mut [bool]) -> Vec<IntLine> {
+        let mut degrees: Vec<usize> = self.nodes.iter().map(|n| n.degree()).collect();
+        let mut stack: Vec<usize> = (0..self.nodes.len()).filter(|&i| degrees[i] == 1).collect();
+        let mut dangles = Vec::new();
+
+        while let Some(node) = stack.pop() {
+            if degrees[node] != 1 {
+                continue;
+            }
+            let Some(&edge) = self.nodes[node].edges.iter().find(|&&e| alive[e]) else {
+                continue;
+            };
+            alive[edge] = false;
+            dangles.push(self.edge_points(edge));
+            degrees[node] = 0;
+
+            let other = self.other_node(edge, node);
+            degrees[other] -= 1;
+            if degrees[other] == 1 {
+                stack.push(other);
+            }
+        }
+
+        dangles
+    }
+
+    #[inline]
+    fn half_node(&self, half: usize) -> usize {
+        let edge = &self.edges[half >> 1];
+        if half & 1 == 0 { edge.a } else { edge.b }
+    }
+}
+
+impl PlanarGraph for LineNetwork {
+    #[inline]
+    fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edges_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    fn half_start(&self, half: usize) -> (usize, IntPoint) {
+        let node = self.half_node(half);
+        (node, self.nodes[node].point)
+    }
+}
@@ -0,0 +1,260 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::core::overlay::ContourDirection;
+use crate::core::overlay_rule::OverlayRule;
+use crate::wide::graph::WideOverlayGraph;
+use crate::wide::point::{cross, dot, orient, vector, WideContour, WidePoint, WideShapes};
+use crate::util::log::Int;
+use crate::wide::segment::WideVSegment;
+use i_tree::key::exp::KeyExpCollection;
+use i_tree::key::tree::KeyExpTree;
+
+#[derive(Clone, Copy)]
+enum Owner {
+    Outer(usize),
+    Hole(usize),
+}
+
+struct Edge {
+    a: WidePoint,
+    b: WidePoint,
+    owner: Owner,
+}
+
+impl WideOverlayGraph {
+    /// Extracts shapes according to the overlay rule.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - Returns: A vector of `WideShape`, outer contours are counterclockwise and holes are clockwise
+    ///   unless `options.output_direction` says otherwise.
+    pub fn extract_shapes(&self, overlay_rule: OverlayRule) -> WideShapes {
+        // the filled side is on the left
+        let mut edges: Vec<(WidePoint, WidePoint)> = self
+            .links
+            .iter()
+            .filter(|link| overlay_rule.is_included(link.fill))
+            .map(|link| {
+                if overlay_rule.is_fill_top(link.fill) {
+                    (link.a, link.b)
+                } else {
+                    (link.b, link.a)
+                }
+            })
+            .collect();
+        edges.sort_unstable();
+
+        let mut outers = Vec::new();
+        let mut holes = Vec::new();
+        let min_area = self.options.min_output_area as u128;
+
+        for path in collect_loops(&edges) {
+            let contour = if self.options.preserve_output_collinear {
+                path
+            } else {
+                remove_collinear(&path)
+            };
+            if contour.len() < 3 {
+                continue;
+            }
+            let area = double_area(&contour);
+            if area == 0 || (area.unsigned_abs() >> 1) < min_area {
+                continue;
+            }
+            if area > 0 {
+                outers.push(contour);
+            } else {
+                holes.push(contour);
+            }
+        }
+
+        let parents = find_parents(&outers, &holes);
+
+        let mut shapes: WideShapes = outers.into_iter().map(|outer| vec![outer]).collect();
+        for (hole, parent) in holes.into_iter().zip(parents) {
+            if let Some(index) = parent {
+                shapes[index].push(hole);
+            }
+        }
+
+        if self.options.output_direction == ContourDirection::Clockwise {
+            for contour in shapes.iter_mut().flatten() {
+                contour.reverse();
+            }
+        }
+
+        shapes
+    }
+}
+
+/// Walks the edges starting from the left top one. An outer contour takes the sharpest left turn at every vertex
+/// and a hole takes the sharpest right turn, so holes touching the outer contour become part of it
+/// and touching holes stay separate, the same as in the `i32` extraction.
+fn collect_loops(edges: &[(WidePoint, WidePoint)]) -> Vec<WideContour> {
+    // by the left end, then from the top to the bottom
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_unstable_by(|&i, &j| {
+        let (pi, qi) = min_max(edges[i]);
+        let (pj, qj) = min_max(edges[j]);
+        pi.cmp(&pj).then_with(|| cross(vector(pi, qi), vector(pj, qj)).cmp(&0))
+    });
+
+    let mut visited = vec![false; edges.len()];
+    let mut loops = Vec::new();
+
+    for &start in order.iter() {
+        if visited[start] {
+            continue;
+        }
+        // the filled side is on the left, so the top edge leaving the leftmost point has the filled side above
+        let is_hole = edges[start].0 < edges[start].1;
+
+        let mut path = Vec::new();
+        let mut index = start;
+        while !visited[index] {
+            visited[index] = true;
+            path.push(edges[index].0);
+            match next_edge(edges, index, is_hole) {
+                Some(next) => index = next,
+                None => break,
+            }
+        }
+        loops.push(path);
+    }
+
+    loops
+}
+
+#[inline]
+fn min_max(edge: (WidePoint, WidePoint)) -> (WidePoint, WidePoint) {
+    if edge.0 < edge.1 { edge } else { (edge.1, edge.0) }
+}
+
+fn next_edge(edges: &[(WidePoint, WidePoint)], index: usize, is_right_turn: bool) -> Option<usize> {
+    let (a, b) = edges[index];
+    let first = edges.partition_point(|e| e.0 < b);
+    let last = edges.partition_point(|e| e.0 <= b);
+
+    // the first edge clockwise from the back direction gives the sharpest left turn
+    let back = vector(b, a);
+    let mut best: Option<usize> = None;
+    for i in first..last {
+        let is_better = match best {
+            Some(j) => {
+                is_clockwise_before(back, vector(b, edges[i].1), vector(b, edges[j].1)) != is_right_turn
+            }
+            None => true,
+        };
+        if is_better {
+            best = Some(i);
+        }
+    }
+
+    best
+}
+
+#[inline]
+fn is_clockwise_before(d: (i128, i128), v0: (i128, i128), v1: (i128, i128)) -> bool {
+    let q0 = clockwise_quarter(d, v0);
+    let q1 = clockwise_quarter(d, v1);
+    if q0 != q1 { q0 < q1 } else { cross(v0, v1) < 0 }
+}
+
+#[inline]
+fn clockwise_quarter(d: (i128, i128), v: (i128, i128)) -> u8 {
+    match cross(d, v) {
+        c if c < 0 => 0,
+        0 if dot(d, v) < 0 => 1,
+        c if c > 0 => 2,
+        _ => 3,
+    }
+}
+
+fn remove_collinear(path: &[WidePoint]) -> WideContour {
+    let n = path.len();
+    if n < 3 {
+        return path.to_vec();
+    }
+    (0..n)
+        .filter(|&i| orient(path[(i + n - 1) % n], path[i], path[(i + 1) % n]) != 0)
+        .map(|i| path[i])
+        .collect()
+}
+
+/// Twice the signed area, positive for counterclockwise contours.
+fn double_area(contour: &[WidePoint]) -> i128 {
+    let o = contour[0];
+    let mut area = 0;
+    for w in contour.windows(2).skip(1) {
+        area += cross(vector(o, w[0]), vector(o, w[1]));
+    }
+    area
+}
+
+/// Finds the outer contour of every hole. The nearest edge under the lowest edge of a hole
+/// has the filled side on top, so it belongs to the outer contour or to another hole of the same shape.
+fn find_parents(outers: &[WideContour], holes: &[WideContour]) -> Vec<Option<usize>> {
+    let mut edges = Vec::new();
+    for (owner, contours) in [(0, outers), (1, holes)] {
+        for (index, contour) in contours.iter().enumerate() {
+            let owner = if owner == 0 { Owner::Outer(index) } else { Owner::Hole(index) };
+            let n = contour.len();
+            for i in 0..n {
+                let (p0, p1) = (contour[i], contour[(i + 1) % n]);
+                if p0.x != p1.x {
+                    let (a, b) = if p0 < p1 { (p0, p1) } else { (p1, p0) };
+                    edges.push(Edge { a, b, owner });
+                }
+            }
+        }
+    }
+    edges.sort_unstable_by_key(|e| e.a);
+
+    // the lowest edge from the leftmost point of each hole
+    let mut queries: Vec<(usize, WidePoint, WidePoint)> = holes
+        .iter()
+        .enumerate()
+        .map(|(index, hole)| {
+            let n = hole.len();
+            let (m, &p) = hole.iter().enumerate().min_by_key(|&(_, p)| p).unwrap();
+            let prev = hole[(m + n - 1) % n];
+            let next = hole[(m + 1) % n];
+            let q = if orient(p, prev, next) > 0 { prev } else { next };
+            (index, p, q)
+        })
+        .collect();
+    queries.sort_unstable_by_key(|q| q.1);
+
+    let mut links: Vec<Option<Owner>> = vec![None; holes.len()];
+    let mut active: KeyExpTree<WideVSegment, i64, Option<Owner>> = KeyExpTree::new(edges.len().log2_sqrt().max(8));
+    let mut next = 0;
+    for &(index, p, q) in queries.iter() {
+        while next < edges.len() && edges[next].a.x <= p.x {
+            let e = &edges[next];
+            if e.b.x > p.x {
+                active.insert(WideVSegment { a: e.a, b: e.b }, Some(e.owner), p.x);
+            }
+            next += 1;
+        }
+
+        // the lowest edge of the hole itself is not under it
+        links[index] = active.first_less_by(p.x, None, |s| s.is_under_segment_order(p, q));
+    }
+
+    // the chain of holes always goes down, so it ends at an outer contour
+    let mut parents = vec![None; holes.len()];
+    for index in 0..holes.len() {
+        let mut current = links[index];
+        let mut steps = 0;
+        while let Some(Owner::Hole(h)) = current {
+            current = links[h];
+            steps += 1;
+            if steps > holes.len() {
+                current = None;
+            }
+        }
+        if let Some(Owner::Outer(o)) = current {
+            parents[index] = Some(o);
+        }
+    }
+
+    parents
+}
@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::IntOverlayOptions;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::segment::SegmentFill;
+use crate::segm::winding::WindingCount;
+use crate::wide::point::{cross, vector, WidePoint};
+use crate::util::log::Int;
+use crate::wide::segment::{WideSegment, WideVSegment};
+use i_tree::key::exp::KeyExpCollection;
+use i_tree::key::tree::KeyExpTree;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WideLink {
+    pub(super) a: WidePoint,
+    pub(super) b: WidePoint,
+    pub(super) fill: SegmentFill,
+}
+
+/// The split and filled segments of a `WideOverlay`. Any overlay rule can be extracted from the same graph.
+pub struct WideOverlayGraph {
+    pub options: IntOverlayOptions,
+    pub(super) links: Vec<WideLink>,
+}
+
+impl WideOverlayGraph {
+    /// Builds fills for segments which are already split and merged.
+    pub(super) fn new(fill_rule: FillRule, options: IntOverlayOptions, segments: &[WideSegment]) -> Self {
+        let mut links = Vec::with_capacity(segments.len());
+
+        // non-vertical segments crossing the scan line with the count above them
+        let mut active: KeyExpTree<WideVSegment, i64, ShapeCountBoolean> =
+            KeyExpTree::new(segments.len().log2_sqrt().max(8));
+        let mut group = Vec::new();
+
+        let mut i = 0;
+        while i < segments.len() {
+            let p = segments[i].a;
+            group.clear();
+            while i < segments.len() && segments[i].a == p {
+                group.push(i);
+                i += 1;
+            }
+
+            // from the bottom to the top
+            group.sort_unstable_by(|&j0, &j1| {
+                let v0 = vector(p, segments[j0].b);
+                let v1 = vector(p, segments[j1].b);
+                0.cmp(&cross(v0, v1))
+            });
+
+            // the count under the point comes from the nearest segment below it
+            let mut bot = active.first_less_by(p.x, ShapeCountBoolean::new(0, 0), |s| s.is_under_point_order(p));
+            for &j in group.iter() {
+                let s = &segments[j];
+                let (top, fill) = add_and_fill(fill_rule, s.count, bot);
+                links.push(WideLink { a: s.a, b: s.b, fill });
+                if !s.is_vertical() {
+                    active.insert(WideVSegment { a: s.a, b: s.b }, top, p.x);
+                }
+                bot = top;
+            }
+        }
+
+        Self { options, links }
+    }
+}
+
+#[inline]
+fn add_and_fill(fill_rule: FillRule, this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+    let top = bot.add(this);
+    let subj_top = is_filled(fill_rule, top.subj) as SegmentFill;
+    let subj_bot = is_filled(fill_rule, bot.subj) as SegmentFill;
+    let clip_top = is_filled(fill_rule, top.clip) as SegmentFill;
+    let clip_bot = is_filled(fill_rule, bot.clip) as SegmentFill;
+
+    let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
+
+    (top, fill)
+}
+
+#[inline(always)]
+fn is_filled(fill_rule: FillRule, count: i32) -> bool {
+    match fill_rule {
+        FillRule::EvenOdd => count & 1 == 1,
+        FillRule::NonZero => count != 0,
+        FillRule::Positive => count > 0,
+        FillRule::Negative => count < 0,
+    }
+}
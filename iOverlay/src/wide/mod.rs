@@ -0,0 +1,18 @@
+//! Boolean overlay with 64-bit integer coordinates.
+//!
+//! `IntPoint` is limited to `i32`, so data with large extents and fine detail has to be rescaled.
+//! This module repeats the overlay pipeline (split, fill, extract) for `WidePoint` with `i64` coordinates.
+//! All predicates are evaluated exactly with `i128` arithmetic, so coordinates must stay within
+//! `[-WidePoint::MAX_COORD, WidePoint::MAX_COORD]`.
+//!
+//! The winding counts, fill rules, overlay rules and the `i_tree` scan tree of the sweeps
+//! are shared with the `i32` pipeline.
+//! It covers the boolean overlay of shapes only, and is available with the `wide` feature.
+
+pub mod point;
+pub mod overlay;
+pub mod graph;
+
+mod segment;
+mod split;
+mod extract;
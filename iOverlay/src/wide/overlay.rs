@@ -0,0 +1,202 @@
+use alloc::vec::Vec;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{IntOverlayOptions, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::winding::WindingCount;
+use crate::wide::graph::WideOverlayGraph;
+use crate::wide::point::{orient, WideContour, WidePoint, WideShape, WideShapes};
+use crate::wide::segment::WideSegment;
+use crate::wide::split::split_segments;
+
+/// The same as `Overlay`, but for `WidePoint` with 64-bit coordinates.
+///
+/// ```rust
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::overlay_rule::OverlayRule;
+/// use i_overlay::wide::overlay::WideOverlay;
+/// use i_overlay::wide::point::WidePoint;
+///
+/// let big = 1 << 50;
+/// let subj = [
+///     WidePoint::new(0, 0),
+///     WidePoint::new(big, 0),
+///     WidePoint::new(big, big),
+///     WidePoint::new(0, big),
+/// ];
+/// let clip = [
+///     WidePoint::new(1, 1),
+///     WidePoint::new(big + 1, 1),
+///     WidePoint::new(big + 1, big + 1),
+///     WidePoint::new(1, big + 1),
+/// ];
+///
+/// let result = WideOverlay::with_contour(&subj, &clip).overlay(OverlayRule::Intersect, FillRule::NonZero);
+///
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(result[0][0].len(), 4);
+/// ```
+pub struct WideOverlay {
+    pub solver: Solver,
+    pub options: IntOverlayOptions,
+    segments: Vec<WideSegment>,
+}
+
+impl WideOverlay {
+    /// Constructs a new `WideOverlay` instance.
+    /// - `capacity`: The initial capacity for storing edge data.
+    pub fn new(capacity: usize) -> Self {
+        Self::new_custom(capacity, Default::default(), Default::default())
+    }
+
+    /// Constructs a new `WideOverlay` instance.
+    /// - `capacity`: The initial capacity for storing edge data.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use, only its precision is used.
+    pub fn new_custom(capacity: usize, options: IntOverlayOptions, solver: Solver) -> Self {
+        Self {
+            solver,
+            options,
+            segments: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a new `WideOverlay` instance and initializes it with subject and clip contours.
+    /// - `subj`: A contour that defines the subject.
+    /// - `clip`: A contour that defines the clip.
+    pub fn with_contour(subj: &[WidePoint], clip: &[WidePoint]) -> Self {
+        let mut overlay = Self::new(subj.len() + clip.len());
+        overlay.add_contour(subj, ShapeType::Subject);
+        overlay.add_contour(clip, ShapeType::Clip);
+        overlay
+    }
+
+    /// Creates a new `WideOverlay` instance and initializes it with subject and clip contours.
+    /// - `subj`: An array of contours that together define the subject.
+    /// - `clip`: An array of contours that together define the clip.
+    pub fn with_contours(subj: &[WideContour], clip: &[WideContour]) -> Self {
+        Self::with_contours_custom(subj, clip, Default::default(), Default::default())
+    }
+
+    /// Creates a new `WideOverlay` instance and initializes it with subject and clip contours.
+    /// - `subj`: An array of contours that together define the subject.
+    /// - `clip`: An array of contours that together define the clip.
+    /// - `options`: Adjust custom behavior.
+    /// - `solver`: Type of solver to use, only its precision is used.
+    pub fn with_contours_custom(
+        subj: &[WideContour],
+        clip: &[WideContour],
+        options: IntOverlayOptions,
+        solver: Solver,
+    ) -> Self {
+        let capacity = subj.iter().fold(0, |s, c| s + c.len()) + clip.iter().fold(0, |s, c| s + c.len());
+        let mut overlay = Self::new_custom(capacity, options, solver);
+        overlay.add_contours(subj, ShapeType::Subject);
+        overlay.add_contours(clip, ShapeType::Clip);
+        overlay
+    }
+
+    /// Creates a new `WideOverlay` instance and initializes it with subject and clip shapes.
+    /// - `subj`: An array of shapes to be used as the subject in the overlay operation.
+    /// - `clip`: An array of shapes to be used as the clip in the overlay operation.
+    pub fn with_shapes(subj: &[WideShape], clip: &[WideShape]) -> Self {
+        let mut overlay = Self::new(0);
+        for shape in subj.iter() {
+            overlay.add_contours(shape, ShapeType::Subject);
+        }
+        for shape in clip.iter() {
+            overlay.add_contours(shape, ShapeType::Clip);
+        }
+        overlay
+    }
+
+    /// Adds a single path to the overlay as either subject or clip paths.
+    /// - `contour`: A closed path.
+    /// - `shape_type`: Specifies the role of the added path in the overlay operation, either as `Subject` or `Clip`.
+    ///
+    /// Every coordinate must be within `[-WidePoint::MAX_COORD, WidePoint::MAX_COORD]`.
+    pub fn add_contour(&mut self, contour: &[WidePoint], shape_type: ShapeType) {
+        debug_assert!(contour.iter().all(WidePoint::is_valid), "coordinate is out of the allowed range");
+
+        let points = if self.options.preserve_input_collinear {
+            contour.to_vec()
+        } else {
+            drop_collinear(contour)
+        };
+
+        let n = points.len();
+        if n < 3 {
+            return;
+        }
+
+        let (direct, _) = ShapeCountBoolean::with_shape_type(shape_type);
+        let mut p0 = points[n - 1];
+        for &p1 in points.iter() {
+            if let Some(segment) = WideSegment::with_edge(p0, p1, direct) {
+                self.segments.push(segment);
+            }
+            p0 = p1;
+        }
+    }
+
+    /// Adds multiple paths to the overlay as either subject or clip paths.
+    /// - `contours`: An array of closed paths.
+    /// - `shape_type`: Specifies the role of the added paths in the overlay operation, either as `Subject` or `Clip`.
+    pub fn add_contours(&mut self, contours: &[WideContour], shape_type: ShapeType) {
+        for contour in contours.iter() {
+            self.add_contour(contour, shape_type);
+        }
+    }
+
+    /// Clears the segments, the overlay can be reused with new contours.
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    /// Splits the segments and computes their fills.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - Returns: A graph from which shapes of any overlay rule can be extracted.
+    pub fn build_graph_view(&mut self, fill_rule: FillRule) -> WideOverlayGraph {
+        split_segments(&mut self.segments, &self.solver);
+        WideOverlayGraph::new(fill_rule, self.options, &self.segments)
+    }
+
+    /// Executes a single Boolean operation on the current geometry using the specified overlay and build rules.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - Returns: A vector of `WideShape`.
+    pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> WideShapes {
+        self.build_graph_view(fill_rule).extract_shapes(overlay_rule)
+    }
+}
+
+/// Removes repeated points and points which lie on the line of their neighbors.
+fn drop_collinear(contour: &[WidePoint]) -> WideContour {
+    let mut points: WideContour = Vec::with_capacity(contour.len());
+    for &p in contour.iter() {
+        while points.len() >= 2 && orient(points[points.len() - 2], points[points.len() - 1], p) == 0 {
+            points.pop();
+        }
+        if points.last() != Some(&p) {
+            points.push(p);
+        }
+    }
+
+    // the same at the joint of the last and the first points
+    loop {
+        let n = points.len();
+        if n < 3 {
+            break;
+        }
+        if points[0] == points[n - 1] || orient(points[n - 2], points[n - 1], points[0]) == 0 {
+            points.pop();
+        } else if orient(points[n - 1], points[0], points[1]) == 0 {
+            points.remove(0);
+        } else {
+            break;
+        }
+    }
+
+    points
+}
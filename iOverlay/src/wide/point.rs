@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+
+/// A point with 64-bit integer coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WidePoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A sequence of points.
+pub type WidePath = Vec<WidePoint>;
+/// A closed sequence of points.
+pub type WideContour = Vec<WidePoint>;
+/// The first contour is the outer boundary, the others are holes.
+pub type WideShape = Vec<WideContour>;
+/// A collection of shapes.
+pub type WideShapes = Vec<WideShape>;
+
+impl WidePoint {
+    /// The largest absolute coordinate value. Any cross product of two vectors
+    /// between such points still fits into `i128`.
+    pub const MAX_COORD: i64 = (1 << 62) - 1;
+
+    #[inline(always)]
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_valid(&self) -> bool {
+        (-Self::MAX_COORD..=Self::MAX_COORD).contains(&self.x) && (-Self::MAX_COORD..=Self::MAX_COORD).contains(&self.y)
+    }
+
+    #[inline(always)]
+    pub(crate) fn sqr_distance(&self, other: WidePoint) -> u128 {
+        let dx = (self.x as i128 - other.x as i128).unsigned_abs();
+        let dy = (self.y as i128 - other.y as i128).unsigned_abs();
+        dx * dx + dy * dy
+    }
+}
+
+impl From<IntPoint> for WidePoint {
+    #[inline(always)]
+    fn from(p: IntPoint) -> Self {
+        Self { x: p.x as i64, y: p.y as i64 }
+    }
+}
+
+/// The vector from `a` to `b`.
+#[inline(always)]
+pub(crate) fn vector(a: WidePoint, b: WidePoint) -> (i128, i128) {
+    (b.x as i128 - a.x as i128, b.y as i128 - a.y as i128)
+}
+
+#[inline(always)]
+pub(crate) fn cross(v0: (i128, i128), v1: (i128, i128)) -> i128 {
+    v0.0 * v1.1 - v0.1 * v1.0
+}
+
+#[inline(always)]
+pub(crate) fn dot(v0: (i128, i128), v1: (i128, i128)) -> i128 {
+    v0.0 * v1.0 + v0.1 * v1.1
+}
+
+/// Positive if `p` is to the left of `a -> b`, negative if to the right, zero if collinear.
+#[inline(always)]
+pub(crate) fn orient(a: WidePoint, b: WidePoint, p: WidePoint) -> i128 {
+    cross(vector(a, b), vector(a, p)).signum()
+}
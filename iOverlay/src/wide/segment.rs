@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use i_tree::ExpiredKey;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::winding::WindingCount;
+use crate::wide::point::{orient, WidePoint};
+
+/// A segment with `a < b`. The count belongs to the direction `a -> b`.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WideSegment {
+    pub(super) a: WidePoint,
+    pub(super) b: WidePoint,
+    pub(super) count: ShapeCountBoolean,
+}
+
+impl WideSegment {
+    #[inline]
+    pub(super) fn with_edge(p0: WidePoint, p1: WidePoint, direct: ShapeCountBoolean) -> Option<Self> {
+        match p0.cmp(&p1) {
+            Ordering::Less => Some(Self { a: p0, b: p1, count: direct }),
+            Ordering::Greater => Some(Self { a: p1, b: p0, count: direct.invert() }),
+            Ordering::Equal => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn is_vertical(&self) -> bool {
+        self.a.x == self.b.x
+    }
+}
+
+/// Sorts segments by their ends and joins equal ones, dropping segments with an empty count.
+pub(super) fn merge_segments(segments: &mut Vec<WideSegment>) {
+    segments.sort_unstable_by(|s0, s1| s0.a.cmp(&s1.a).then(s0.b.cmp(&s1.b)));
+
+    let mut n = 0;
+    for i in 0..segments.len() {
+        let s = segments[i];
+        if n > 0 && segments[n - 1].a == s.a && segments[n - 1].b == s.b {
+            segments[n - 1].count.apply(s.count);
+        } else {
+            if n > 0 && !segments[n - 1].count.is_not_empty() {
+                n -= 1;
+            }
+            segments[n] = s;
+            n += 1;
+        }
+    }
+    if n > 0 && !segments[n - 1].count.is_not_empty() {
+        n -= 1;
+    }
+    segments.truncate(n);
+}
+
+/// Tests if the segment `a0 -> b0` lies under the segment `a1 -> b1` right after the larger start.
+/// Both segments are non-vertical, ordered (`a < b`) and overlap by x.
+#[inline]
+pub(super) fn is_under(a0: WidePoint, b0: WidePoint, a1: WidePoint, b1: WidePoint) -> bool {
+    if a0 == a1 {
+        return orient(a0, b0, b1) > 0;
+    }
+
+    // an end can touch the other segment when collinear points are already removed
+    if a0.x < a1.x {
+        match orient(a0, b0, a1) {
+            0 => orient(a0, b0, b1) > 0,
+            o => o > 0,
+        }
+    } else {
+        match orient(a1, b1, a0) {
+            0 => orient(a1, b1, b0) < 0,
+            o => o < 0,
+        }
+    }
+}
+
+/// A non-vertical segment crossing the scan line, the key of the scan tree like `VSegment` in the main pipeline.
+/// The segments in the tree do not cross each other, so they are ordered from the bottom to the top
+/// the same way at any common x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct WideVSegment {
+    pub(super) a: WidePoint,
+    pub(super) b: WidePoint,
+}
+
+impl WideVSegment {
+    /// The order of the segment relative to the point, `Less` if the segment is strictly under it.
+    #[inline]
+    pub(super) fn is_under_point_order(&self, p: WidePoint) -> Ordering {
+        if orient(self.a, self.b, p) > 0 { Ordering::Less } else { Ordering::Greater }
+    }
+
+    /// The order of the segment relative to the segment `a -> b`, which is not in the tree.
+    #[inline]
+    pub(super) fn is_under_segment_order(&self, a: WidePoint, b: WidePoint) -> Ordering {
+        if is_under(self.a, self.b, a, b) { Ordering::Less } else { Ordering::Greater }
+    }
+}
+
+impl PartialOrd for WideVSegment {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WideVSegment {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else if is_under(self.a, self.b, other.a, other.b) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+impl ExpiredKey<i64> for WideVSegment {
+    #[inline]
+    fn expiration(&self) -> i64 {
+        self.b.x
+    }
+}
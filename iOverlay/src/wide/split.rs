@@ -0,0 +1,306 @@
+use alloc::vec::Vec;
+use crate::core::solver::Solver;
+use crate::segm::boolean::ShapeCountBoolean;
+use crate::segm::winding::WindingCount;
+use crate::wide::point::{cross, dot, orient, vector, WidePoint};
+use crate::wide::segment::{merge_segments, WideSegment};
+
+struct Mark {
+    index: usize,
+    point: WidePoint,
+}
+
+/// Splits segments at all intersections until no two segments cross or overlap.
+/// A rounded cross point which is close to an end of a segment is snapped to that end,
+/// the snap radius grows with every iteration the same way as in the `i32` solver.
+pub(super) fn split_segments(segments: &mut Vec<WideSegment>, solver: &Solver) {
+    merge_segments(segments);
+
+    let mut exponent = solver.precision.start;
+    let mut marks = Vec::new();
+
+    loop {
+        let radius: u128 = 1 << exponent.min(120);
+
+        let bands = Bands::new(segments);
+        for (band, list) in bands.lists.iter().enumerate() {
+            for (k, &i) in list.iter().enumerate() {
+                let ei = &segments[i];
+                let (min_y, max_y) = (ei.a.y.min(ei.b.y), ei.a.y.max(ei.b.y));
+                for &j in list[k + 1..].iter() {
+                    let ej = &segments[j];
+                    if ei.b.x < ej.a.x {
+                        break;
+                    }
+                    let (ej_min_y, ej_max_y) = (ej.a.y.min(ej.b.y), ej.a.y.max(ej.b.y));
+                    if ej_max_y < min_y || ej_min_y > max_y {
+                        continue;
+                    }
+                    // the pair is listed in every band of the common y range, it is tested in the lowest one
+                    if bands.index(min_y.max(ej_min_y)) != band {
+                        continue;
+                    }
+                    cross_segments(i, j, ei, ej, radius, &mut marks);
+                }
+            }
+        }
+
+        if marks.is_empty() {
+            return;
+        }
+
+        apply_marks(segments, &mut marks);
+        merge_segments(segments);
+
+        exponent += solver.precision.progression;
+    }
+}
+
+/// Horizontal bands of the bounding box, every segment is listed in all bands of its y range, ordered by the start.
+/// Segments stacked one over another are not tested against each other even if they overlap by x.
+struct Bands {
+    min_y: i64,
+    height: u128,
+    lists: Vec<Vec<usize>>,
+}
+
+impl Bands {
+    /// The segments of all bands together are limited to `MAX_COPIES` times the segments count,
+    /// so the number of bands is halved until the tall segments fit.
+    fn new(segments: &[WideSegment]) -> Self {
+        const MAX_COPIES: usize = 4;
+
+        let min_y = segments.iter().map(|s| s.a.y.min(s.b.y)).min().unwrap_or(0);
+        let max_y = segments.iter().map(|s| s.a.y.max(s.b.y)).max().unwrap_or(0);
+        let mut bands = Self {
+            min_y,
+            height: (max_y as i128 - min_y as i128 + 1) as u128,
+            lists: Vec::new(),
+        };
+
+        let mut count = (segments.len() / 8).max(1);
+        while count > 1 {
+            let copies: usize = segments.iter().map(|s| bands.range(s, count).len()).sum();
+            if copies <= MAX_COPIES * segments.len() {
+                break;
+            }
+            count /= 2;
+        }
+
+        bands.lists.resize(count, Vec::new());
+        for (i, s) in segments.iter().enumerate() {
+            for band in bands.range(s, count) {
+                bands.lists[band].push(i);
+            }
+        }
+        bands
+    }
+
+    #[inline]
+    fn index(&self, y: i64) -> usize {
+        Self::band(self.min_y, self.height, self.lists.len(), y)
+    }
+
+    #[inline]
+    fn range(&self, s: &WideSegment, count: usize) -> core::ops::Range<usize> {
+        let start = Self::band(self.min_y, self.height, count, s.a.y.min(s.b.y));
+        let end = Self::band(self.min_y, self.height, count, s.a.y.max(s.b.y));
+        start..end + 1
+    }
+
+    #[inline]
+    fn band(min_y: i64, height: u128, count: usize, y: i64) -> usize {
+        ((y as i128 - min_y as i128) as u128 * count as u128 / height) as usize
+    }
+}
+
+fn cross_segments(i: usize, j: usize, ei: &WideSegment, ej: &WideSegment, radius: u128, marks: &mut Vec<Mark>) {
+    let d0 = orient(ei.a, ei.b, ej.a);
+    let d1 = orient(ei.a, ei.b, ej.b);
+
+    if d0 == 0 && d1 == 0 {
+        // collinear, ends are ordered along the line
+        for p in [ej.a, ej.b] {
+            if ei.a < p && p < ei.b {
+                marks.push(Mark { index: i, point: p });
+            }
+        }
+        for p in [ei.a, ei.b] {
+            if ej.a < p && p < ej.b {
+                marks.push(Mark { index: j, point: p });
+            }
+        }
+        return;
+    }
+
+    let d2 = orient(ej.a, ej.b, ei.a);
+    let d3 = orient(ej.a, ej.b, ei.b);
+
+    if d0 * d1 > 0 || d2 * d3 > 0 {
+        return;
+    }
+
+    if d0 == 0 || d1 == 0 || d2 == 0 || d3 == 0 {
+        // an end lies on the other segment
+        if d0 == 0 && ej.a != ei.a && ej.a != ei.b {
+            marks.push(Mark { index: i, point: ej.a });
+        }
+        if d1 == 0 && ej.b != ei.a && ej.b != ei.b {
+            marks.push(Mark { index: i, point: ej.b });
+        }
+        if d2 == 0 && ei.a != ej.a && ei.a != ej.b {
+            marks.push(Mark { index: j, point: ei.a });
+        }
+        if d3 == 0 && ei.b != ej.a && ei.b != ej.b {
+            marks.push(Mark { index: j, point: ei.b });
+        }
+        return;
+    }
+
+    let p = cross_point(ei, ej);
+
+    if orient(ei.a, ei.b, p) == 0 && orient(ej.a, ej.b, p) == 0 {
+        marks.push(Mark { index: i, point: p });
+        marks.push(Mark { index: j, point: p });
+        return;
+    }
+
+    // rounding can move the point to an end, snap to the nearest one
+    let ra0 = ei.a.sqr_distance(p);
+    let rb0 = ei.b.sqr_distance(p);
+    let ra1 = ej.a.sqr_distance(p);
+    let rb1 = ej.b.sqr_distance(p);
+
+    if ra0 <= radius || rb0 <= radius || ra1 <= radius || rb1 <= radius {
+        if ra0.min(rb0) <= ra1.min(rb1) {
+            let end = if ra0 < rb0 { ei.a } else { ei.b };
+            marks.push(Mark { index: j, point: end });
+        } else {
+            let end = if ra1 < rb1 { ej.a } else { ej.b };
+            marks.push(Mark { index: i, point: end });
+        }
+        return;
+    }
+
+    marks.push(Mark { index: i, point: p });
+    marks.push(Mark { index: j, point: p });
+}
+
+fn apply_marks(segments: &mut Vec<WideSegment>, marks: &mut Vec<Mark>) {
+    marks.sort_unstable_by(|m0, m1| m0.index.cmp(&m1.index).then(m0.point.cmp(&m1.point)));
+    marks.dedup_by(|m0, m1| m0.index == m1.index && m0.point == m1.point);
+
+    let mut points = Vec::new();
+    let mut k = 0;
+    while k < marks.len() {
+        let index = marks[k].index;
+        let s = segments[index];
+
+        points.clear();
+        while k < marks.len() && marks[k].index == index {
+            points.push(marks[k].point);
+            k += 1;
+        }
+
+        // order along the segment, a rounded point may lie a bit aside of it
+        let v = vector(s.a, s.b);
+        points.sort_unstable_by(|p0, p1| {
+            let t0 = dot(vector(s.a, *p0), v);
+            let t1 = dot(vector(s.a, *p1), v);
+            t0.cmp(&t1).then(p0.cmp(p1))
+        });
+
+        let mut start = s.a;
+        for &p in points.iter() {
+            if let Some(part) = WideSegment::with_edge(start, p, s.count) {
+                segments.push(part);
+            }
+            start = p;
+        }
+
+        segments[index] = WideSegment::with_edge(start, s.b, s.count).unwrap_or(WideSegment {
+            a: s.a,
+            b: s.b,
+            count: ShapeCountBoolean::new(0, 0),
+        });
+    }
+
+    marks.clear();
+}
+
+/// The cross point of two segments which cross each other in their interiors, rounded to the grid.
+fn cross_point(ei: &WideSegment, ej: &WideSegment) -> WidePoint {
+    let va = vector(ei.a, ei.b);
+    let vb = vector(ej.a, ej.b);
+    let vd = vector(ei.a, ej.a);
+
+    // t = num / den is in (0, 1)
+    let den = cross(va, vb);
+    let num = cross(vd, vb);
+
+    let dx = mul_div_round(va.0, num, den);
+    let dy = mul_div_round(va.1, num, den);
+
+    WidePoint::new((ei.a.x as i128 + dx) as i64, (ei.a.y as i128 + dy) as i64)
+}
+
+/// Rounded `a * b / c` for `|a| < 2^64`, `|b| <= |c| < 2^127`.
+/// The product does not fit into 128 bits, so it is divided as a 256-bit number.
+fn mul_div_round(a: i128, b: i128, c: i128) -> i128 {
+    let is_negative = (a < 0) ^ (b < 0) ^ (c < 0);
+    let a = a.unsigned_abs();
+    let b = b.unsigned_abs();
+    let c = c.unsigned_abs();
+
+    // a * b = high * 2^128 + low
+    let mask = u64::MAX as u128;
+    let p0 = a * (b & mask);
+    let p1 = a * (b >> 64);
+    let low = p0.wrapping_add(p1 << 64);
+    let high = (p1 >> 64) + (low < p0) as u128;
+
+    // the quotient fits into 64 bits, so high < c and the remainder never exceeds 2^127
+    let mut rem = high;
+    let mut quotient: u128 = 0;
+    for bit in (0..128).rev() {
+        rem = (rem << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if rem >= c {
+            rem -= c;
+            quotient |= 1;
+        }
+    }
+
+    if rem >= c - rem {
+        quotient += 1;
+    }
+
+    let value = quotient as i128;
+    if is_negative { -value } else { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_round() {
+        assert_eq!(mul_div_round(7, 1, 2), 4);
+        assert_eq!(mul_div_round(-7, 1, 2), -4);
+        assert_eq!(mul_div_round(10, -2, 3), -7);
+        assert_eq!(mul_div_round(5, 3, 3), 5);
+
+        let a = (1i128 << 63) - 1;
+        let b = (1i128 << 125) + 12345;
+        assert_eq!(mul_div_round(a, b, b), a);
+        assert_eq!(mul_div_round(a, b, 2 * b), 1i128 << 62);
+    }
+
+    #[test]
+    fn test_cross_point() {
+        let big = 1i64 << 60;
+        let ei = WideSegment::with_edge(WidePoint::new(-big, -big), WidePoint::new(big, big), ShapeCountBoolean::SUBJ_DIRECT).unwrap();
+        let ej = WideSegment::with_edge(WidePoint::new(-big, big + 2), WidePoint::new(big, -big + 2), ShapeCountBoolean::SUBJ_DIRECT).unwrap();
+        assert_eq!(cross_point(&ei, &ej), WidePoint::new(1, 1));
+    }
+}
@@ -0,0 +1,134 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_overlay::core::arrangement::{Arrangement, Face};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::segm::segment::{BOTH_TOP, CLIP_TOP, NONE, SUBJ_TOP};
+    use i_shape::int::area::Area;
+
+    fn arrangement(overlay: &mut Overlay, fill_rule: FillRule) -> Arrangement {
+        overlay.build_graph_view(fill_rule).unwrap().extract_arrangement()
+    }
+
+    fn face_area(face: &Face) -> i64 {
+        let outer = face.outer.area_two().abs();
+        let holes: i64 = face.holes.iter().map(|h| h.area_two().abs()).sum();
+        (outer - holes) / 2
+    }
+
+    fn find(arrangement: &Arrangement, fill: u8) -> Vec<usize> {
+        arrangement.faces.iter().enumerate()
+            .filter(|(_, f)| f.is_bounded() && f.fill == fill)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping_squares() {
+        let mut overlay = Overlay::with_contour(&rect(0, 0, 10, 10), &rect(5, 5, 15, 15));
+        let arrangement = arrangement(&mut overlay, FillRule::NonZero);
+
+        assert_eq!(arrangement.faces.len(), 4);
+        let unbounded = &arrangement.faces[Arrangement::UNBOUNDED];
+        assert!(!unbounded.is_bounded());
+        assert_eq!(unbounded.fill, NONE);
+        assert_eq!(unbounded.holes.len(), 1);
+
+        let subj = find(&arrangement, SUBJ_TOP);
+        let clip = find(&arrangement, CLIP_TOP);
+        let both = find(&arrangement, BOTH_TOP);
+        assert_eq!(subj.len(), 1);
+        assert_eq!(clip.len(), 1);
+        assert_eq!(both.len(), 1);
+
+        assert_eq!(face_area(&arrangement.faces[subj[0]]), 75);
+        assert_eq!(face_area(&arrangement.faces[clip[0]]), 75);
+        assert_eq!(face_area(&arrangement.faces[both[0]]), 25);
+
+        let mut expected = vec![subj[0], clip[0]];
+        expected.sort();
+        assert_eq!(arrangement.faces[both[0]].neighbors, expected);
+        assert!(arrangement.faces[subj[0]].neighbors.contains(&Arrangement::UNBOUNDED));
+        assert!(arrangement.faces[both[0]].is_subject());
+        assert!(arrangement.faces[both[0]].is_clip());
+    }
+
+    #[test]
+    fn test_ring_with_inner_empty_face() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour(&rect(0, 0, 30, 30), ShapeType::Subject);
+        overlay.add_contour(&rect(10, 10, 20, 20), ShapeType::Subject);
+        let arrangement = arrangement(&mut overlay, FillRule::EvenOdd);
+
+        assert_eq!(arrangement.faces.len(), 3);
+
+        let ring = find(&arrangement, SUBJ_TOP);
+        let inner = find(&arrangement, NONE);
+        assert_eq!(ring.len(), 1);
+        assert_eq!(inner.len(), 1);
+
+        let ring_face = &arrangement.faces[ring[0]];
+        assert_eq!(ring_face.holes.len(), 1);
+        assert_eq!(face_area(ring_face), 900 - 100);
+        assert_eq!(ring_face.neighbors, vec![Arrangement::UNBOUNDED, inner[0]]);
+
+        let inner_face = &arrangement.faces[inner[0]];
+        assert!(inner_face.holes.is_empty());
+        assert_eq!(face_area(inner_face), 100);
+        assert_eq!(inner_face.neighbors, vec![ring[0]]);
+    }
+
+    #[test]
+    fn test_island_inside_hole() {
+        let mut overlay = Overlay::new(12);
+        overlay.add_contour(&rect(0, 0, 50, 50), ShapeType::Subject);
+        overlay.add_contour(&rect(10, 10, 40, 40), ShapeType::Subject);
+        overlay.add_contour(&rect(20, 20, 30, 30), ShapeType::Clip);
+        let arrangement = arrangement(&mut overlay, FillRule::EvenOdd);
+
+        assert_eq!(arrangement.faces.len(), 4);
+
+        let inner = find(&arrangement, NONE);
+        let island = find(&arrangement, CLIP_TOP);
+        assert_eq!(inner.len(), 1);
+        assert_eq!(island.len(), 1);
+
+        let inner_face = &arrangement.faces[inner[0]];
+        assert_eq!(inner_face.holes.len(), 1);
+        assert_eq!(face_area(inner_face), 900 - 100);
+        assert_eq!(arrangement.faces[island[0]].neighbors, vec![inner[0]]);
+    }
+
+    #[test]
+    fn test_disjoint() {
+        let mut overlay = Overlay::with_contour(&rect(0, 0, 10, 10), &rect(20, 0, 30, 10));
+        let arrangement = arrangement(&mut overlay, FillRule::NonZero);
+
+        assert_eq!(arrangement.faces.len(), 3);
+        let unbounded = &arrangement.faces[Arrangement::UNBOUNDED];
+        assert_eq!(unbounded.holes.len(), 2);
+        assert_eq!(unbounded.neighbors.len(), 2);
+    }
+
+    #[test]
+    fn test_union_area() {
+        let subj = [rect(0, 0, 10, 10)];
+        let clip = [rect(5, 2, 20, 8), rect(3, -5, 7, 15)];
+
+        let mut overlay = Overlay::with_contours(&subj, &clip);
+        let arrangement = arrangement(&mut overlay, FillRule::NonZero);
+
+        let filled: i64 = arrangement.faces.iter()
+            .filter(|f| f.is_bounded() && f.fill != NONE)
+            .map(face_area)
+            .sum();
+
+        let union = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(filled, union.area().abs());
+    }
+}
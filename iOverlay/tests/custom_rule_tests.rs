@@ -0,0 +1,103 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_overlay::core::custom_rule::CustomOverlayRule;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::segm::segment::{CLIP_BOTH, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP};
+
+    fn overlay() -> Overlay {
+        Overlay::with_contours(
+            &[rect(0, 0, 20, 20), rect(5, 5, 10, 10)],
+            &[rect(10, -5, 30, 15), rect(15, 0, 25, 10)],
+        )
+    }
+
+    #[test]
+    fn test_same_as_built_in() {
+        let rules = [
+            OverlayRule::Subject,
+            OverlayRule::Clip,
+            OverlayRule::Intersect,
+            OverlayRule::Union,
+            OverlayRule::Difference,
+            OverlayRule::InverseDifference,
+            OverlayRule::Xor,
+        ];
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            for rule in rules {
+                let expected = overlay().overlay(rule, fill_rule);
+                let result = overlay().overlay_custom(rule.into(), fill_rule);
+                assert_eq!(result, expected, "{}", rule);
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph() {
+        let rule = CustomOverlayRule::with_region_predicate(|s, c| s && !c);
+        let mut buffer = Default::default();
+        let mut overlay = overlay();
+        let graph = overlay.build_graph_view(FillRule::EvenOdd).unwrap();
+        let custom = graph.extract_shapes_custom(rule, &mut buffer);
+        let expected = graph.extract_shapes(OverlayRule::Difference, &mut buffer);
+
+        assert_eq!(custom, expected);
+    }
+
+    #[test]
+    fn test_subject_inside_clip() {
+        // subject boundary only where the clip covers both sides
+        let rule = CustomOverlayRule::with_fill_predicate(
+            |fill| {
+                let subj = fill & SUBJ_BOTH;
+                (subj == SUBJ_TOP || subj == SUBJ_BOTTOM) && fill & CLIP_BOTH == CLIP_BOTH
+            },
+            |fill| fill & SUBJ_TOP != 0,
+        );
+
+        // the subject is fully inside the clip, so the result is the subject itself
+        let mut overlay = Overlay::with_contour(&rect(5, 5, 10, 10), &rect(0, 0, 20, 20));
+        let result = overlay.overlay_custom(rule, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        assert_eq!(result[0][0].len(), 4);
+    }
+
+    #[test]
+    fn test_truth_table() {
+        // subject only: included links have exactly one subject side filled
+        let mut included = 0u16;
+        let mut fill_top = 0u16;
+        for fill in 0..16u8 {
+            let subj = fill & SUBJ_BOTH;
+            if subj == SUBJ_TOP || subj == SUBJ_BOTTOM {
+                included |= 1 << fill;
+            }
+            if subj & SUBJ_TOP != 0 {
+                fill_top |= 1 << fill;
+            }
+        }
+        let rule = CustomOverlayRule::with_truth_table(included, fill_top);
+
+        assert_eq!(rule, CustomOverlayRule::from(OverlayRule::Subject));
+    }
+
+    #[test]
+    fn test_float() {
+        let subj = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let clip = vec![[0.5, 0.0], [0.5, 1.0], [1.5, 1.0], [1.5, 0.0]];
+
+        let rule = CustomOverlayRule::with_region_predicate(|s, c| s != c);
+        let result = FloatOverlay::with_subj_and_clip(&subj, &clip).overlay_custom(rule, FillRule::NonZero);
+        let expected = FloatOverlay::with_subj_and_clip(&subj, &clip).overlay(OverlayRule::Xor, FillRule::NonZero);
+
+        assert_eq!(result, expected);
+        assert_eq!(result.len(), 2);
+    }
+}
@@ -0,0 +1,214 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::float::point::FloatPoint;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::distance::DistanceQuery;
+    use i_overlay::float::distance::FloatDistanceQuery;
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn point_segment(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+        let (vx, vy) = (b[0] - a[0], b[1] - a[1]);
+        let len = vx * vx + vy * vy;
+        let t = if len > 0.0 { (((p[0] - a[0]) * vx + (p[1] - a[1]) * vy) / len).clamp(0.0, 1.0) } else { 0.0 };
+        let (dx, dy) = (p[0] - a[0] - vx * t, p[1] - a[1] - vy * t);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    fn to_f64(p: IntPoint) -> [f64; 2] {
+        [p.x as f64, p.y as f64]
+    }
+
+    // the paths do not cross each other in the random tests, so the distance is reached at a vertex
+    fn brute_distance(a: &[IntPath], b: &[IntPath]) -> f64 {
+        let mut best = f64::INFINITY;
+        for (p, q) in [(a, b), (b, a)] {
+            for v in p.iter().flatten() {
+                for path in q.iter() {
+                    for w in path.windows(2) {
+                        best = best.min(point_segment(to_f64(*v), to_f64(w[0]), to_f64(w[1])));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_separate_shapes() {
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 10)]]);
+        let b = DistanceQuery::with_shapes(&[vec![rect(13, 14, 20, 20)]]);
+
+        let pair = a.distance(&b).unwrap();
+        assert_eq!(pair.distance, 5.0);
+        assert_eq!((pair.a.x, pair.a.y), (10.0, 10.0));
+        assert_eq!((pair.b.x, pair.b.y), (13.0, 14.0));
+
+        let pair = b.distance(&a).unwrap();
+        assert_eq!((pair.a.x, pair.a.y), (13.0, 14.0));
+
+        assert!(a.is_within_distance(&b, 5.0));
+        assert!(!a.is_within_distance(&b, 4.99));
+    }
+
+    #[test]
+    fn test_overlap_and_contain() {
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 10)]]);
+        let overlap = DistanceQuery::with_shapes(&[vec![rect(5, 5, 15, 15)]]);
+        let inner = DistanceQuery::with_shapes(&[vec![rect(2, 2, 4, 4)]]);
+        let line = DistanceQuery::with_paths(&[path(&[[2, 3], [8, 3]])]);
+
+        assert_eq!(a.distance(&overlap).unwrap().distance, 0.0);
+        assert_eq!(a.distance(&inner).unwrap().distance, 0.0);
+        assert_eq!(inner.distance(&a).unwrap().distance, 0.0);
+        assert_eq!(a.distance(&line).unwrap().distance, 0.0);
+        assert!(a.is_within_distance(&inner, 0.0));
+
+        // only one of the contours is inside
+        let two = DistanceQuery::with_shapes(&[vec![rect(100, 100, 110, 110)], vec![rect(2, 2, 3, 3)]]);
+        assert_eq!(two.distance(&a).unwrap().distance, 0.0);
+        assert_eq!(a.distance(&two).unwrap().distance, 0.0);
+        assert!(two.is_within_distance(&a, 1.0));
+
+        // the same square as a closed line is not an area
+        let ring = DistanceQuery::with_paths(&[path(&[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]])]);
+        assert_eq!(ring.distance(&inner).unwrap().distance, 2.0);
+    }
+
+    #[test]
+    fn test_hole() {
+        let mut hole = rect(10, 10, 20, 20);
+        hole.reverse();
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 30, 30), hole]]);
+        let b = DistanceQuery::with_shapes(&[vec![rect(13, 12, 17, 17)]]);
+
+        let pair = a.distance(&b).unwrap();
+        assert_eq!(pair.distance, 2.0);
+        assert_eq!((pair.a.y, pair.b.y), (10.0, 12.0));
+    }
+
+    #[test]
+    fn test_crossing_paths() {
+        let a = DistanceQuery::with_paths(&[path(&[[0, 0], [10, 10]])]);
+        let b = DistanceQuery::with_paths(&[path(&[[0, 10], [10, 0]])]);
+        let pair = a.distance(&b).unwrap();
+        assert_eq!(pair.distance, 0.0);
+        assert_eq!((pair.a.x, pair.a.y), (5.0, 5.0));
+    }
+
+    #[test]
+    fn test_hausdorff() {
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 10)]]);
+        let b = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 12)]]);
+
+        assert_eq!(a.directed_discrete_hausdorff(&b, f64::INFINITY).unwrap().distance, 0.0);
+
+        let pair = b.directed_discrete_hausdorff(&a, f64::INFINITY).unwrap();
+        assert_eq!(pair.distance, 2.0);
+        assert_eq!(pair.a.y, 12.0);
+        assert_eq!(pair.b.y, 10.0);
+
+        let pair = a.discrete_hausdorff(&b, f64::INFINITY).unwrap();
+        assert_eq!(pair.distance, 2.0);
+        assert_eq!(pair.a.y, 10.0);
+        assert_eq!(pair.b.y, 12.0);
+
+        let lines = DistanceQuery::with_paths(&[path(&[[0, 0], [10, 0]])]);
+        let shifted = DistanceQuery::with_paths(&[path(&[[0, 3], [14, 3]])]);
+        assert_eq!(lines.discrete_hausdorff(&shifted, f64::INFINITY).unwrap().distance, 5.0);
+    }
+
+    #[test]
+    fn test_hausdorff_max_step() {
+        // the farthest point of the line is the middle of its edge
+        let line = DistanceQuery::with_paths(&[path(&[[0, 0], [10, 0]])]);
+        let ends = DistanceQuery::with_paths(&[path(&[[0, 0], [0, 1]]), path(&[[10, 0], [10, 1]])]);
+
+        assert_eq!(line.directed_discrete_hausdorff(&ends, f64::INFINITY).unwrap().distance, 0.0);
+
+        let pair = line.directed_discrete_hausdorff(&ends, 1.0).unwrap();
+        assert_eq!(pair.distance, 5.0);
+        assert_eq!((pair.a.x, pair.a.y), (5.0, 0.0));
+
+        // the samples at 3.33 and 6.67 are closer than the middle by less than a half of the step
+        let pair = line.directed_discrete_hausdorff(&ends, 4.0).unwrap();
+        assert!(pair.distance < 5.0 && pair.distance >= 5.0 - 2.0);
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = DistanceQuery::with_paths(&[]);
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 10)]]);
+        assert!(empty.is_empty());
+        assert!(a.distance(&empty).is_none());
+        assert!(empty.discrete_hausdorff(&a, 1.0).is_none());
+        assert!(!a.is_within_distance(&empty, 100.0));
+        assert!(empty.nearest_point(FloatPoint::new(0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_nearest_point() {
+        let a = DistanceQuery::with_shapes(&[vec![rect(0, 0, 10, 10)]]);
+        let pair = a.nearest_point(FloatPoint::new(15.0, 5.0)).unwrap();
+        assert_eq!(pair.distance, 5.0);
+        assert_eq!((pair.b.x, pair.b.y), (10.0, 5.0));
+
+        let pair = a.nearest_point(FloatPoint::new(3.0, 5.0)).unwrap();
+        assert_eq!(pair.distance, 0.0);
+    }
+
+    #[test]
+    fn test_float() {
+        let square = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let other = vec![vec![[2.0, 0.0], [3.0, 0.0], [3.0, 1.0], [2.0, 1.0]]];
+
+        let a = FloatDistanceQuery::with_shapes(&square);
+        let b = FloatDistanceQuery::with_shapes(&other);
+
+        let pair = a.distance(&b).unwrap();
+        assert_eq!(pair.distance, 1.0);
+        assert_eq!(pair.a[0], 1.0);
+        assert_eq!(pair.b[0], 2.0);
+        assert!(a.is_within_distance(&b, 1.0));
+        assert!(!a.is_within_distance(&b, 0.5));
+        assert_eq!(a.discrete_hausdorff(&b, f64::INFINITY).unwrap().distance, 2.0);
+
+        let point = a.nearest_point(&[0.5, 3.0]).unwrap();
+        assert_eq!(point.b, [0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            // the paths are on the left and on the right side, so they never cross
+            let a: Vec<IntPath> = (0..3)
+                .map(|_| (0..4).map(|_| IntPoint::new(rng.random_range(-100..0), rng.random_range(-100..100))).collect())
+                .collect();
+            let b: Vec<IntPath> = (0..3)
+                .map(|_| (0..4).map(|_| IntPoint::new(rng.random_range(1..100), rng.random_range(-100..100))).collect())
+                .collect();
+
+            let qa = DistanceQuery::with_paths(&a);
+            let qb = DistanceQuery::with_paths(&b);
+
+            let expected = brute_distance(&a, &b);
+            let pair = qa.distance(&qb).unwrap();
+            assert!((pair.distance - expected).abs() < 1e-9);
+
+            let dx = pair.a.x - pair.b.x;
+            let dy = pair.a.y - pair.b.y;
+            assert!(((dx * dx + dy * dy).sqrt() - expected).abs() < 1e-9);
+
+            let limit = rng.random_range(0.0..20.0);
+            assert_eq!(qa.is_within_distance(&qb, limit), expected <= limit);
+        }
+    }
+}
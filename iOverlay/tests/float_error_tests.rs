@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::clip::FloatClip;
+    use i_overlay::float::error::FloatInputError;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::single::SingleFloatOverlay;
+    use i_overlay::float::slice::FloatSlice;
+    use i_overlay::mesh::outline::offset::OutlineOffset;
+    use i_overlay::mesh::stroke::offset::StrokeOffset;
+    use i_overlay::mesh::style::{OutlineStyle, StrokeStyle};
+    use i_overlay::string::clip::ClipRule;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x, y + size], [x + size, y + size], [x + size, y]]
+    }
+
+    #[test]
+    fn test_overlay_ok() {
+        let subj = square(0.0, 0.0, 1.0);
+        let clip = square(0.5, 0.0, 1.0);
+
+        let expected = subj.overlay(&clip, OverlayRule::Union, FillRule::NonZero);
+        let result = subj.try_overlay(&clip, OverlayRule::Union, FillRule::NonZero).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_overlay_non_finite() {
+        let subj = vec![square(0.0, 0.0, 1.0), square(2.0, 0.0, 1.0)];
+        let mut clip = square(0.5, 0.0, 1.0);
+        clip[3][0] = f64::INFINITY;
+
+        let result = subj.try_overlay(&clip, OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 1, path: 0, point: 3 }));
+    }
+
+    #[test]
+    fn test_overlay_degenerate() {
+        let subj = vec![[-f64::MAX, 0.0], [f64::MAX, 0.0], [0.0, 1.0]];
+        let clip = square(0.0, 0.0, 1.0);
+
+        let result = FloatOverlay::try_with_subj_and_clip(&subj, &clip);
+        assert_eq!(result.err(), Some(FloatInputError::DegenerateAdapter));
+    }
+
+    #[test]
+    fn test_overlay_precision() {
+        let subj = square(0.0, 0.0, 1.0e6);
+        let clip = square(0.5, 0.0, 1.0);
+
+        let result = FloatOverlay::try_with_subj_and_clip_custom(&subj, &clip, Default::default(), Default::default(), 1.0e-3);
+        assert!(result.is_ok());
+
+        let result = FloatOverlay::try_with_subj_and_clip_custom(&subj, &clip, Default::default(), Default::default(), 1.0e-6);
+        assert!(matches!(result.err(), Some(FloatInputError::PrecisionLoss { .. })));
+    }
+
+    #[test]
+    fn test_slice_and_clip() {
+        let shape = square(0.0, 0.0, 2.0);
+        let line = vec![[-1.0, 1.0], [3.0, 1.0]];
+
+        let result = shape.try_slice_by(&line, FillRule::NonZero).unwrap();
+        assert_eq!(result, shape.slice_by(&line, FillRule::NonZero));
+
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+        let result = line.try_clip_by(&shape, FillRule::NonZero, clip_rule).unwrap();
+        assert_eq!(result, line.clip_by(&shape, FillRule::NonZero, clip_rule));
+
+        let bad_line = vec![[-1.0, 1.0], [f64::NAN, 1.0]];
+        let result = shape.try_slice_by(&bad_line, FillRule::NonZero);
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 1, path: 0, point: 1 }));
+
+        let result = bad_line.try_clip_by(&shape, FillRule::NonZero, clip_rule);
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 1, path: 0, point: 1 }));
+    }
+
+    #[test]
+    fn test_stroke() {
+        let path = vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+
+        let result = path.try_stroke(StrokeStyle::new(1.0), false).unwrap();
+        assert_eq!(result, path.stroke(StrokeStyle::new(1.0), false));
+
+        let result = path.try_stroke(StrokeStyle::new(f64::NAN), false);
+        assert_eq!(result, Err(FloatInputError::NonFiniteParameter));
+
+        let result = path.try_stroke_custom(StrokeStyle::new(1.0), false, Default::default(), 1.0e-12);
+        assert!(matches!(result, Err(FloatInputError::PrecisionLoss { .. })));
+    }
+
+    #[test]
+    fn test_outline() {
+        let shape = square(0.0, 0.0, 10.0);
+
+        let result = shape.try_outline(&OutlineStyle::new(1.0)).unwrap();
+        assert_eq!(result, shape.outline(&OutlineStyle::new(1.0)));
+
+        let result = shape.try_outline(&OutlineStyle::new(f64::INFINITY));
+        assert_eq!(result, Err(FloatInputError::NonFiniteParameter));
+
+        let mut bad_shape = shape.clone();
+        bad_shape[1][1] = f64::NEG_INFINITY;
+        let result = bad_shape.try_outline(&OutlineStyle::new(1.0));
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 0, path: 0, point: 1 }));
+    }
+}
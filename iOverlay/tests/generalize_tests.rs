@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::generalize::{Generalize, GeneralizeMethod};
+    use i_overlay::float::generalize::FloatGeneralize;
+    use i_shape::int::path::ContourExtension;
+
+    fn points(coords: &[[i32; 2]]) -> Vec<IntPoint> {
+        coords.iter().map(|c| IntPoint::new(c[0], c[1])).collect()
+    }
+
+    #[test]
+    fn test_noise() {
+        let shapes = [vec![points(&[
+            [0, 0],
+            [5, 1],
+            [10, 0],
+            [20, -1],
+            [30, 0],
+            [30, 30],
+            [15, 31],
+            [0, 30],
+            [1, 15],
+        ])]];
+
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(2), false);
+        assert_eq!(result, vec![vec![points(&[[0, 0], [30, 0], [30, 30], [0, 30]])]]);
+
+        let result = shapes.generalize(GeneralizeMethod::Visvalingam(20), false);
+        assert_eq!(result[0][0].len(), 4);
+
+        // a small tolerance keeps all points
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(0), false);
+        assert_eq!(result[0][0].len(), 9);
+    }
+
+    #[test]
+    fn test_hole_stays_inside() {
+        let outer = points(&[[0, 0], [40, 0], [50, -30], [60, 0], [100, 0], [100, 100], [0, 100]]);
+        let hole = points(&[[48, -14], [48, -10], [52, -10], [52, -14]]);
+
+        // without the hole the bump is removed
+        let result = [vec![outer.clone()]].generalize(GeneralizeMethod::DouglasPeucker(40), false);
+        assert_eq!(result[0][0].len(), 4);
+
+        // the hole is inside the bump, so the bump stays
+        let result = [vec![outer, hole.clone()]].generalize(GeneralizeMethod::DouglasPeucker(40), false);
+        assert!(result[0][0].contains(&IntPoint::new(50, -30)));
+        assert!(result[0][1].len() >= 3 && result[0][1].iter().all(|p| hole.contains(p)));
+
+        let result_vw = [vec![result[0][0].clone(), hole]].generalize(GeneralizeMethod::Visvalingam(5000), false);
+        assert!(result_vw[0][0].contains(&IntPoint::new(50, -30)));
+    }
+
+    #[test]
+    fn test_neighbor_inside_dent() {
+        let a = points(&[[0, 0], [50, 30], [100, 0], [100, 100], [0, 100]]);
+        let b = points(&[[45, 5], [55, 5], [55, 10], [45, 10]]);
+        let shapes = [vec![a], vec![b.clone()]];
+
+        // independent shapes do not see each other
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(40), false);
+        assert_eq!(result[0][0].len(), 4);
+
+        // the neighbor is inside the dent, so the dent stays
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(40), true);
+        assert!(result[0][0].contains(&IntPoint::new(50, 30)));
+        assert!(result[1][0].iter().all(|p| b.contains(p)));
+    }
+
+    #[test]
+    fn test_shared_border() {
+        let a = points(&[[0, 0], [10, 0], [12, 2], [9, 4], [11, 6], [10, 10], [0, 10]]);
+        let b = points(&[[10, 0], [20, 0], [20, 10], [10, 10], [11, 6], [9, 4], [12, 2]]);
+        let shapes = [vec![a], vec![b]];
+
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(3), true);
+
+        assert_eq!(result.len(), 2);
+        let a = &result[0][0];
+        let b = &result[1][0];
+
+        // junctions are kept and the border is the same for both shapes
+        let border = |contour: &Vec<IntPoint>| {
+            let mut points: Vec<IntPoint> = contour.iter().filter(|p| p.x > 0 && p.x < 20).copied().collect();
+            points.sort();
+            points
+        };
+        assert_eq!(border(a), border(b));
+        assert!(a.contains(&IntPoint::new(10, 0)) && a.contains(&IntPoint::new(10, 10)));
+        assert_eq!(a.unsafe_area().abs() + b.unsafe_area().abs(), 2 * 200);
+    }
+
+    #[test]
+    fn test_float() {
+        let shapes = [
+            vec![vec![[0.0, 0.0], [1.0, 0.0], [1.1, 0.5], [1.0, 1.0], [0.0, 1.0]]],
+            vec![vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.1, 0.5]]],
+        ];
+
+        let result = shapes.generalize(GeneralizeMethod::DouglasPeucker(0.2), true);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0][0].len(), 4);
+        assert_eq!(result[1][0].len(), 4);
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_shape::int::path::IntPath;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    #[test]
+    fn test_hole_on_shape_touching_neighbor() {
+        // the hole touches its shape at (-11, 2), the bottom edge of the shape passes through a vertex of a neighbor at (-11, -1),
+        // the sweep must order the segments which start at that vertex by their ends, otherwise the hole goes to the neighbor
+        let subj = [
+            path(&[[9, 1], [-6, 3], [17, -13], [-1, 19], [12, -16], [-14, 18]]),
+            path(&[[-17, -1], [-1, 7], [7, 10], [-6, -16], [-15, 14], [16, -1]]),
+            path(&[[-12, 4], [-8, -14], [17, -14], [-11, -15], [-18, -11], [-5, -12]]),
+        ];
+        let clip = [
+            path(&[[-11, -19], [-3, -12], [-11, 18], [-10, -3], [2, 14], [0, 8]]),
+            path(&[[11, 18], [-4, 18], [-11, 11], [12, -3], [10, 15], [-6, -12]]),
+            path(&[[-2, 13], [-2, -8], [10, 17], [14, 1], [-20, 9], [-2, -2]]),
+        ];
+
+        let result = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Xor, FillRule::EvenOdd);
+        let point = IntPoint::new(-11, 2);
+        let shape = result.iter().find(|shape| shape.iter().skip(1).any(|hole| hole.contains(&point))).unwrap();
+        assert!(shape[0].contains(&point));
+    }
+}
@@ -0,0 +1,176 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::layer_overlay::{LayerError, LayerOverlay};
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_shape::int::area::Area;
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    type Predicate = fn(&[i32]) -> bool;
+
+    #[test]
+    fn test_empty() {
+        let mut overlay = LayerOverlay::new(3, 0);
+        assert!(overlay.build_graph_view(|c| c[0] != 0).is_none());
+        assert!(overlay.overlay(|c| c[0] != 0).is_empty());
+    }
+
+    #[test]
+    fn test_same_as_two_layers_overlay() {
+        let subj = rect(0, 0, 10, 10);
+        let clip = rect(5, 5, 15, 15);
+
+        let rules: [(OverlayRule, Predicate); 5] = [
+            (OverlayRule::Union, |c| c[0] != 0 || c[1] != 0),
+            (OverlayRule::Intersect, |c| c[0] != 0 && c[1] != 0),
+            (OverlayRule::Difference, |c| c[0] != 0 && c[1] == 0),
+            (OverlayRule::InverseDifference, |c| c[0] == 0 && c[1] != 0),
+            (OverlayRule::Xor, |c| (c[0] != 0) != (c[1] != 0)),
+        ];
+
+        for (rule, predicate) in rules {
+            let expected = Overlay::with_contour(&subj, &clip).overlay(rule, FillRule::NonZero);
+
+            let mut overlay = LayerOverlay::new(2, 8);
+            overlay.add_contour(&subj, 0).unwrap();
+            overlay.add_contour(&clip, 1).unwrap();
+            let result = overlay.overlay(predicate);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_at_least_k_layers() {
+        // five horizontal stripes, each shifted by 10
+        let mut overlay = LayerOverlay::new(5, 20);
+        for i in 0..5 {
+            overlay.add_contour(&rect(10 * i, 0, 10 * i + 30, 10), i as usize).unwrap();
+        }
+
+        let at_least = |k: usize, overlay: &mut LayerOverlay| {
+            overlay.overlay(|c| c.iter().filter(|&&w| w != 0).count() >= k)
+        };
+
+        let one = at_least(1, &mut overlay);
+        assert_eq!(one.len(), 1);
+        assert_eq!(one.area().abs(), 70 * 10);
+
+        let three = at_least(3, &mut overlay);
+        assert_eq!(three.len(), 1);
+        assert_eq!(three.area().abs(), 30 * 10);
+
+        let four = at_least(4, &mut overlay);
+        assert!(four.is_empty());
+    }
+
+    #[test]
+    fn test_a_and_b_not_c() {
+        let mut overlay = LayerOverlay::new(3, 12);
+        overlay.add_contour(&rect(0, 0, 20, 20), 0).unwrap();
+        overlay.add_contour(&rect(10, 0, 30, 20), 1).unwrap();
+        overlay.add_contour(&rect(12, 5, 18, 15), 2).unwrap();
+
+        let result = overlay.overlay(|c| c[0] != 0 && c[1] != 0 && c[2] == 0);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+        assert_eq!(result.area().abs(), 10 * 20 - 6 * 10);
+    }
+
+    #[test]
+    fn test_graph_view() {
+        let mut overlay = LayerOverlay::new(3, 12);
+        overlay.add_contour(&rect(0, 0, 10, 10), 0).unwrap();
+        overlay.add_contour(&rect(5, 0, 15, 10), 1).unwrap();
+        overlay.add_contour(&rect(20, 0, 30, 10), 2).unwrap();
+
+        let mut buffer = Default::default();
+        let graph = overlay.build_graph_view(|c| c.iter().any(|&w| w != 0)).unwrap();
+        let result = graph.extract_shapes(OverlayRule::Subject, &mut buffer);
+
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_layer_out_of_range() {
+        let mut overlay = LayerOverlay::new(2, 4);
+        let error = overlay.add_contour(&rect(0, 0, 10, 10), 2);
+        assert_eq!(error, Err(LayerError::OutOfRange { layer: 2, layer_count: 2 }));
+        assert!(overlay.overlay(|c| c[0] != 0).is_empty());
+    }
+
+    #[test]
+    fn test_single_layer() {
+        let mut overlay = LayerOverlay::new(1, 8);
+        overlay.add_contour(&rect(0, 0, 10, 10), 0).unwrap();
+        overlay.add_contour(&rect(5, 0, 15, 10), 0).unwrap();
+
+        let result = overlay.overlay(|c| c[0] != 0);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.area().abs(), 15 * 10);
+
+        let twice = overlay.overlay(|c| c[0].abs() >= 2);
+        assert_eq!(twice.area().abs(), 5 * 10);
+    }
+
+    #[test]
+    fn test_opposite_contours_cancel_out() {
+        let mut overlay = LayerOverlay::new(2, 8);
+        let a = rect(0, 0, 10, 10);
+        let b: IntPath = a.iter().rev().copied().collect();
+        overlay.add_contour(&a, 1).unwrap();
+        overlay.add_contour(&b, 1).unwrap();
+        overlay.add_contour(&rect(5, 0, 15, 10), 0).unwrap();
+
+        let result = overlay.overlay(|c| c[1] != 0);
+        assert!(result.is_empty());
+
+        let result = overlay.overlay(|c| c[0] != 0);
+        assert_eq!(result.area().abs(), 10 * 10);
+    }
+
+    #[test]
+    fn test_many_layers() {
+        // a thousand layers, every stripe is in its own layer
+        let n = 1000;
+        let mut overlay = LayerOverlay::new(n, 4 * n);
+        for i in 0..n {
+            let x = i as i32 * 10;
+            overlay.add_contour(&rect(x, 0, x + 20, 10), i).unwrap();
+        }
+
+        let result = overlay.overlay(|c| c.iter().filter(|&&w| w != 0).count() == 2);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.area().abs(), (n as i64 - 1) * 10 * 10);
+
+        let result = overlay.overlay(|c| c[n - 1] != 0 && c[n - 2] == 0);
+        assert_eq!(result.area().abs(), 10 * 10);
+    }
+
+    #[test]
+    fn test_random_same_as_two_layers_overlay() {
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let mut random_path = |n: usize| -> IntPath {
+                (0..n).map(|_| IntPoint::new(rng.random_range(-8..8), rng.random_range(-8..8))).collect()
+            };
+            let subj = [random_path(5), random_path(4)];
+            let clip = [random_path(5), random_path(3)];
+
+            let mut overlay = LayerOverlay::new(2, 20);
+            overlay.add_contours(&subj, 0).unwrap();
+            overlay.add_contours(&clip, 1).unwrap();
+            let result = overlay.overlay(|c| (c[0] != 0) != (c[1] != 0));
+
+            let expected = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Xor, FillRule::NonZero);
+            assert_eq!(result, expected);
+        }
+    }
+}
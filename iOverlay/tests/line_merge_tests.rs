@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::float::line_merge::FloatLineMerge;
+    use i_overlay::string::clip::ClipRule;
+    use i_overlay::string::line_merge::LineMerge;
+    use i_overlay::string::noding::LineNoder;
+    use i_overlay::string::overlay::StringOverlay;
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    #[test]
+    fn test_chain() {
+        let paths = [
+            path(&[[10, 0], [20, 0]]),
+            path(&[[0, 0], [10, 0]]),
+            path(&[[30, 5], [20, 0]]),
+        ];
+        let merged = paths.merge_lines(false);
+        let expected = path(&[[0, 0], [10, 0], [20, 0], [30, 5]]);
+        let reversed: IntPath = expected.iter().rev().copied().collect();
+        assert_eq!(merged.paths.len(), 1);
+        assert!(merged.paths[0] == expected || merged.paths[0] == reversed);
+        assert!(merged.rings.is_empty());
+    }
+
+    #[test]
+    fn test_junction() {
+        let paths = [
+            path(&[[0, 0], [10, 0]]),
+            path(&[[10, 0], [20, 0]]),
+            path(&[[10, 0], [10, 10]]),
+            path(&[[10, 10], [10, 20]]),
+        ];
+        let merged = paths.merge_lines(false);
+        assert_eq!(merged.paths.len(), 3);
+        assert!(merged.paths.contains(&path(&[[10, 0], [10, 10], [10, 20]])));
+    }
+
+    #[test]
+    fn test_directed() {
+        let paths = [
+            path(&[[0, 0], [10, 0]]),
+            path(&[[10, 0], [20, 0]]),
+            path(&[[30, 0], [20, 0]]),
+        ];
+        let merged = paths.merge_lines(true);
+        assert_eq!(merged.paths, vec![path(&[[0, 0], [10, 0], [20, 0]]), path(&[[30, 0], [20, 0]])]);
+
+        let merged = paths.merge_lines(false);
+        assert_eq!(merged.paths.len(), 1);
+        assert_eq!(merged.paths[0].len(), 4);
+    }
+
+    #[test]
+    fn test_ring() {
+        let paths = [
+            path(&[[0, 0], [10, 0], [10, 10]]),
+            path(&[[0, 0], [0, 10], [10, 10]]),
+            path(&[[20, 0], [30, 0], [30, 10], [20, 0]]),
+        ];
+        let merged = paths.merge_lines(false);
+        assert!(merged.paths.is_empty());
+        assert_eq!(merged.rings.len(), 2);
+        assert_eq!(merged.rings[0], path(&[[0, 0], [10, 0], [10, 10], [0, 10]]));
+        assert_eq!(merged.rings[1], path(&[[20, 0], [30, 0], [30, 10]]));
+
+        // the first two paths have opposite directions
+        let merged = paths.merge_lines(true);
+        assert_eq!(merged.paths.len(), 2);
+        assert_eq!(merged.rings.len(), 1);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let paths = [path(&[[0, 0]]), vec![]];
+        let merged = paths.merge_lines(false);
+        assert!(merged.paths.is_empty());
+        assert!(merged.rings.is_empty());
+    }
+
+    #[test]
+    fn test_float() {
+        let paths = [vec![[0.5, 0.0], [1.0, 0.0]], vec![[0.0, 0.0], [0.5, 0.0]]];
+        let merged = paths.merge_lines(false);
+        assert_eq!(merged.paths.len(), 1);
+        assert_eq!(merged.paths[0].len(), 3);
+    }
+
+    #[test]
+    fn test_float_negative_zero() {
+        let paths = [vec![[0.5, 0.0], [1.0, 0.0]], vec![[-1.0, 0.0], [-0.0, -0.0]], vec![[0.0, 0.0], [0.5, 0.0]]];
+        let merged = paths.merge_lines(true);
+        assert_eq!(merged.paths.len(), 1);
+        assert_eq!(merged.paths[0].len(), 4);
+    }
+
+    #[test]
+    fn test_after_clip() {
+        let shape = path(&[[0, 0], [10, 0], [10, 10], [0, 10]]);
+
+        let mut overlay = StringOverlay::with_shape_contour(&shape);
+        overlay.add_string_path(&path(&[[-5, 5], [0, 5], [5, 5], [10, 5], [15, 5]]));
+        let clipped = overlay.clip_string_lines(FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+        let merged = clipped.merge_lines(true);
+        assert_eq!(merged.paths.len(), 1);
+        assert_eq!((merged.paths[0][0], merged.paths[0][merged.paths[0].len() - 1]), (IntPoint::new(0, 5), IntPoint::new(10, 5)));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let paths: Vec<IntPath> = (0..3)
+                .map(|_| (0..4).map(|_| IntPoint::new(rng.random_range(-10..10), rng.random_range(-10..10))).collect())
+                .collect();
+            let lines: Vec<IntPath> = LineNoder::with_paths(&paths)
+                .into_lines()
+                .into_iter()
+                .map(|line| line.to_vec())
+                .collect();
+
+            let merged = lines.merge_lines(false);
+
+            // every edge is used once
+            let mut edges: Vec<[IntPoint; 2]> = merged
+                .paths
+                .iter()
+                .flat_map(|p| p.windows(2).map(|w| [w[0].min(w[1]), w[0].max(w[1])]).collect::<Vec<_>>())
+                .chain(merged.rings.iter().flat_map(|r| {
+                    (0..r.len()).map(|i| {
+                        let (a, b) = (r[i], r[(i + 1) % r.len()]);
+                        [a.min(b), a.max(b)]
+                    }).collect::<Vec<_>>()
+                }))
+                .collect();
+            edges.sort();
+            let mut expected: Vec<[IntPoint; 2]> = lines.iter().map(|l| [l[0], l[1]]).collect();
+            expected.sort();
+            assert_eq!(edges, expected);
+
+            // a chain end is never a node of two ends
+            let degree = |p: IntPoint| lines.iter().filter(|l| l[0] == p || l[1] == p).count();
+            for p in merged.paths.iter() {
+                let (a, b) = (p[0], p[p.len() - 1]);
+                assert!(degree(a) != 2 || a == b);
+                assert!(degree(b) != 2 || a == b);
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::minkowski::Minkowski;
+    use i_overlay::float::minkowski::FloatMinkowski;
+    use i_shape::int::path::ContourExtension;
+    use i_shape::int::shape::IntShapes;
+
+    fn area(shapes: &IntShapes) -> i64 {
+        let mut area = 0;
+        for shape in shapes.iter() {
+            for (i, contour) in shape.iter().enumerate() {
+                let a = contour.unsafe_area().abs();
+                area += if i == 0 { a } else { -a };
+            }
+        }
+        area / 2
+    }
+
+    #[test]
+    fn test_square() {
+        let shapes = [vec![rect(0, 0, 10, 10)]];
+        let kernel = vec![vec![rect(-1, -1, 1, 1)]];
+
+        let result = shapes.minkowski_sum(&kernel, Default::default());
+
+        assert_eq!(result, vec![vec![rect(-1, -1, 11, 11)]]);
+    }
+
+    #[test]
+    fn test_non_convex() {
+        let u = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(30, 0),
+            IntPoint::new(30, 30),
+            IntPoint::new(20, 30),
+            IntPoint::new(20, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 30),
+            IntPoint::new(0, 30),
+        ];
+        let shapes = [vec![u]];
+        let kernel = vec![vec![rect(-2, -2, 2, 2)]];
+
+        let result = shapes.minkowski_sum(&kernel, Default::default());
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        assert_eq!(area(&result), 34 * 34 - 6 * 20);
+    }
+
+    #[test]
+    fn test_hole() {
+        // the direction of the input contours does not matter
+        let mut hole = rect(10, 10, 20, 20);
+        hole.reverse();
+        let shapes = [vec![rect(0, 0, 30, 30), hole]];
+
+        let small = vec![vec![rect(-2, -2, 2, 2)]];
+        let result = shapes.minkowski_sum(&small, Default::default());
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+        assert_eq!(area(&result), 34 * 34 - 6 * 6);
+
+        // the hole is filled
+        let big = vec![vec![rect(-6, -6, 6, 6)]];
+        let result = shapes.minkowski_sum(&big, Default::default());
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        assert_eq!(area(&result), 42 * 42);
+    }
+
+    #[test]
+    fn test_diff() {
+        let shapes = [vec![rect(0, 0, 10, 10)]];
+        let triangle = vec![vec![vec![IntPoint::new(0, 0), IntPoint::new(4, 0), IntPoint::new(0, 4)]]];
+
+        let sum = shapes.minkowski_sum(&triangle, Default::default());
+        let diff = shapes.minkowski_diff(&triangle, Default::default());
+
+        let bounds = |shapes: &IntShapes| {
+            let points = shapes[0][0].iter();
+            let min_x = points.clone().map(|p| p.x).min().unwrap();
+            let max_x = points.clone().map(|p| p.x).max().unwrap();
+            let min_y = points.clone().map(|p| p.y).min().unwrap();
+            let max_y = points.map(|p| p.y).max().unwrap();
+            [min_x, min_y, max_x, max_y]
+        };
+
+        assert_eq!(bounds(&sum), [0, 0, 14, 14]);
+        assert_eq!(bounds(&diff), [-4, -4, 10, 10]);
+        assert_eq!(area(&sum), area(&diff));
+        assert_eq!(area(&sum), 100 + 2 * 40 + 8);
+    }
+
+    #[test]
+    fn test_several_shapes() {
+        let shapes = [vec![rect(0, 0, 10, 10)], vec![rect(20, 0, 30, 10)]];
+        let kernel = vec![vec![rect(0, 0, 1, 1)], vec![rect(0, 20, 1, 21)]];
+
+        let result = shapes.minkowski_sum(&kernel, Default::default());
+
+        assert_eq!(result.len(), 4);
+        assert_eq!(area(&result), 4 * 121);
+    }
+
+    #[test]
+    fn test_float() {
+        let square = [[0.0, 0.0f64], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let kernel = [[100.0, 100.0], [102.0, 100.0], [102.0, 102.0], [100.0, 102.0]];
+
+        let sum = square.minkowski_sum(&kernel);
+
+        assert_eq!(sum.len(), 1);
+        assert_eq!(sum[0].len(), 1);
+        let contour = &sum[0][0];
+        assert_eq!(contour.len(), 4);
+        for p in contour.iter() {
+            assert!((p[0] - 100.0).abs() < 1e-6 || (p[0] - 112.0).abs() < 1e-6);
+            assert!((p[1] - 100.0).abs() < 1e-6 || (p[1] - 112.0).abs() < 1e-6);
+        }
+
+        let diff = square.minkowski_diff(&kernel);
+        for p in diff[0][0].iter() {
+            assert!((p[0] + 102.0).abs() < 1e-6 || (p[0] + 90.0).abs() < 1e-6);
+            assert!((p[1] + 102.0).abs() < 1e-6 || (p[1] + 90.0).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::string::noding::{LineNetwork, LineNoder};
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn node(network: &LineNetwork, x: i32, y: i32) -> usize {
+        network.nodes.iter().position(|n| n.point == IntPoint::new(x, y)).unwrap()
+    }
+
+    fn cross(a: IntPoint, b: IntPoint, c: IntPoint) -> i64 {
+        let (ux, uy) = ((b.x - a.x) as i64, (b.y - a.y) as i64);
+        let (vx, vy) = ((c.x - a.x) as i64, (c.y - a.y) as i64);
+        ux * vy - uy * vx
+    }
+
+    // true if the edges share a point that is not a common end
+    fn is_crossed(a: [IntPoint; 2], b: [IntPoint; 2]) -> bool {
+        if a[0] == b[0] || a[0] == b[1] || a[1] == b[0] || a[1] == b[1] {
+            let d0 = cross(a[0], a[1], b[0]);
+            let d1 = cross(a[0], a[1], b[1]);
+            // collinear edges with a common end may overlap
+            if d0 != 0 || d1 != 0 {
+                return false;
+            }
+            let dot = |p: IntPoint, q: IntPoint, r: IntPoint| (q.x - p.x) as i64 * (r.x - p.x) as i64 + (q.y - p.y) as i64 * (r.y - p.y) as i64;
+            let inside = |p: IntPoint, s: [IntPoint; 2]| p != s[0] && p != s[1] && dot(p, s[0], s[1]) < 0;
+            return inside(b[0], a) || inside(b[1], a) || inside(a[0], b) || inside(a[1], b);
+        }
+        let d0 = cross(a[0], a[1], b[0]).signum();
+        let d1 = cross(a[0], a[1], b[1]).signum();
+        let d2 = cross(b[0], b[1], a[0]).signum();
+        let d3 = cross(b[0], b[1], a[1]).signum();
+        if d0 == 0 && d1 == 0 {
+            let (min_a, max_a) = (a[0].min(a[1]), a[0].max(a[1]));
+            let (min_b, max_b) = (b[0].min(b[1]), b[0].max(b[1]));
+            return min_a < max_b && min_b < max_a;
+        }
+        d0 * d1 <= 0 && d2 * d3 <= 0
+    }
+
+    #[test]
+    fn test_cross() {
+        let noder = LineNoder::with_paths(&[path(&[[0, 0], [10, 10]]), path(&[[0, 10], [10, 0]])]);
+        let network = noder.into_network();
+        assert_eq!(network.nodes.len(), 5);
+        assert_eq!(network.edges.len(), 4);
+
+        let center = node(&network, 5, 5);
+        assert_eq!(network.nodes[center].degree(), 4);
+        for n in network.nodes.iter().filter(|n| n.point != IntPoint::new(5, 5)) {
+            assert_eq!(n.degree(), 1);
+        }
+    }
+
+    #[test]
+    fn test_t_junction() {
+        let noder = LineNoder::with_paths(&[path(&[[0, 0], [10, 0]]), path(&[[5, 0], [5, 10]])]);
+        let network = noder.into_network();
+        assert_eq!(network.edges.len(), 3);
+        assert_eq!(network.nodes[node(&network, 5, 0)].degree(), 3);
+    }
+
+    #[test]
+    fn test_overlap() {
+        let noder = LineNoder::with_paths(&[path(&[[0, 0], [10, 0]]), path(&[[15, 0], [5, 0]])]);
+        let network = noder.into_network();
+        assert_eq!(network.edges.len(), 3);
+
+        let shared = network
+            .edges
+            .iter()
+            .position(|e| network.nodes[e.a].point == IntPoint::new(5, 0))
+            .unwrap();
+        assert_eq!(network.edge_points(shared), [IntPoint::new(5, 0), IntPoint::new(10, 0)]);
+        assert_eq!(network.edges[shared].count, 2);
+        assert_eq!(network.nodes[node(&network, 5, 0)].degree(), 2);
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let a = path(&[[0, 0], [10, 0], [10, 10]]);
+        let mut b = a.clone();
+        b.reverse();
+        let network = LineNoder::with_paths(&[a, b]).into_network();
+        assert_eq!(network.edges.len(), 2);
+        assert!(network.edges.iter().all(|e| e.count == 2));
+    }
+
+    #[test]
+    fn test_contour() {
+        let mut noder = LineNoder::new(8);
+        noder.add_contour(&path(&[[0, 0], [10, 0], [10, 10], [0, 10]]));
+        noder.add_line([IntPoint::new(-5, 5), IntPoint::new(15, 5)]);
+        let network = noder.into_network();
+
+        assert_eq!(network.nodes.len(), 8);
+        assert_eq!(network.edges.len(), 9);
+        let left = node(&network, 0, 5);
+        assert_eq!(network.nodes[left].degree(), 4);
+
+        let outer = node(&network, -5, 5);
+        let edge = network.nodes[outer].edges[0];
+        assert_eq!(network.other_node(edge, outer), left);
+    }
+
+    #[test]
+    fn test_empty() {
+        let network = LineNoder::with_paths(&[path(&[[1, 1], [1, 1]]), path(&[[3, 3]])]).into_network();
+        assert!(network.nodes.is_empty());
+        assert!(network.edges.is_empty());
+        assert!(LineNoder::new(0).into_lines().is_empty());
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let paths: Vec<IntPath> = (0..4)
+                .map(|_| (0..4).map(|_| IntPoint::new(rng.random_range(-20..20), rng.random_range(-20..20))).collect())
+                .collect();
+
+            let network = LineNoder::with_paths(&paths).into_network();
+
+            for (i, n) in network.nodes.iter().enumerate() {
+                for &e in n.edges.iter() {
+                    let edge = network.edges[e];
+                    assert!(edge.a == i || edge.b == i);
+                }
+            }
+
+            let degrees: usize = network.nodes.iter().map(|n| n.degree()).sum();
+            assert_eq!(degrees, 2 * network.edges.len());
+
+            for i in 0..network.edges.len() {
+                let a = network.edge_points(i);
+                assert!(a[0] < a[1]);
+                for j in i + 1..network.edges.len() {
+                    let b = network.edge_points(j);
+                    assert!(!is_crossed(a, b), "{:?} {:?}", a, b);
+                }
+            }
+        }
+    }
+}
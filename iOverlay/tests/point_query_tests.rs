@@ -0,0 +1,186 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::point_query::{PointQuery, PointWinding};
+    use i_overlay::segm::segment::{BOTH_TOP, CLIP_TOP, NONE, SUBJ_TOP};
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    #[test]
+    fn test_winding() {
+        let mut overlay = Overlay::new(12);
+        overlay.add_contour(&rect(0, 0, 10, 10), ShapeType::Subject);
+        overlay.add_contour(&rect(2, 2, 8, 8), ShapeType::Subject);
+        overlay.add_contour(&rect(5, 0, 15, 10), ShapeType::Clip);
+
+        let query = overlay.build_point_query(FillRule::EvenOdd);
+
+        assert_eq!(query.winding(IntPoint::new(1, 1)), PointWinding { subj: 1, clip: 0, is_boundary: false });
+        assert_eq!(query.winding(IntPoint::new(3, 3)), PointWinding { subj: 2, clip: 0, is_boundary: false });
+        assert_eq!(query.winding(IntPoint::new(6, 3)), PointWinding { subj: 2, clip: 1, is_boundary: false });
+        assert_eq!(query.winding(IntPoint::new(20, 3)), PointWinding::default());
+        assert!(query.winding(IntPoint::new(0, 5)).is_boundary);
+        assert!(query.winding(IntPoint::new(3, 10)).is_boundary);
+        assert!(query.winding(IntPoint::new(8, 8)).is_boundary);
+
+        assert_eq!(query.fill(IntPoint::new(3, 3)), NONE);
+        assert_eq!(query.fill(IntPoint::new(6, 3)), CLIP_TOP);
+        assert_eq!(query.fill(IntPoint::new(9, 1)), BOTH_TOP);
+        assert_eq!(query.fill(IntPoint::new(1, 1)), SUBJ_TOP);
+
+        assert!(query.contains(IntPoint::new(9, 1), OverlayRule::Intersect));
+        assert!(!query.contains(IntPoint::new(3, 3), OverlayRule::Union));
+        assert!(query.contains(IntPoint::new(12, 3), OverlayRule::InverseDifference));
+    }
+
+    #[test]
+    fn test_shapes() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour(&rect(0, 0, 10, 10), ShapeType::Subject);
+        overlay.add_contour(&rect(3, 3, 7, 7), ShapeType::Clip);
+        overlay.add_contour(&rect(20, 0, 30, 10), ShapeType::Subject);
+        let shapes = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+        assert_eq!(shapes.len(), 2);
+
+        let query = PointQuery::with_shapes(&shapes, FillRule::NonZero);
+
+        let left = query.shape_index(IntPoint::new(1, 1)).unwrap();
+        let right = query.shape_index(IntPoint::new(25, 5)).unwrap();
+        assert_ne!(left, right);
+        assert_eq!(query.shape_index(IntPoint::new(9, 5)), Some(left));
+        assert_eq!(query.shape_index(IntPoint::new(5, 8)), Some(left));
+        assert_eq!(query.shape_index(IntPoint::new(5, 5)), None);
+        assert_eq!(query.shape_index(IntPoint::new(15, 5)), None);
+        assert_eq!(query.shape_index(IntPoint::new(0, 5)), None);
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut overlay = Overlay::with_contour(&rect(0, 0, 10, 10), &rect(5, 5, 15, 15));
+        let graph = overlay.build_graph_view(FillRule::NonZero).unwrap();
+        let query = graph.build_point_query();
+
+        assert_eq!(query.fill(IntPoint::new(7, 7)), BOTH_TOP);
+        assert_eq!(query.fill(IntPoint::new(2, 2)), SUBJ_TOP);
+        assert_eq!(query.fill(IntPoint::new(12, 12)), CLIP_TOP);
+        assert_eq!(query.fill(IntPoint::new(12, 2)), NONE);
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut overlay = Overlay::new(16);
+        overlay.add_contour(&[IntPoint::new(0, 0), IntPoint::new(20, 5), IntPoint::new(3, 17)], ShapeType::Subject);
+        overlay.add_contour(&rect(5, -3, 12, 9), ShapeType::Clip);
+        overlay.add_contour(&[IntPoint::new(-4, 8), IntPoint::new(9, -2), IntPoint::new(14, 14)], ShapeType::Clip);
+
+        let query = overlay.build_point_query(FillRule::NonZero);
+
+        let mut rng = rand::rng();
+        let points: Vec<_> = (0..2000)
+            .map(|_| IntPoint::new(rng.random_range(-6..=22), rng.random_range(-6..=20)))
+            .collect();
+
+        let batch = query.winding_batch(&points);
+        for (p, w) in points.iter().zip(batch.iter()) {
+            assert_eq!(*w, query.winding(*p), "{:?}", p);
+        }
+
+        let contains = query.contains_batch(&points, OverlayRule::Xor);
+        for (p, &c) in points.iter().zip(contains.iter()) {
+            assert_eq!(c, query.contains(*p, OverlayRule::Xor));
+        }
+    }
+
+    /// The winding number of the contours at a point that is not on their edges.
+    fn reference_winding(contours: &[IntPath], p: IntPoint) -> i32 {
+        let mut winding = 0;
+        for contour in contours.iter() {
+            let mut a = contour[contour.len() - 1];
+            for &b in contour.iter() {
+                let cross = (b.x as i64 - a.x as i64) * (p.y as i64 - a.y as i64) - (b.y as i64 - a.y as i64) * (p.x as i64 - a.x as i64);
+                if a.y <= p.y && p.y < b.y && cross > 0 {
+                    winding += 1;
+                } else if b.y <= p.y && p.y < a.y && cross < 0 {
+                    winding -= 1;
+                }
+                a = b;
+            }
+        }
+        winding
+    }
+
+    fn is_on_edge(contours: &[IntPath], p: IntPoint) -> bool {
+        contours.iter().any(|contour| {
+            let mut a = contour[contour.len() - 1];
+            contour.iter().any(|&b| {
+                let cross = (b.x as i64 - a.x as i64) * (p.y as i64 - a.y as i64) - (b.y as i64 - a.y as i64) * (p.x as i64 - a.x as i64);
+                let is_on = cross == 0 && a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y);
+                a = b;
+                is_on
+            })
+        })
+    }
+
+    #[test]
+    fn test_random_against_reference() {
+        let mut rng = rand::rng();
+        for _ in 0..10 {
+            // axis-aligned edges cross on the grid, so the split keeps the input geometry exactly
+            let random_paths = |rng: &mut rand::rngs::ThreadRng| -> Vec<IntPath> {
+                (0..20)
+                    .map(|_| {
+                        let xs: Vec<i32> = (0..6).map(|_| rng.random_range(-100..=100)).collect();
+                        let ys: Vec<i32> = (0..6).map(|_| rng.random_range(-100..=100)).collect();
+                        (0..6).flat_map(|i| [IntPoint::new(xs[i], ys[i]), IntPoint::new(xs[(i + 1) % 6], ys[i])]).collect()
+                    })
+                    .collect()
+            };
+            let subj = random_paths(&mut rng);
+            let clip = random_paths(&mut rng);
+            let query = Overlay::with_contours(&subj, &clip).build_point_query(FillRule::NonZero);
+
+            let points: Vec<IntPoint> = (0..2000)
+                .map(|_| IntPoint::new(rng.random_range(-110..=110), rng.random_range(-110..=110)))
+                .collect();
+            let batch = query.winding_batch(&points);
+            for (&p, w) in points.iter().zip(batch.iter()) {
+                // edges that cancel each other are removed by the split, so a point on them is not a boundary
+                if is_on_edge(&subj, p) || is_on_edge(&clip, p) {
+                    continue;
+                }
+                assert!(!w.is_boundary, "{:?}", p);
+                assert_eq!((w.subj, w.clip), (reference_winding(&subj, p), reference_winding(&clip, p)), "{:?}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_grid() {
+        // 40 000 squares, a linear scan per query would take too long
+        let n = 200;
+        let shapes: Vec<Vec<IntPath>> = (0..n * n)
+            .map(|i| vec![rect(10 * (i % n), 10 * (i / n), 10 * (i % n) + 8, 10 * (i / n) + 8)])
+            .collect();
+        let query = PointQuery::with_shapes(&shapes, FillRule::NonZero);
+
+        let mut rng = rand::rng();
+        for _ in 0..100_000 {
+            let p = IntPoint::new(rng.random_range(-5..10 * n + 5), rng.random_range(-5..10 * n + 5));
+            let (cx, cy) = (p.x.div_euclid(10), p.y.div_euclid(10));
+            let (rx, ry) = (p.x.rem_euclid(10), p.y.rem_euclid(10));
+            let is_in_grid = 0 <= cx && cx < n && 0 <= cy && cy < n;
+            let is_inside = is_in_grid && 0 < rx && rx < 8 && 0 < ry && ry < 8;
+            let is_boundary = is_in_grid && rx <= 8 && ry <= 8 && !is_inside;
+
+            let expected = if is_inside { Some((cy * n + cx) as usize) } else { None };
+            assert_eq!(query.shape_index(p), expected, "{:?}", p);
+            assert_eq!(query.winding(p).is_boundary, is_boundary, "{:?}", p);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::string::line::IntLine;
+    use i_overlay::string::noding::LineNoder;
+    use i_shape::int::area::Area;
+    use i_shape::int::path::{ContourExtension, IntPath};
+    use rand::Rng;
+
+    fn line(a: [i32; 2], b: [i32; 2]) -> IntLine {
+        [IntPoint::new(a[0], a[1]), IntPoint::new(b[0], b[1])]
+    }
+
+    #[test]
+    fn test_square() {
+        let mut noder = LineNoder::new(4);
+        noder.add_contour(&rect(0, 0, 10, 10));
+        let result = noder.polygonize();
+
+        assert_eq!(result.shapes.len(), 1);
+        assert_eq!(result.shapes[0].len(), 1);
+        assert!(!result.shapes[0][0].is_clockwise_ordered());
+        assert_eq!(result.shapes.area_two().abs(), 200);
+        assert!(result.dangles.is_empty());
+        assert!(result.cut_edges.is_empty());
+    }
+
+    #[test]
+    fn test_loose_segments() {
+        // two squares sharing a side, given as separate unordered segments that overrun each other
+        let lines = [
+            line([0, 0], [20, 0]),
+            line([20, 10], [-5, 10]),
+            line([0, 12], [0, -2]),
+            line([10, 0], [10, 10]),
+            line([20, 10], [20, 0]),
+        ];
+        let mut noder = LineNoder::new(lines.len());
+        noder.add_lines(&lines);
+        let result = noder.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        assert!(result.shapes.iter().all(|shape| shape.len() == 1 && shape.area_two().abs() == 200));
+
+        let mut dangles = result.dangles.clone();
+        dangles.sort();
+        assert_eq!(dangles, vec![line([-5, 10], [0, 10]), line([0, -2], [0, 0]), line([0, 10], [0, 12])]);
+    }
+
+    #[test]
+    fn test_hole() {
+        let mut noder = LineNoder::new(8);
+        noder.add_contour(&rect(0, 0, 30, 30));
+        noder.add_contour(&rect(10, 10, 20, 20));
+        let result = noder.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        let outer = result.shapes.iter().find(|shape| shape.len() == 2).unwrap();
+        assert!(outer[1].is_clockwise_ordered());
+        assert_eq!(outer.area_two().abs(), 2 * (900 - 100));
+
+        let inner = result.shapes.iter().find(|shape| shape.len() == 1).unwrap();
+        assert_eq!(inner.area_two().abs(), 200);
+    }
+
+    #[test]
+    fn test_cut_edge() {
+        let mut noder = LineNoder::new(10);
+        noder.add_contour(&rect(0, 0, 10, 10));
+        noder.add_contour(&rect(20, 0, 30, 10));
+        noder.add_line([IntPoint::new(10, 5), IntPoint::new(20, 5)]);
+        noder.add_line([IntPoint::new(30, 5), IntPoint::new(40, 5)]);
+        let result = noder.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        assert_eq!(result.cut_edges, vec![line([10, 5], [20, 5])]);
+        assert_eq!(result.dangles, vec![line([30, 5], [40, 5])]);
+    }
+
+    #[test]
+    fn test_cut_edge_inside() {
+        // a square in a square connected by a bridge, the bridge is a cut edge of the ring between them
+        let mut noder = LineNoder::new(10);
+        noder.add_contour(&rect(0, 0, 30, 30));
+        noder.add_contour(&rect(10, 10, 20, 20));
+        noder.add_line([IntPoint::new(0, 15), IntPoint::new(10, 15)]);
+        let result = noder.polygonize();
+
+        assert_eq!(result.cut_edges, vec![line([0, 15], [10, 15])]);
+        assert_eq!(result.shapes.len(), 2);
+        assert!(result.shapes.iter().any(|shape| shape.len() == 2));
+    }
+
+    #[test]
+    fn test_empty() {
+        let result = LineNoder::new(0).polygonize();
+        assert!(result.shapes.is_empty());
+
+        let mut noder = LineNoder::new(2);
+        noder.add_line(line([0, 0], [10, 0]));
+        noder.add_line(line([10, 0], [10, 10]));
+        let result = noder.polygonize();
+        assert!(result.shapes.is_empty());
+        assert_eq!(result.dangles.len(), 2);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let contours: Vec<IntPath> = (0..3)
+                .map(|_| {
+                    let (x, y) = (rng.random_range(-20..20), rng.random_range(-20..20));
+                    rect(x, y, x + rng.random_range(1..20), y + rng.random_range(1..20))
+                })
+                .collect();
+
+            let mut noder = LineNoder::new(12);
+            noder.add_contours(&contours);
+            let result = noder.polygonize();
+
+            assert!(result.dangles.is_empty());
+            assert!(result.cut_edges.is_empty());
+
+            // the faces cover the union of the rects without overlaps
+            let union = Overlay::with_contours(&contours, &[]).overlay(OverlayRule::Subject, FillRule::NonZero);
+            let faces_area: i64 = result.shapes.iter().map(|shape| shape.area_two().abs()).sum();
+            assert_eq!(faces_area, union.area_two().abs());
+
+            let faces: Vec<IntPath> = result.shapes.iter().flatten().cloned().collect();
+            let faces_union = Overlay::with_contours(&faces, &[]).overlay(OverlayRule::Subject, FillRule::EvenOdd);
+            assert_eq!(faces_union.area_two().abs(), union.area_two().abs());
+        }
+    }
+}
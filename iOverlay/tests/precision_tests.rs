@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::clip::FloatClip;
+    use i_overlay::float::error::FloatInputError;
+    use i_overlay::float::precision::FixedPrecision;
+    use i_overlay::float::single::SingleFloatOverlay;
+    use i_overlay::float::slice::FloatSlice;
+    use i_overlay::mesh::outline::offset::OutlineOffset;
+    use i_overlay::mesh::stroke::offset::StrokeOffset;
+    use i_overlay::mesh::style::{OutlineStyle, StrokeStyle};
+    use i_overlay::string::clip::ClipRule;
+    use rand::Rng;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    }
+
+    fn is_on_grid(precision: &FixedPrecision<f64>, points: &[[f64; 2]]) -> bool {
+        points.iter().all(|p| precision.snap(p) == *p)
+    }
+
+    #[test]
+    fn test_shared_vertices() {
+        let precision = FixedPrecision::with_origin(1.0e-6, 1000.0, 2000.0);
+
+        let subj = square(1000.1234567, 2000.7654321, 0.5);
+        let near = square(1000.3333333, 2000.9, 0.5);
+        let far = square(1200.0000001, 2200.0000003, 100.0);
+
+        // different bounds, so the automatic grids are different
+        let union = subj.overlay_with_precision(&near, OverlayRule::Union, FillRule::NonZero, &precision).unwrap();
+        let xor = subj.overlay_with_precision(&far, OverlayRule::Xor, FillRule::NonZero, &precision).unwrap();
+
+        let corner = precision.snap(&subj[0]);
+        assert!(union.iter().flatten().flatten().any(|p| *p == corner));
+        assert!(xor.iter().flatten().flatten().any(|p| *p == corner));
+
+        for shapes in [&union, &xor] {
+            for contour in shapes.iter().flatten() {
+                assert!(is_on_grid(&precision, contour));
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_operations() {
+        let precision = FixedPrecision::new(1.0 / 1024.0);
+        let shape = square(0.1, 0.1, 3.3);
+        let line = vec![[-1.05, 1.7], [4.45, 1.9]];
+
+        let sliced = shape.slice_by_with_precision(&line, FillRule::NonZero, &precision).unwrap();
+        assert_eq!(sliced.len(), 2);
+
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+        let clipped = line.clip_by_with_precision(&shape, FillRule::NonZero, clip_rule, &precision).unwrap();
+        assert_eq!(clipped.len(), 1);
+
+        let stroke = line.stroke_with_precision(StrokeStyle::new(0.3), false, &precision).unwrap();
+        assert_eq!(stroke.len(), 1);
+
+        let outline = shape.outline_with_precision(&OutlineStyle::new(0.7), &precision).unwrap();
+        assert_eq!(outline.len(), 1);
+
+        for contour in sliced.iter().chain(stroke.iter()).chain(outline.iter()).flatten() {
+            assert!(is_on_grid(&precision, contour));
+        }
+        for path in clipped.iter() {
+            assert!(is_on_grid(&precision, path));
+        }
+
+        // the sliced parts and the clipped line share the cut
+        let end = *clipped[0].last().unwrap();
+        assert!(sliced.iter().flatten().flatten().any(|p| *p == end));
+    }
+
+    #[test]
+    fn test_snap_same_as_operation() {
+        // 2.15 / 0.1 is rounded down, but 2.15 * (1 / 0.1) is rounded up as the operations do
+        let precision = FixedPrecision::new(0.1);
+        let shape = square(2.15, 2.15, 1.0);
+        let result = shape.overlay_with_precision(&shape, OverlayRule::Union, FillRule::NonZero, &precision).unwrap();
+        let snapped: Vec<_> = shape.iter().map(|p| precision.snap(p)).collect();
+        assert_eq!(snapped[0], [2.2, 2.2]);
+        assert!(result[0][0].iter().all(|p| snapped.contains(p)));
+
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let shape = square(rng.random_range(-50.0..50.0), rng.random_range(-50.0..50.0), rng.random_range(1.0..5.0));
+            let result = shape.overlay_with_precision(&shape, OverlayRule::Union, FillRule::NonZero, &precision).unwrap();
+            let snapped: Vec<_> = shape.iter().map(|p| precision.snap(p)).collect();
+            assert_eq!(result[0][0].len(), 4);
+            assert!(result[0][0].iter().all(|p| snapped.contains(p)));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let precision = FixedPrecision::new(1.0e-6);
+        let subj = square(0.0, 0.0, 1.0);
+        let clip = square(0.5, 0.5, 1.0);
+
+        let result = subj.overlay_with_precision(&clip, OverlayRule::Union, FillRule::NonZero, &precision);
+        assert!(result.is_ok());
+
+        let far = square(5000.0, 0.0, 1.0);
+        let result = subj.overlay_with_precision(&far, OverlayRule::Union, FillRule::NonZero, &precision);
+        assert_eq!(result, Err(FloatInputError::OutOfGrid));
+
+        let result = subj.overlay_with_precision(&clip, OverlayRule::Union, FillRule::NonZero, &FixedPrecision::new(-1.0));
+        assert_eq!(result, Err(FloatInputError::InvalidPrecision));
+
+        let mut bad = clip.clone();
+        bad[2][1] = f64::NAN;
+        let result = subj.overlay_with_precision(&bad, OverlayRule::Union, FillRule::NonZero, &precision);
+        assert_eq!(result, Err(FloatInputError::NonFinitePoint { source: 1, path: 0, point: 2 }));
+
+        // the stroke goes beyond the input
+        let line = vec![[0.0, 0.0], [1000.0, 0.0]];
+        let result = line.stroke_with_precision(StrokeStyle::new(300.0), false, &precision);
+        assert_eq!(result, Err(FloatInputError::OutOfGrid));
+    }
+
+    #[test]
+    fn test_random() {
+        let precision = FixedPrecision::with_origin(0.01, -50.0, 50.0);
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let a = square(rng.random_range(-60.0..-40.0), rng.random_range(40.0..60.0), rng.random_range(0.1..5.0));
+            let b = square(rng.random_range(-60.0..-40.0), rng.random_range(40.0..60.0), rng.random_range(0.1..5.0));
+            let c = square(rng.random_range(-60.0..-40.0), rng.random_range(40.0..60.0), rng.random_range(0.1..5.0));
+
+            let ab = a.overlay_with_precision(&b, OverlayRule::Difference, FillRule::NonZero, &precision).unwrap();
+            let ac = a.overlay_with_precision(&c, OverlayRule::Union, FillRule::NonZero, &precision).unwrap();
+
+            for contour in ab.iter().chain(ac.iter()).flatten() {
+                assert!(is_on_grid(&precision, contour));
+            }
+        }
+    }
+}
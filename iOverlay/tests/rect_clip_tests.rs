@@ -0,0 +1,261 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_float::int::rect::IntRect;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::rect_clip::{RectClip, RectClipLines, TileGrid};
+    use i_shape::int::area::Area;
+    use i_shape::int::path::IntPath;
+    use i_shape::int::shape::IntShapes;
+    use rand::Rng;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn intersect(shapes: &IntShapes, clip: &IntRect, fill_rule: FillRule) -> IntShapes {
+        let mut clip = rect(clip.min_x, clip.min_y, clip.max_x, clip.max_y);
+        if fill_rule == FillRule::Negative {
+            clip.reverse();
+        }
+        let contours: Vec<IntPath> = shapes.iter().flatten().cloned().collect();
+        Overlay::with_contours(&contours, &[clip]).overlay(OverlayRule::Intersect, fill_rule)
+    }
+
+    // the start point of a contour depends on the graph, so both results are rotated to the min point
+    fn normalize(mut shapes: IntShapes) -> IntShapes {
+        for contour in shapes.iter_mut().flatten() {
+            let start = (0..contour.len()).min_by_key(|&i| contour[i]).unwrap_or(0);
+            contour.rotate_left(start);
+        }
+        shapes
+    }
+
+    fn random_rectilinear(rng: &mut impl Rng, count: usize) -> IntShapes {
+        (0..count)
+            .map(|_| {
+                let (x, y) = (rng.random_range(-20..20), rng.random_range(-20..20));
+                vec![rect(x, y, x + rng.random_range(1..20), y + rng.random_range(1..20))]
+            })
+            .collect()
+    }
+
+    fn random_polygon(rng: &mut impl Rng, count: usize) -> IntPath {
+        (0..count)
+            .map(|_| IntPoint::new(rng.random_range(-1000..1000), rng.random_range(-1000..1000)))
+            .collect()
+    }
+
+    #[test]
+    fn test_square() {
+        let shapes = vec![vec![rect(0, 0, 10, 10)]];
+        let result = shapes.clip_by_rect(&IntRect::new(5, 20, -5, 5), FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.area_two().abs(), 2 * 25);
+        assert_eq!(result, intersect(&shapes, &IntRect::new(5, 20, -5, 5), FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_inside_and_outside() {
+        let shapes = vec![vec![rect(2, 2, 4, 4)], vec![rect(20, 20, 30, 30)]];
+        let clip = IntRect::new(0, 10, 0, 10);
+        let result = shapes.clip_by_rect(&clip, FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result, intersect(&shapes, &clip, FillRule::NonZero));
+
+        // the rect is inside the shape
+        let shapes = vec![vec![rect(-100, -100, 100, 100)]];
+        let result = shapes.clip_by_rect(&clip, FillRule::NonZero);
+        assert_eq!(result.area_two().abs(), 2 * 100);
+        assert_eq!(result, intersect(&shapes, &clip, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_hole() {
+        let mut hole = rect(-5, -5, 5, 5);
+        hole.reverse();
+        let shapes = vec![vec![rect(-20, -20, 20, 20), hole]];
+        let clip = IntRect::new(-10, 10, -10, 10);
+        let result = shapes.clip_by_rect(&clip, FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+        assert_eq!(result, intersect(&shapes, &clip, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let shapes = vec![vec![path(&[[0, 0], [10, 0], [0, 10]])]];
+        let clip = IntRect::new(-5, 5, -5, 5);
+        let result = shapes.clip_by_rect(&clip, FillRule::NonZero);
+        assert_eq!(result[0][0].len(), 4);
+        assert_eq!(result.area_two().abs(), 2 * 25);
+        assert_eq!(result, intersect(&shapes, &clip, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_rounded_crossing() {
+        // the edge from (4, -13) to (1, 5) crosses the bottom side at x = 1.83
+        let shapes = vec![vec![path(&[[1, 5], [3, 0], [4, -13]])]];
+        let clip = IntRect::new(0, 10, 0, 10);
+        let result = shapes.clip_by_rect(&clip, FillRule::NonZero);
+
+        // the crossing is snapped to the rectangle corner, the same as in the general overlay
+        assert_eq!(normalize(result.clone()), vec![vec![path(&[[0, 0], [3, 0], [1, 5]])]]);
+        assert_eq!(result, intersect(&shapes, &clip, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let paths = [path(&[[-2_000_000_000, -1], [2_000_000_000, 1]])];
+        let result = paths.clip_lines_by_rect(&IntRect::new(-10, 10, -10, 10), false);
+        assert_eq!(result, vec![path(&[[-10, 0], [10, 0]])]);
+
+        let shapes = [vec![path(&[[-1_000_000_000, -1_000_000_000], [1_000_000_000, -1_000_000_000], [0, 1_000_000_000]])]];
+        let result = shapes.clip_by_rect(&IntRect::new(-10, 10, -10, 10), FillRule::NonZero);
+        assert_eq!(result.area_two().abs(), 2 * 400);
+        assert_eq!(result, intersect(&shapes.to_vec(), &IntRect::new(-10, 10, -10, 10), FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_random_rectilinear() {
+        let mut rng = rand::rng();
+        let rules = [FillRule::NonZero, FillRule::EvenOdd, FillRule::Positive];
+        for _ in 0..1000 {
+            let shapes = random_rectilinear(&mut rng, 4);
+            let (x, y) = (rng.random_range(-20..20), rng.random_range(-20..20));
+            let clip = IntRect::new(x, x + rng.random_range(1..30), y, y + rng.random_range(1..30));
+            let fill_rule = rules[rng.random_range(0..rules.len())];
+
+            let result = shapes.clip_by_rect(&clip, fill_rule);
+            assert_eq!(normalize(result), normalize(intersect(&shapes, &clip, fill_rule)));
+        }
+    }
+
+    #[test]
+    fn test_random_polygon() {
+        let mut rng = rand::rng();
+        let rules = [FillRule::NonZero, FillRule::EvenOdd, FillRule::Positive, FillRule::Negative];
+        for _ in 0..1000 {
+            let shapes = vec![vec![random_polygon(&mut rng, 8)]];
+            let (x, y) = (rng.random_range(-1000..1000), rng.random_range(-1000..1000));
+            let clip = IntRect::new(x, x + rng.random_range(1..1000), y, y + rng.random_range(1..1000));
+            let fill_rule = rules[rng.random_range(0..rules.len())];
+
+            let result = shapes.clip_by_rect(&clip, fill_rule);
+            assert_eq!(result, intersect(&shapes, &clip, fill_rule));
+        }
+    }
+
+    #[test]
+    fn test_random_scattered_polygons() {
+        // small polygons far from each other, so only some of them can reach the rectangle
+        let mut rng = rand::rng();
+        let rules = [FillRule::NonZero, FillRule::EvenOdd, FillRule::Positive, FillRule::Negative];
+        for _ in 0..1000 {
+            let shapes: IntShapes = (0..8)
+                .map(|_| {
+                    let (x, y) = (rng.random_range(-1000..1000), rng.random_range(-1000..1000));
+                    let contour = (0..5)
+                        .map(|_| IntPoint::new(x + rng.random_range(0..300), y + rng.random_range(0..300)))
+                        .collect();
+                    vec![contour]
+                })
+                .collect();
+            let (x, y) = (rng.random_range(-1000..1000), rng.random_range(-1000..1000));
+            let clip = IntRect::new(x, x + rng.random_range(1..500), y, y + rng.random_range(1..500));
+            let fill_rule = rules[rng.random_range(0..rules.len())];
+
+            let result = shapes.clip_by_rect(&clip, fill_rule);
+            assert_eq!(result, intersect(&shapes, &clip, fill_rule));
+        }
+    }
+
+    #[test]
+    fn test_tiles() {
+        let grid = TileGrid::new(IntPoint::new(0, 0), 10, 10, 3, 2);
+        let shapes = vec![vec![path(&[[-5, 5], [25, -5], [35, 15], [5, 25]])]];
+        let tiles = grid.split_shapes(&shapes, FillRule::NonZero);
+        assert_eq!(tiles.len(), 6);
+
+        for row in 0..2 {
+            for column in 0..3 {
+                let clip = grid.tile_rect(column, row);
+                let expected = shapes.clip_by_rect(&clip, FillRule::NonZero);
+                assert_eq!(tiles[row * 3 + column], expected);
+            }
+        }
+
+        // the tiles together cover the same area as the whole grid clip
+        let total: i64 = tiles.iter().map(|tile| tile.area_two()).sum();
+        let whole = shapes.clip_by_rect(&IntRect::new(0, 30, 0, 20), FillRule::NonZero);
+        assert!((total - whole.area_two()).abs() <= 2 * 30);
+    }
+
+    #[test]
+    fn test_random_tiles() {
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let shapes = vec![vec![random_polygon(&mut rng, 8)], vec![random_polygon(&mut rng, 5)]];
+            let size = rng.random_range(50..500);
+            let grid = TileGrid::new(IntPoint::new(-1000, -1000), size, size, 2000 / size as usize, 2000 / size as usize);
+
+            let tiles = grid.split_shapes(&shapes, FillRule::NonZero);
+            for row in 0..grid.rows {
+                for column in 0..grid.columns {
+                    let clip = grid.tile_rect(column, row);
+                    let expected = intersect(&shapes, &clip, FillRule::NonZero);
+                    assert_eq!(tiles[row * grid.columns + column], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let clip = IntRect::new(0, 10, 0, 10);
+        let lines = [
+            path(&[[-5, 5], [15, 5]]),
+            path(&[[2, 2], [4, 2], [4, 20], [6, 20], [6, 4], [8, 4]]),
+            path(&[[20, 20], [30, 30]]),
+        ];
+        let result = lines.clip_lines_by_rect(&clip, true);
+        assert_eq!(
+            result,
+            vec![
+                path(&[[0, 5], [10, 5]]),
+                path(&[[2, 2], [4, 2], [4, 10]]),
+                path(&[[6, 10], [6, 4], [8, 4]]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines_on_boundary() {
+        let clip = IntRect::new(0, 10, 0, 10);
+        let lines = [path(&[[-5, 0], [5, 0], [5, 5]])];
+
+        let included = lines.clip_lines_by_rect(&clip, true);
+        assert_eq!(included, vec![path(&[[0, 0], [5, 0], [5, 5]])]);
+
+        let excluded = lines.clip_lines_by_rect(&clip, false);
+        assert_eq!(excluded, vec![path(&[[5, 0], [5, 5]])]);
+    }
+
+    #[test]
+    fn test_lines_diagonal() {
+        let clip = IntRect::new(0, 10, 0, 10);
+        let lines = [path(&[[-10, 0], [20, 30]])];
+        let result = lines.clip_lines_by_rect(&clip, true);
+        // touches the corner only
+        assert!(result.is_empty());
+
+        let lines = [path(&[[-10, -5], [20, 10]])];
+        let result = lines.clip_lines_by_rect(&clip, true);
+        assert_eq!(result, vec![path(&[[0, 0], [10, 5]])]);
+    }
+}
@@ -0,0 +1,149 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::Overlay;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::relate::{Dimension, IntersectionMatrix, Location};
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    fn relate(subj: &[IntPath], clip: &[IntPath]) -> IntersectionMatrix {
+        Overlay::with_contours(subj, clip).relate(FillRule::NonZero)
+    }
+
+    #[test]
+    fn test_disjoint() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(20, 0, 30, 10)]);
+        assert_eq!(matrix.to_string(), "FF2FF1212");
+        assert!(matrix.disjoint());
+        assert!(!matrix.intersects());
+        assert!(!matrix.touches());
+    }
+
+    #[test]
+    fn test_touch_point() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(10, 10, 20, 20)]);
+        assert_eq!(matrix.to_string(), "FF2F01212");
+        assert!(matrix.touches());
+        assert!(matrix.intersects());
+        assert!(!matrix.overlaps());
+    }
+
+    #[test]
+    fn test_touch_edge() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(10, 5, 20, 20)]);
+        assert_eq!(matrix.to_string(), "FF2F11212");
+        assert!(matrix.touches());
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(5, 5, 15, 15)]);
+        assert_eq!(matrix.to_string(), "212101212");
+        assert!(matrix.overlaps());
+        assert!(!matrix.contains());
+        assert!(!matrix.within());
+        assert!(!matrix.touches());
+    }
+
+    #[test]
+    fn test_contains() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(2, 2, 8, 8)]);
+        assert_eq!(matrix.to_string(), "212FF1FF2");
+        assert!(matrix.contains());
+        assert!(matrix.covers());
+        assert!(!matrix.within());
+        assert!(!matrix.overlaps());
+
+        let matrix = relate(&[rect(2, 2, 8, 8)], &[rect(0, 0, 10, 10)]);
+        assert!(matrix.within());
+        assert!(matrix.covered_by());
+        assert!(!matrix.contains());
+    }
+
+    #[test]
+    fn test_covers_with_common_edge() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(0, 0, 5, 10)]);
+        assert_eq!(matrix.to_string(), "212F11FF2");
+        assert!(matrix.covers());
+        assert!(matrix.contains());
+        assert!(!matrix.equals());
+    }
+
+    #[test]
+    fn test_equals() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(0, 0, 10, 10)]);
+        assert_eq!(matrix.to_string(), "2FFF1FFF2");
+        assert!(matrix.equals());
+        assert!(matrix.contains());
+        assert!(matrix.within());
+    }
+
+    #[test]
+    fn test_hole() {
+        // the clip lies in the hole of the subject and touches it
+        let mut hole = rect(10, 10, 20, 20);
+        hole.reverse();
+        let subj = [rect(0, 0, 30, 30), hole];
+        let matrix = relate(&subj, &[rect(10, 10, 20, 15)]);
+        assert!(matrix.touches());
+        assert_eq!(matrix.get(Location::Boundary, Location::Boundary), Dimension::Line);
+        assert_eq!(matrix.get(Location::Exterior, Location::Interior), Dimension::Area);
+    }
+
+    #[test]
+    fn test_empty() {
+        let matrix = relate(&[], &[]);
+        assert_eq!(matrix, IntersectionMatrix::empty());
+        assert!(matrix.disjoint());
+
+        let matrix = relate(&[], &[rect(0, 0, 10, 10)]);
+        assert_eq!(matrix.to_string(), "FFFFFF212");
+    }
+
+    #[test]
+    fn test_pattern() {
+        let matrix = relate(&[rect(0, 0, 10, 10)], &[rect(5, 5, 15, 15)]);
+        assert!(matrix.matches("T*T***T**"));
+        assert!(matrix.matches("2121*1212"));
+        assert!(!matrix.matches("F********"));
+        assert!(!matrix.matches("T*T"));
+        assert!(!matrix.matches("X********"));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let subj: Vec<IntPath> = (0..2)
+                .map(|_| {
+                    let (x, y) = (rng.random_range(-10..10), rng.random_range(-10..10));
+                    rect(x, y, x + rng.random_range(1..10), y + rng.random_range(1..10))
+                })
+                .collect();
+            let clip: Vec<IntPath> = (0..2)
+                .map(|_| {
+                    let (x, y) = (rng.random_range(-10..10), rng.random_range(-10..10));
+                    rect(x, y, x + rng.random_range(1..10), y + rng.random_range(1..10))
+                })
+                .collect();
+
+            let matrix = relate(&subj, &clip);
+
+            let is_filled = |rule: OverlayRule| !Overlay::with_contours(&subj, &clip).overlay(rule, FillRule::NonZero).is_empty();
+
+            let ii = is_filled(OverlayRule::Intersect);
+            let ie = is_filled(OverlayRule::Difference);
+            let ei = is_filled(OverlayRule::InverseDifference);
+
+            assert_eq!(matrix.get(Location::Interior, Location::Interior) == Dimension::Area, ii);
+            assert_eq!(matrix.get(Location::Interior, Location::Exterior) == Dimension::Area, ie);
+            assert_eq!(matrix.get(Location::Exterior, Location::Interior) == Dimension::Area, ei);
+            assert_eq!(matrix.within(), ii && !ie);
+            assert_eq!(matrix.contains(), ii && !ei);
+        }
+    }
+}
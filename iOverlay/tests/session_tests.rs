@@ -0,0 +1,106 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::session::OverlaySession;
+    use i_shape::int::path::{ContourExtension, IntPath};
+    use i_shape::int::shape::IntShapes;
+    use rand::Rng;
+
+    fn area(shapes: &IntShapes) -> i64 {
+        shapes.iter().flatten().map(|contour| contour.unsafe_area()).sum::<i64>().abs() / 2
+    }
+
+    fn full(contours: &[(IntPath, ShapeType)], rule: OverlayRule, fill_rule: FillRule) -> IntShapes {
+        let mut overlay = Overlay::new(64);
+        for (contour, shape_type) in contours.iter() {
+            overlay.add_contour(contour, *shape_type);
+        }
+        overlay.overlay(rule, fill_rule)
+    }
+
+    #[test]
+    fn test_split_cluster() {
+        let mut session = OverlaySession::new(OverlayRule::Union, FillRule::NonZero);
+        let a = session.add_contour(rect(0, 0, 10, 10), ShapeType::Subject);
+        session.add_contour(rect(20, 0, 30, 10), ShapeType::Subject);
+        // a bridge joins both squares
+        let bridge = session.add_contour(rect(5, 4, 25, 6), ShapeType::Subject);
+
+        assert_eq!(session.overlay().len(), 1);
+
+        assert!(session.remove_contour(bridge));
+        assert!(!session.remove_contour(bridge));
+        assert_eq!(session.overlay().len(), 2);
+
+        assert!(session.replace_contour(a, rect(15, 0, 25, 10)));
+        let result = session.overlay();
+        assert_eq!(result.len(), 1);
+        assert_eq!(area(&result), 150);
+        assert_eq!(session.len(), 2);
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut session = OverlaySession::new(OverlayRule::Difference, FillRule::NonZero);
+        session.add_contour(rect(0, 0, 10, 10), ShapeType::Subject);
+        session.add_contour(rect(5, 0, 15, 10), ShapeType::Clip);
+        session.add_contour(rect(100, 0, 110, 10), ShapeType::Clip);
+
+        assert_eq!(area(&session.overlay()), 50);
+
+        session.set_rules(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(area(&session.overlay()), 250);
+    }
+
+    #[test]
+    fn test_random_edits() {
+        let rules = [
+            OverlayRule::Subject,
+            OverlayRule::Clip,
+            OverlayRule::Intersect,
+            OverlayRule::Union,
+            OverlayRule::Difference,
+            OverlayRule::InverseDifference,
+            OverlayRule::Xor,
+        ];
+        let mut rng = rand::rng();
+        for &rule in rules.iter() {
+            let mut session = OverlaySession::new(rule, FillRule::EvenOdd);
+            let mut contours: Vec<(usize, IntPath, ShapeType)> = Vec::new();
+            for _ in 0..100 {
+                let x = rng.random_range(0..200);
+                let y = rng.random_range(0..200);
+                let w = rng.random_range(1..30);
+                let h = rng.random_range(1..30);
+                let contour = rect(x, y, x + w, y + h);
+                match rng.random_range(0..3) {
+                    0 if !contours.is_empty() => {
+                        let (id, _, _) = contours.swap_remove(rng.random_range(0..contours.len()));
+                        session.remove_contour(id);
+                    }
+                    1 if !contours.is_empty() => {
+                        let i = rng.random_range(0..contours.len());
+                        session.replace_contour(contours[i].0, contour.clone());
+                        contours[i].1 = contour;
+                    }
+                    _ => {
+                        let shape_type = if rng.random_bool(0.5) { ShapeType::Subject } else { ShapeType::Clip };
+                        let id = session.add_contour(contour.clone(), shape_type);
+                        contours.push((id, contour, shape_type));
+                    }
+                }
+
+                let all: Vec<_> = contours.iter().map(|(_, c, t)| (c.clone(), *t)).collect();
+                let expected = full(&all, rule, FillRule::EvenOdd);
+                let result = session.overlay();
+                assert_eq!(result.len(), expected.len());
+                assert_eq!(area(&result), area(&expected));
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::source::EdgeSource;
+    use rand::Rng;
+
+    #[test]
+    fn test_difference() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour_with_id(&rect(0, 0, 10, 10), ShapeType::Subject, 1);
+        overlay.add_contour_with_id(&rect(5, -5, 15, 15), ShapeType::Clip, 2);
+
+        let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].len(), 1);
+
+        let contour = &shapes[0][0];
+        let edges = &sources[0][0];
+        assert_eq!(contour.len(), edges.len());
+
+        let subj = EdgeSource { id: 1, shape_type: ShapeType::Subject };
+        let clip = EdgeSource { id: 2, shape_type: ShapeType::Clip };
+
+        for (i, edge_sources) in edges.iter().enumerate() {
+            let a = contour[i];
+            let b = contour[(i + 1) % contour.len()];
+            if a.x == 5 && b.x == 5 {
+                assert_eq!(edge_sources, &vec![clip]);
+            } else {
+                assert_eq!(edge_sources, &vec![subj]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_edge() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour_with_id(&rect(0, 0, 10, 10), ShapeType::Subject, 3);
+        overlay.add_contour_with_id(&rect(0, 0, 10, 20), ShapeType::Clip, 4);
+
+        let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Intersect, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        let contour = &shapes[0][0];
+        let edges = &sources[0][0];
+
+        for (i, edge_sources) in edges.iter().enumerate() {
+            let a = contour[i];
+            let b = contour[(i + 1) % contour.len()];
+            if a.y == 10 && b.y == 10 {
+                // the top edge comes only from the subject
+                assert_eq!(edge_sources.len(), 1);
+                assert_eq!(edge_sources[0].id, 3);
+            } else {
+                // other edges are shared by both
+                assert_eq!(edge_sources.len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_without_id() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour(&rect(0, 0, 10, 10), ShapeType::Subject);
+        overlay.add_contour_with_id(&rect(5, 0, 15, 10), ShapeType::Clip, 9);
+
+        let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Union, FillRule::NonZero);
+
+        let contour = &shapes[0][0];
+        let edges = &sources[0][0];
+        for (i, edge_sources) in edges.iter().enumerate() {
+            let a = contour[i];
+            let b = contour[(i + 1) % contour.len()];
+            if a.x == 0 && b.x == 0 {
+                assert!(edge_sources.is_empty());
+            }
+            if a.x == 15 && b.x == 15 {
+                assert_eq!(edge_sources[0].id, 9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour_with_id(&rect(0, 0, 10, 10), ShapeType::Subject, 5);
+        overlay.add_contour_with_id(&rect(5, 5, 15, 15), ShapeType::Clip, 6);
+
+        let (shapes, sources) = overlay.build_shape_vectors_with_sources(FillRule::NonZero, OverlayRule::Xor);
+
+        assert_eq!(shapes.len(), sources.len());
+        for (shape, shape_sources) in shapes.iter().zip(sources.iter()) {
+            for (path, path_sources) in shape.iter().zip(shape_sources.iter()) {
+                assert_eq!(path.len(), path_sources.len());
+                for edge_sources in path_sources.iter() {
+                    assert_eq!(edge_sources.len(), 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounded_intersection() {
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour_with_id(&[IntPoint::new(0, 0), IntPoint::new(7, 3), IntPoint::new(0, 9)], ShapeType::Subject, 1);
+        overlay.add_contour_with_id(&rect(3, -10, 20, 20), ShapeType::Clip, 2);
+
+        let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        for edge_sources in sources[0][0].iter() {
+            assert_eq!(edge_sources.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_near_edge_is_not_a_source() {
+        let square = [IntPoint::new(0, 0), IntPoint::new(1, 0), IntPoint::new(1, 1), IntPoint::new(0, 1)];
+        let triangle = [IntPoint::new(0, 0), IntPoint::new(1, 1), IntPoint::new(-5, 1)];
+
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour_with_id(&square, ShapeType::Subject, 7);
+        overlay.add_contour_with_id(&triangle, ShapeType::Clip, 9);
+
+        let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Subject, FillRule::NonZero);
+
+        let subj = EdgeSource { id: 7, shape_type: ShapeType::Subject };
+        let clip = EdgeSource { id: 9, shape_type: ShapeType::Clip };
+
+        let contour = &shapes[0][0];
+        let edges = &sources[0][0];
+        assert_eq!(contour.len(), 4);
+        for (i, edge_sources) in edges.iter().enumerate() {
+            let a = contour[i];
+            let b = contour[(i + 1) % contour.len()];
+            if a.y == 1 && b.y == 1 {
+                // the top edge lies on the triangle edge
+                assert_eq!(edge_sources, &vec![subj, clip]);
+            } else {
+                assert_eq!(edge_sources, &vec![subj]);
+            }
+        }
+    }
+
+    fn distance(m: IntPoint, p: IntPoint, q: IntPoint) -> f64 {
+        let (ex, ey) = ((q.x - p.x) as f64, (q.y - p.y) as f64);
+        let (px, py) = ((m.x - p.x) as f64, (m.y - p.y) as f64);
+        let sqr_len = ex * ex + ey * ey;
+        let t = if sqr_len > 0.0 { ((px * ex + py * ey) / sqr_len).clamp(0.0, 1.0) } else { 0.0 };
+        let (dx, dy) = (px - t * ex, py - t * ey);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    #[test]
+    fn test_random_sources_lie_on_edges() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let contours: Vec<Vec<IntPoint>> = (0..4)
+                .map(|_| (0..4).map(|_| IntPoint::new(rng.random_range(-10..10), rng.random_range(-10..10))).collect())
+                .collect();
+
+            let mut overlay = Overlay::new(16);
+            for (id, contour) in contours.iter().enumerate() {
+                let shape_type = if id % 2 == 0 { ShapeType::Subject } else { ShapeType::Clip };
+                overlay.add_contour_with_id(contour, shape_type, id);
+            }
+            let (shapes, sources) = overlay.overlay_with_sources(OverlayRule::Xor, FillRule::EvenOdd);
+
+            for (shape, shape_sources) in shapes.iter().zip(sources.iter()) {
+                for (contour, edges) in shape.iter().zip(shape_sources.iter()) {
+                    assert_eq!(contour.len(), edges.len());
+                    for (i, edge_sources) in edges.iter().enumerate() {
+                        // every output edge has a source, as all contours have ids
+                        assert!(!edge_sources.is_empty());
+                        let a = contour[i];
+                        let b = contour[(i + 1) % contour.len()];
+                        for source in edge_sources.iter() {
+                            // a part of the source contour lies on the edge, maybe moved by the rounding
+                            let c = &contours[source.id];
+                            let near = (0..c.len()).any(|j| {
+                                let (p, q) = (c[j], c[(j + 1) % c.len()]);
+                                distance(a, p, q) <= 2.0 || distance(b, p, q) <= 2.0
+                            });
+                            assert!(near);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::float::clip::FloatClip;
+    use i_overlay::string::clip::{ClipRule, IntClip};
+    use i_overlay::string::overlay::StringOverlay;
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    const INSIDE: ClipRule = ClipRule { invert: false, boundary_included: false };
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn square() -> IntPath {
+        path(&[[0, 0], [10, 0], [10, 10], [0, 10]])
+    }
+
+    fn length(path: &[IntPoint]) -> f64 {
+        path.windows(2)
+            .map(|w| {
+                let (dx, dy) = ((w[1].x - w[0].x) as f64, (w[1].y - w[0].y) as f64);
+                (dx * dx + dy * dy).sqrt()
+            })
+            .sum()
+    }
+
+    fn point_at(path: &[IntPoint], arc_length: f64) -> (f64, f64) {
+        let mut rest = arc_length;
+        for w in path.windows(2) {
+            let l = length(w);
+            if rest <= l && l > 0.0 {
+                let t = rest / l;
+                return (
+                    w[0].x as f64 + (w[1].x - w[0].x) as f64 * t,
+                    w[0].y as f64 + (w[1].y - w[0].y) as f64 * t,
+                );
+            }
+            rest -= l;
+        }
+        let p = path[path.len() - 1];
+        (p.x as f64, p.y as f64)
+    }
+
+    #[test]
+    fn test_sources() {
+        let paths = [path(&[[-5, 5], [15, 5]]), path(&[[5, 15], [5, -5]])];
+        let pieces = square().clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+
+        // the pieces are split by sources even if they cross
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].source, 0);
+        assert_eq!(pieces[0].path, path(&[[0, 5], [5, 5], [10, 5]]));
+        assert_eq!((pieces[0].start, pieces[0].end), (5.0, 15.0));
+        assert_eq!(pieces[1].source, 1);
+        assert_eq!(pieces[1].path, path(&[[5, 10], [5, 5], [5, 0]]));
+        assert_eq!((pieces[1].start, pieces[1].end), (5.0, 15.0));
+    }
+
+    #[test]
+    fn test_order_along_source() {
+        // the path goes in and out of the square twice, backwards along the x axis
+        let paths = [path(&[[15, 2], [-5, 2], [-5, 8], [15, 8]])];
+        let pieces = square().clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].path, path(&[[10, 2], [0, 2]]));
+        assert_eq!((pieces[0].start, pieces[0].end), (5.0, 15.0));
+        assert_eq!(pieces[1].path, path(&[[0, 8], [10, 8]]));
+        assert_eq!((pieces[1].start, pieces[1].end), (31.0, 41.0));
+    }
+
+    #[test]
+    fn test_invert() {
+        let paths = [path(&[[-5, 5], [15, 5]])];
+        let clip_rule = ClipRule { invert: true, boundary_included: false };
+        let pieces = square().clip_paths_with_sources(&paths, FillRule::NonZero, clip_rule);
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!((pieces[0].start, pieces[0].end), (0.0, 5.0));
+        assert_eq!((pieces[1].start, pieces[1].end), (15.0, 20.0));
+    }
+
+    #[test]
+    fn test_shared_part() {
+        let paths = [path(&[[-5, 5], [5, 5]]), path(&[[15, 5], [-5, 5]])];
+        let pieces = vec![square()].clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+
+        // the common part goes to both sources
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].path, path(&[[0, 5], [5, 5]]));
+        assert_eq!(pieces[1].path, path(&[[10, 5], [5, 5], [0, 5]]));
+        assert_eq!((pieces[1].start, pieces[1].end), (5.0, 15.0));
+
+        // the common part goes to both sources in the same direction too
+        let paths = [path(&[[-5, 5], [5, 5]]), path(&[[-5, 5], [15, 5]])];
+        let pieces = vec![square()].clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].source, 0);
+        assert_eq!(pieces[0].path, path(&[[0, 5], [5, 5]]));
+        assert_eq!((pieces[0].start, pieces[0].end), (5.0, 10.0));
+        assert_eq!(pieces[1].source, 1);
+        assert_eq!(pieces[1].path, path(&[[0, 5], [5, 5], [10, 5]]));
+        assert_eq!((pieces[1].start, pieces[1].end), (5.0, 15.0));
+    }
+
+    #[test]
+    fn test_partial_overlap() {
+        // the path goes back along a part of itself, both passes are reported
+        let paths = [path(&[[2, 5], [8, 5], [4, 5], [4, 20]])];
+        let pieces = square().clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].path, path(&[[2, 5], [4, 5], [8, 5], [4, 5], [4, 10]]));
+        assert_eq!((pieces[0].start, pieces[0].end), (0.0, 15.0));
+    }
+
+    #[test]
+    fn test_shape_between_lines() {
+        // the shape is added after the first line, the lines still keep their numbers
+        let mut overlay = StringOverlay::new(8);
+        overlay.add_string_line([IntPoint::new(-5, 5), IntPoint::new(15, 5)]);
+        overlay.add_shape_contour(&square());
+        overlay.add_string_line([IntPoint::new(5, 15), IntPoint::new(5, -5)]);
+        let pieces = overlay.clip_string_lines_with_sources(FillRule::NonZero, INSIDE);
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].source, 0);
+        assert_eq!(pieces[0].path, path(&[[0, 5], [5, 5], [10, 5]]));
+        assert_eq!(pieces[1].source, 1);
+        assert_eq!(pieces[1].path, path(&[[5, 10], [5, 5], [5, 0]]));
+        assert_eq!((pieces[1].start, pieces[1].end), (5.0, 15.0));
+    }
+
+    #[test]
+    fn test_empty() {
+        let paths = [path(&[[20, 20], [30, 30]]), path(&[[5, 5]]), vec![]];
+        let pieces = square().clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+        assert!(pieces.is_empty());
+    }
+
+    #[test]
+    fn test_float() {
+        let shape: [[f64; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let paths: [Vec<[f64; 2]>; 2] = [vec![[-0.5, 0.5], [1.5, 0.5]], vec![[0.5, 1.5], [0.5, 0.75]]];
+        let pieces = paths.clip_by_with_sources(&shape, FillRule::NonZero, INSIDE);
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].source, 0);
+        assert!((pieces[0].start - 0.5).abs() < 1e-6);
+        assert!((pieces[0].end - 1.5).abs() < 1e-6);
+        assert_eq!(pieces[1].source, 1);
+        assert!((pieces[1].start - 0.5).abs() < 1e-6);
+        assert!((pieces[1].end - 0.75).abs() < 1e-6);
+        let last = pieces[1].path[pieces[1].path.len() - 1];
+        assert!((last[0] - 0.5).abs() < 1e-6 && (last[1] - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            // a large grid, so the snapped intersection points stay close to the source lines
+            let shape: IntPath = (0..5)
+                .map(|_| IntPoint::new(rng.random_range(-10_000..10_000), rng.random_range(-10_000..10_000)))
+                .collect();
+            let paths: Vec<IntPath> = (0..3)
+                .map(|_| {
+                    (0..4)
+                        .map(|_| IntPoint::new(rng.random_range(-15_000..15_000), rng.random_range(-15_000..15_000)))
+                        .collect()
+                })
+                .collect();
+
+            let pieces = shape.clip_paths_with_sources(&paths, FillRule::NonZero, INSIDE);
+            let clipped = shape.clip_paths(&paths, FillRule::NonZero, INSIDE);
+
+            // the edges are taken from the plain clip
+            let edges: Vec<[IntPoint; 2]> = clipped
+                .iter()
+                .flat_map(|path| path.windows(2).map(|w| [w[0].min(w[1]), w[0].max(w[1])]))
+                .collect();
+            for w in pieces.iter().flat_map(|piece| piece.path.windows(2)) {
+                assert!(edges.contains(&[w[0].min(w[1]), w[0].max(w[1])]));
+            }
+            // an edge shared by several sources goes to each of them, so the length is checked per source
+            let clipped_total: f64 = clipped.iter().map(|path| length(path)).sum();
+            for source in 0..paths.len() {
+                let total: f64 = pieces.iter().filter(|piece| piece.source == source).map(|piece| length(&piece.path)).sum();
+                assert!(total <= clipped_total + 1e-6);
+            }
+
+            for (i, piece) in pieces.iter().enumerate() {
+                assert!(piece.start <= piece.end);
+                if i > 0 && pieces[i - 1].source == piece.source {
+                    // the snapped intersection points can move a piece end a bit back
+                    assert!(pieces[i - 1].end <= piece.start + 2.0);
+                }
+
+                // the piece ends lie on the source at its arc lengths
+                let source = &paths[piece.source];
+                for (p, arc_length) in [(piece.path[0], piece.start), (piece.path[piece.path.len() - 1], piece.end)] {
+                    let (x, y) = point_at(source, arc_length);
+                    assert!((p.x as f64 - x).abs() <= 2.0 && (p.y as f64 - y).abs() <= 2.0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use crate::util::shape::rect;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{ContourDirection, IntOverlayOptions, Overlay};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::union_all::UnionAll;
+    use i_overlay::float::union_all::FloatUnionAll;
+    use i_shape::int::path::ContourExtension;
+    use i_shape::int::shape::{IntShape, IntShapes};
+    use rand::Rng;
+
+    fn area(shapes: &IntShapes) -> i64 {
+        shapes.iter().flatten().map(|contour| contour.unsafe_area()).sum::<i64>().abs() / 2
+    }
+
+    fn single_union(shapes: &[IntShape]) -> IntShapes {
+        Overlay::with_contours(&shapes.iter().flatten().cloned().collect::<Vec<_>>(), &[])
+            .overlay(OverlayRule::Subject, FillRule::NonZero)
+    }
+
+    #[test]
+    fn test_small() {
+        let shapes = [vec![rect(0, 0, 10, 10)], vec![rect(5, 5, 15, 15)]];
+        let result = shapes.union_all(Default::default(), Default::default());
+
+        assert_eq!(result, single_union(&shapes));
+    }
+
+    #[test]
+    fn test_grid() {
+        // overlapping squares in a grid build one square
+        let mut shapes = Vec::new();
+        for i in 0..40 {
+            for j in 0..40 {
+                shapes.push(vec![rect(10 * i, 10 * j, 10 * i + 12, 10 * j + 12)]);
+            }
+        }
+
+        let result = shapes.union_all(Default::default(), Default::default());
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        assert_eq!(area(&result), 402 * 402);
+    }
+
+    #[test]
+    fn test_holes() {
+        // rings with holes, the holes of the first column are covered by squares
+        let mut shapes = Vec::new();
+        for i in 0..100 {
+            let x = 100 * i;
+            let mut hole = rect(x + 10, 10, x + 20, 20);
+            hole.reverse();
+            shapes.push(vec![rect(x, 0, x + 30, 30), hole]);
+            if i % 2 == 0 {
+                // the direction does not matter
+                let mut cover = rect(x + 5, 5, x + 25, 25);
+                cover.reverse();
+                shapes.push(vec![cover]);
+            }
+        }
+
+        let result = shapes.union_all(Default::default(), Default::default());
+
+        assert_eq!(result.len(), 100);
+        assert_eq!(result.iter().filter(|shape| shape.len() == 2).count(), 50);
+        assert_eq!(area(&result), 100 * 900 - 50 * 100);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::rng();
+        for _ in 0..4 {
+            let shapes: Vec<IntShape> = (0..500)
+                .map(|_| {
+                    let x = rng.random_range(0..2000);
+                    let y = rng.random_range(0..2000);
+                    let w = rng.random_range(1..100);
+                    let h = rng.random_range(1..100);
+                    vec![rect(x, y, x + w, y + h)]
+                })
+                .collect();
+
+            let result = shapes.union_all(Default::default(), Default::default());
+            let expected = single_union(&shapes);
+
+            assert_eq!(result.len(), expected.len());
+            assert_eq!(area(&result), area(&expected));
+        }
+    }
+
+    #[test]
+    fn test_options() {
+        let mut shapes = Vec::new();
+        for i in 0..100 {
+            let x = 20 * i;
+            shapes.push(vec![rect(x, 0, x + 15, 15)]);
+            shapes.push(vec![rect(x, 100, x + 2, 102)]);
+        }
+
+        let options = IntOverlayOptions {
+            min_output_area: 10,
+            output_direction: ContourDirection::Clockwise,
+            ..Default::default()
+        };
+        let result = shapes.union_all(options, Default::default());
+
+        assert_eq!(result.len(), 100);
+        assert!(result.iter().all(|shape| shape[0].unsafe_area() > 0));
+    }
+
+    #[test]
+    fn test_float() {
+        let shapes: Vec<_> = (0..200)
+            .map(|i| {
+                let x = 0.5 * i as f64;
+                vec![vec![[x, 0.0], [x + 1.0, 0.0], [x + 1.0, 1.0], [x, 1.0]]]
+            })
+            .collect();
+
+        let result = shapes.union_all();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0][0].len(), 4);
+    }
+}
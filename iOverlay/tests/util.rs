@@ -82,6 +82,7 @@ pub mod overlay {
         false
     }
 
+    #[allow(dead_code)]
     pub trait JsonPrint {
         fn json_print(&self) -> String;
     }
@@ -138,3 +139,20 @@ pub mod overlay {
     }
 }
 
+#[cfg(test)]
+pub mod shape {
+    use i_float::int::point::IntPoint;
+    use i_shape::int::path::IntPath;
+
+    /// An axis-aligned rectangle. For `x0 < x1` and `y0 < y1` it goes counter-clockwise,
+    /// the direction of an outer contour.
+    #[allow(dead_code)]
+    pub fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntPath {
+        vec![
+            IntPoint::new(x0, y0),
+            IntPoint::new(x1, y0),
+            IntPoint::new(x1, y1),
+            IntPoint::new(x0, y1),
+        ]
+    }
+}
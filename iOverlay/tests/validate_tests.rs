@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{ContourDirection, Overlay};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::validate::{Validate, ValidationIssue, VertexIndex};
+    use i_overlay::float::validate::FloatValidate;
+    use i_shape::int::path::IntPath;
+    use rand::Rng;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn vertex(shape: usize, contour: usize, vertex: usize) -> VertexIndex {
+        VertexIndex { shape, contour, vertex }
+    }
+
+    #[test]
+    fn test_valid() {
+        let shapes = [
+            vec![
+                path(&[[0, 0], [10, 0], [10, 10], [0, 10]]),
+                path(&[[2, 2], [2, 8], [8, 8], [8, 2]]),
+            ],
+            vec![path(&[[20, 0], [30, 0], [30, 10]])],
+        ];
+
+        assert!(shapes.validate(ContourDirection::CounterClockwise).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_and_spike() {
+        let shapes = [vec![path(&[[0, 0], [10, 0], [10, 0], [10, 10], [10, 5], [10, 12], [0, 10]])]];
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+
+        assert!(issues.contains(&ValidationIssue::DuplicatePoint { vertex: vertex(0, 0, 1), point: IntPoint::new(10, 0) }));
+        assert!(issues.contains(&ValidationIssue::Spike { vertex: vertex(0, 0, 3), point: IntPoint::new(10, 10) }));
+        assert!(issues.contains(&ValidationIssue::Spike { vertex: vertex(0, 0, 4), point: IntPoint::new(10, 5) }));
+        assert!(issues.iter().all(|issue| !matches!(issue, ValidationIssue::WrongOrientation { .. })));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let shapes = [vec![path(&[[0, 0], [10, 0], [10, 0], [0, 0]])]];
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+
+        assert!(issues.contains(&ValidationIssue::Degenerate { shape: 0, contour: 0 }));
+    }
+
+    #[test]
+    fn test_orientation_and_hole_outside() {
+        let shapes = [vec![
+            path(&[[0, 0], [0, 10], [10, 10], [10, 0]]),
+            path(&[[20, 20], [30, 20], [30, 30], [20, 30]]),
+        ]];
+
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::WrongOrientation { shape: 0, contour: 0 },
+                ValidationIssue::WrongOrientation { shape: 0, contour: 1 },
+                ValidationIssue::HoleOutside { shape: 0, contour: 1, point: IntPoint::new(20, 20) },
+            ]
+        );
+
+        let issues = shapes.validate(ContourDirection::Clockwise);
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::HoleOutside { shape: 0, contour: 1, point: IntPoint::new(20, 20) }]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_shapes() {
+        let shapes = [
+            vec![path(&[[0, 0], [10, 0], [10, 10], [0, 10]])],
+            vec![path(&[[5, 5], [15, 5], [15, 15], [5, 15]])],
+            vec![path(&[[10, 2], [20, 2], [20, 4], [10, 4]])],
+        ];
+
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+
+        let crossings: Vec<_> = issues
+            .iter()
+            .filter_map(|issue| match issue {
+                ValidationIssue::SelfIntersection { point, .. } => Some(*point),
+                _ => None,
+            })
+            .collect();
+        assert!(crossings.contains(&IntPoint::new(10, 5)));
+        assert!(crossings.contains(&IntPoint::new(5, 10)));
+
+        assert!(issues.contains(&ValidationIssue::OverlappingEdges { a: vertex(0, 0, 1), b: vertex(2, 0, 3) }));
+    }
+
+    #[test]
+    fn test_touch_reported_once() {
+        // the contour touches itself at (5, 0)
+        let shapes = [vec![path(&[[0, 0], [10, 0], [10, 10], [5, 0], [0, 10]])]];
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::SelfIntersection { a: vertex(0, 0, 0), b: vertex(0, 0, 3), point: IntPoint::new(5, 0) }]
+        );
+    }
+
+    #[test]
+    fn test_crossing_rounded_to_end() {
+        // the edge (100, -1)-(99, 2) crosses (0, 0)-(100, 0) at x = 99.67, the rounded point is the end (100, 0)
+        let mut contour = path(&[[0, 0], [100, 0], [110, 10], [100, -1], [99, 2], [50, 50]]);
+        for _ in 0..2 {
+            let issues = [vec![contour.clone()]].validate(ContourDirection::CounterClockwise);
+            let crossings = issues.iter().filter(|issue| matches!(issue, ValidationIssue::SelfIntersection { .. })).count();
+            assert!(crossings > 0, "{:?}", issues);
+            contour.reverse();
+        }
+    }
+
+    #[test]
+    fn test_float() {
+        let shapes = [vec![vec![[0.0, 0.0], [4.0, 4.0], [4.0, 0.0], [0.0, 4.0]]]];
+        let issues = shapes.validate(ContourDirection::CounterClockwise);
+
+        assert_eq!(issues.len(), 1);
+        if let ValidationIssue::SelfIntersection { point, .. } = issues[0] {
+            assert!((point[0] - 2.0f64).abs() < 1e-6);
+            assert!((point[1] - 2.0f64).abs() < 1e-6);
+        } else {
+            panic!("expected a self intersection");
+        }
+    }
+
+    #[test]
+    fn test_overlay_result_is_valid() {
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let subj: Vec<IntPath> = (0..3)
+                .map(|_| (0..6).map(|_| IntPoint::new(rng.random_range(-20..=20), rng.random_range(-20..=20))).collect())
+                .collect();
+            let clip: Vec<IntPath> = (0..3)
+                .map(|_| (0..6).map(|_| IntPoint::new(rng.random_range(-20..=20), rng.random_range(-20..=20))).collect())
+                .collect();
+
+            let result = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Xor, FillRule::EvenOdd);
+            let issues = result.validate(ContourDirection::CounterClockwise);
+
+            let is_bad = issues.iter().any(|issue| {
+                matches!(
+                    issue,
+                    ValidationIssue::Degenerate { .. }
+                        | ValidationIssue::DuplicatePoint { .. }
+                        | ValidationIssue::Spike { .. }
+                        | ValidationIssue::OverlappingEdges { .. }
+                        | ValidationIssue::WrongOrientation { .. }
+                        | ValidationIssue::HoleOutside { .. }
+                )
+            });
+            assert!(!is_bad, "{:?}", issues);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+mod util;
+
+#[cfg(all(test, feature = "wide"))]
+mod tests {
+    use crate::util::shape::rect;
+    use i_float::int::point::IntPoint;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::{ContourDirection, IntOverlayOptions, Overlay};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::validate::{Validate, ValidationIssue};
+    use i_overlay::wide::overlay::WideOverlay;
+    use i_overlay::wide::point::{WideContour, WidePoint, WideShapes};
+    use i_shape::int::path::IntPath;
+    use i_shape::int::shape::IntShapes;
+    use rand::Rng;
+
+    const RULES: [OverlayRule; 7] = [
+        OverlayRule::Subject,
+        OverlayRule::Clip,
+        OverlayRule::Intersect,
+        OverlayRule::Union,
+        OverlayRule::Difference,
+        OverlayRule::InverseDifference,
+        OverlayRule::Xor,
+    ];
+
+    const FILL_RULES: [FillRule; 4] = [FillRule::EvenOdd, FillRule::NonZero, FillRule::Positive, FillRule::Negative];
+
+    fn contour(points: &[[i64; 2]]) -> WideContour {
+        points.iter().map(|p| WidePoint::new(p[0], p[1])).collect()
+    }
+
+    fn to_wide(paths: &[IntPath]) -> Vec<WideContour> {
+        paths.iter().map(|path| path.iter().map(|&p| WidePoint::from(p)).collect()).collect()
+    }
+
+    fn area(contour: &WideContour) -> i128 {
+        let n = contour.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (contour[i], contour[(i + 1) % n]);
+                a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128
+            })
+            .sum()
+    }
+
+    /// Starts every contour from its smallest point and sorts holes and shapes.
+    fn normalize(shapes: WideShapes) -> WideShapes {
+        let mut shapes: WideShapes = shapes
+            .into_iter()
+            .map(|shape| {
+                let mut shape: Vec<WideContour> = shape
+                    .into_iter()
+                    .map(|mut contour| {
+                        let (m, _) = contour.iter().enumerate().min_by_key(|&(_, p)| p).unwrap();
+                        contour.rotate_left(m);
+                        contour
+                    })
+                    .collect();
+                shape[1..].sort();
+                shape
+            })
+            .collect();
+        shapes.sort();
+        shapes
+    }
+
+    fn normalize_int(shapes: IntShapes) -> WideShapes {
+        normalize(shapes.iter().map(|shape| to_wide(shape)).collect())
+    }
+
+    fn random_paths(rng: &mut impl Rng, count: usize, n: usize, range: i32) -> Vec<IntPath> {
+        (0..count)
+            .map(|_| (0..n).map(|_| IntPoint::new(rng.random_range(-range..=range), rng.random_range(-range..=range))).collect())
+            .collect()
+    }
+
+    fn random_rectilinear_paths(rng: &mut impl Rng, count: usize, n: usize, range: i32) -> Vec<IntPath> {
+        (0..count)
+            .map(|_| {
+                let xs: Vec<i32> = (0..n).map(|_| rng.random_range(-range..=range)).collect();
+                let ys: Vec<i32> = (0..n).map(|_| rng.random_range(-range..=range)).collect();
+                let mut path = Vec::with_capacity(2 * n);
+                for i in 0..n {
+                    path.push(IntPoint::new(xs[i], ys[i]));
+                    path.push(IntPoint::new(xs[(i + 1) % n], ys[i]));
+                }
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_square_hole() {
+        let subj = [contour(&[[0, 0], [10, 0], [10, 10], [0, 10]])];
+        let clip = [contour(&[[2, 2], [8, 2], [8, 8], [2, 8]])];
+
+        let result = WideOverlay::with_contours(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+        assert!(area(&result[0][0]) > 0);
+        assert!(area(&result[0][1]) < 0);
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let max = WidePoint::MAX_COORD;
+        let subj = [contour(&[[-max, -max], [max, -max], [max, max], [-max, max]])];
+        let clip = [contour(&[[-1, -max], [1, -max], [1, max], [-1, max]])];
+
+        let mut overlay = WideOverlay::with_contours(&subj, &clip);
+        let result = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(result.len(), 2);
+        let total: i128 = result.iter().map(|shape| area(&shape[0])).sum();
+        assert_eq!(total, 2 * 2 * (max as i128 - 1) * 2 * max as i128);
+    }
+
+    #[test]
+    fn test_millimetre_detail() {
+        // a global extent with a small notch that would vanish after rescaling to i32
+        let big = 1 << 55;
+        let subj = [contour(&[[0, 0], [big, 0], [big, big], [0, big]])];
+        let clip = [contour(&[[big - 3, big - 3], [big + 5, big - 3], [big + 5, big + 5], [big - 3, big + 5]])];
+
+        let result = WideOverlay::with_contours(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0][0].len(), 6);
+        assert!(result[0][0].contains(&WidePoint::new(big - 3, big - 3)));
+    }
+
+    #[test]
+    fn test_large_cross() {
+        let big = 1 << 61;
+        let subj = [contour(&[[-big, -1], [big, -1], [big, 1], [-big, 1]])];
+        let clip = [contour(&[[-1, -big], [1, -big], [1, big], [-1, big]])];
+
+        let result = WideOverlay::with_contours(&subj, &clip).overlay(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0][0].len(), 12);
+    }
+
+    #[test]
+    fn test_clockwise_output() {
+        let subj = [contour(&[[0, 0], [10, 0], [10, 10], [0, 10]])];
+        let clip = [contour(&[[2, 2], [8, 2], [8, 8], [2, 8]])];
+        let options = IntOverlayOptions {
+            output_direction: ContourDirection::Clockwise,
+            ..Default::default()
+        };
+
+        let result = WideOverlay::with_contours_custom(&subj, &clip, options, Default::default())
+            .overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        assert!(area(&result[0][0]) < 0);
+        assert!(area(&result[0][1]) > 0);
+    }
+
+    #[test]
+    fn test_graph_reuse() {
+        let subj = [contour(&[[0, 0], [10, 0], [10, 10], [0, 10]])];
+        let clip = [contour(&[[5, 5], [15, 5], [15, 15], [5, 15]])];
+
+        let mut overlay = WideOverlay::with_contours(&subj, &clip);
+        let graph = overlay.build_graph_view(FillRule::NonZero);
+
+        for rule in RULES {
+            let expected = WideOverlay::with_contours(&subj, &clip).overlay(rule, FillRule::NonZero);
+            assert_eq!(graph.extract_shapes(rule), expected);
+        }
+    }
+
+    #[test]
+    fn test_same_as_int() {
+        // all crossings of axis-aligned edges lie on the grid, so both pipelines must give the same result
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let subj = random_rectilinear_paths(&mut rng, 2, 4, 10);
+            let clip = random_rectilinear_paths(&mut rng, 2, 4, 10);
+            let wide_subj = to_wide(&subj);
+            let wide_clip = to_wide(&clip);
+
+            let fill_rule = FILL_RULES[rng.random_range(0..FILL_RULES.len())];
+            let rule = RULES[rng.random_range(0..RULES.len())];
+
+            let expected = Overlay::with_contours(&subj, &clip).overlay(rule, fill_rule);
+            let result = WideOverlay::with_contours(&wide_subj, &wide_clip).overlay(rule, fill_rule);
+
+            assert_eq!(
+                normalize(result),
+                normalize_int(expected),
+                "subj: {:?}, clip: {:?}, {:?}, {:?}",
+                subj,
+                clip,
+                rule,
+                fill_rule
+            );
+        }
+    }
+
+    #[test]
+    fn test_many_segments() {
+        // long stripes stacked one over another overlap by x, the split must not test all of them in pairs
+        let subj: Vec<IntPath> = (0..2000).map(|i| rect(-100_000, 4 * i, 100_000, 4 * i + 2)).collect();
+        let clip: Vec<IntPath> = (0..2).map(|i| rect(100_000 * i - 50_000, -1, 100_000 * i - 48_000, 8001)).collect();
+        let expected = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Xor, FillRule::NonZero);
+        let result = WideOverlay::with_contours(&to_wide(&subj), &to_wide(&clip)).overlay(OverlayRule::Xor, FillRule::NonZero);
+        assert_eq!(normalize(result), normalize_int(expected));
+
+        let mut rng = rand::rng();
+        for _ in 0..20 {
+            let subj = random_rectilinear_paths(&mut rng, 10, 6, 1000);
+            let clip = random_rectilinear_paths(&mut rng, 10, 6, 1000);
+            let expected = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Union, FillRule::EvenOdd);
+            let result = WideOverlay::with_contours(&to_wide(&subj), &to_wide(&clip)).overlay(OverlayRule::Union, FillRule::EvenOdd);
+            assert_eq!(normalize(result), normalize_int(expected));
+        }
+    }
+
+    #[test]
+    fn test_many_holes() {
+        // every hole is bound to its outer contour by the scan tree of the extraction
+        let mut subj = Vec::new();
+        for i in 0..100 {
+            for j in 0..100 {
+                let (x, y) = (10 * i, 10 * j);
+                subj.push(rect(x, y, x + 8, y + 8));
+                let mut hole = rect(x + 2, y + 2, x + 6, y + 6);
+                hole.reverse();
+                subj.push(hole);
+            }
+        }
+        let expected = Overlay::with_contours(&subj, &[]).overlay(OverlayRule::Subject, FillRule::NonZero);
+        let result = WideOverlay::with_contours(&to_wide(&subj), &[]).overlay(OverlayRule::Subject, FillRule::NonZero);
+        assert_eq!(result.len(), 10_000);
+        assert!(result.iter().all(|shape| shape.len() == 2));
+        assert_eq!(normalize(result), normalize_int(expected));
+    }
+
+    #[test]
+    fn test_random_is_valid() {
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let subj = to_wide(&random_paths(&mut rng, 2, 5, 10));
+            let clip = to_wide(&random_paths(&mut rng, 2, 5, 10));
+
+            let fill_rule = FILL_RULES[rng.random_range(0..FILL_RULES.len())];
+            let rule = RULES[rng.random_range(0..RULES.len())];
+
+            let result = WideOverlay::with_contours(&subj, &clip).overlay(rule, fill_rule);
+            let int_result: IntShapes = result
+                .iter()
+                .map(|shape| {
+                    shape
+                        .iter()
+                        .map(|contour| contour.iter().map(|p| IntPoint::new(p.x as i32, p.y as i32)).collect())
+                        .collect()
+                })
+                .collect();
+
+            let issues = int_result.validate(ContourDirection::CounterClockwise);
+            let is_bad = issues.iter().any(|issue| {
+                matches!(
+                    issue,
+                    ValidationIssue::Degenerate { .. }
+                        | ValidationIssue::DuplicatePoint { .. }
+                        | ValidationIssue::Spike { .. }
+                        | ValidationIssue::OverlappingEdges { .. }
+                        | ValidationIssue::WrongOrientation { .. }
+                        | ValidationIssue::HoleOutside { .. }
+                )
+            });
+            assert!(!is_bad, "subj: {:?}, clip: {:?}, {:?}", subj, clip, issues);
+        }
+    }
+
+    #[test]
+    fn test_scaled_random() {
+        // the same geometry far away from the origin keeps the area
+        let mut rng = rand::rng();
+        let shift = 1 << 60;
+        for _ in 0..200 {
+            let subj = random_paths(&mut rng, 2, 6, 1000);
+            let clip = random_paths(&mut rng, 2, 6, 1000);
+            let wide_subj = to_wide(&subj);
+            let wide_clip = to_wide(&clip);
+            let shifted = |paths: &[WideContour]| -> Vec<WideContour> {
+                paths.iter().map(|path| path.iter().map(|p| WidePoint::new(p.x + shift, p.y - shift)).collect()).collect()
+            };
+
+            let result = WideOverlay::with_contours(&wide_subj, &wide_clip).overlay(OverlayRule::Xor, FillRule::EvenOdd);
+            let moved = WideOverlay::with_contours(&shifted(&wide_subj), &shifted(&wide_clip))
+                .overlay(OverlayRule::Xor, FillRule::EvenOdd);
+
+            let area_0: i128 = result.iter().flatten().map(area).sum();
+            let area_1: i128 = moved.iter().flatten().map(area).sum();
+            assert_eq!(area_0, area_1);
+        }
+    }
+}